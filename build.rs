@@ -0,0 +1,77 @@
+//! Une los bancos de `src/data` (un fichero o un directorio por lenguaje) en
+//! `$OUT_DIR/quiz_questions_<lenguaje>.yaml`, que `data.rs` integra en el binario,
+//! y genera `$OUT_DIR/bank_images.rs` con las imágenes de `src/data/images`.
+
+#[path = "src/bank_layout.rs"]
+#[allow(dead_code)]
+mod bank_layout;
+
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/data");
+    println!("cargo:rerun-if-changed=src/bank_layout.rs");
+
+    let data_dir = Path::new("src/data");
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR"));
+
+    for (key, _) in bank_layout::LANGUAGE_DIRS {
+        let yaml = match bank_layout::read_bank(data_dir, key) {
+            Ok(Some((_, yaml))) => yaml,
+            Ok(None) => bank_layout::EMPTY_BANK.to_string(),
+            Err(err) => panic!("Banco de preguntas inválido: {err}"),
+        };
+        let out = out_dir.join(bank_layout::bank_file_name(key));
+        std::fs::write(&out, yaml)
+            .unwrap_or_else(|e| panic!("No se pudo escribir {}: {e}", out.display()));
+    }
+
+    write_bank_images(data_dir, &out_dir.join("bank_images.rs"));
+}
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+
+/// `static EMBEDDED_IMAGES: &[(&str, &[u8])]` con cada imagen de `data/images`,
+/// indexada por su ruta relativa a `data` (`images/...`), que es como la citan los enunciados.
+fn write_bank_images(data_dir: &Path, out: &Path) {
+    let mut images = Vec::new();
+    collect_images(&data_dir.join("images"), &mut images);
+    images.sort();
+
+    let mut code = String::from("static EMBEDDED_IMAGES: &[(&str, &[u8])] = &[\n");
+    for path in images {
+        let rel = path
+            .strip_prefix(data_dir)
+            .expect("imagen fuera de src/data")
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let abs = std::fs::canonicalize(&path)
+            .unwrap_or_else(|e| panic!("No se pudo leer {}: {e}", path.display()));
+        code.push_str(&format!(
+            "    ({rel:?}, include_bytes!({:?})),\n",
+            abs.display().to_string()
+        ));
+    }
+    code.push_str("];\n");
+    std::fs::write(out, code)
+        .unwrap_or_else(|e| panic!("No se pudo escribir {}: {e}", out.display()));
+}
+
+fn collect_images(dir: &Path, images: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_images(&path, images);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            images.push(path);
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use super::*;
-use crate::code_utils::normalize_code;
+use crate::blanks::wrong_blanks;
+use crate::diagnostics;
+use crate::equivalence::grade_normalize;
+use crate::history::{self, Attempt, Verdict};
 use crate::judge::judge_c::{
     JudgeResult, format_judge_message, grade_c_question, should_use_judge,
 };
@@ -9,15 +12,15 @@ use crate::judge::judge_pseudo::{CJudge, PseudoConfig, run_pseudo_tests};
 use crate::judge::judge_python::grade_python_question;
 use crate::judge::judge_remote::grade_remote_question;
 use crate::judge::judge_rust::grade_rust_question;
-use crate::model::GradingMode;
+use crate::model::{GradingMode, QuestionKind};
+use crate::parsons;
+use crate::predict::{computed_output, grade_prediction, judged_question};
+use crate::requirements::violation as requirement_violation;
+use crate::review::{self, ReviewCard};
+use crate::token_diff::REVEAL_AFTER_FAILS;
 
 impl QuizApp {
     pub fn procesar_respuesta(&mut self, respuesta: &str) {
-        if respuesta.trim().is_empty() {
-            self.message = "⚠ Debes escribir una respuesta antes de enviar.".into();
-            return;
-        }
-
         if self.remote_judge_pending.is_some() {
             self.message =
                 "⏳ Ya hay una evaluación remota en progreso. Espera el resultado.".into();
@@ -41,6 +44,33 @@ impl QuizApp {
 
         let q = &self.quiz.modules[cw].levels[cl].questions[ci];
 
+        // Predecir que no se imprime nada es una respuesta válida.
+        if respuesta.trim().is_empty() && q.kind != QuestionKind::PredictOutput {
+            self.message = "⚠ Debes escribir una respuesta antes de enviar.".into();
+            return;
+        }
+
+        // `requires`, `forbids` y `max_lines` se comprueban antes de ejecutar nada.
+        if let Some(message) = requirement_violation(q, respuesta) {
+            let result = JudgeResult::RequirementViolation { message };
+            self.apply_grading_result(cw, cl, ci, result);
+            return;
+        }
+
+        // "Predice la salida" con salida calculada: se envía el snippet al juez del lenguaje.
+        let judged = judged_question(q);
+        let (q, respuesta) = match &judged {
+            Some(judged) => (judged, judged.answer.as_str()),
+            None => (q, respuesta),
+        };
+
+        // Sin el compilador no se puede corregir, pero el resto del curso sigue disponible.
+        if let Some(reason) = self.judge_unavailable_reason(q) {
+            self.message = format!("⚠ Esta pregunta no se puede corregir aquí: {reason}");
+            return;
+        }
+        self.submitted_at = Some(history::now_ms());
+
         #[cfg(target_arch = "wasm32")]
         if q.uses_judge_remote()
             || should_use_judge(q)
@@ -49,7 +79,8 @@ impl QuizApp {
             || matches!(q.mode, Some(GradingMode::JudgeRust))
             || matches!(q.mode, Some(GradingMode::JudgePython))
         {
-            self.start_remote_judge_submission(cw, cl, ci, respuesta.to_string());
+            let question = q.clone();
+            self.start_remote_judge_submission(cw, cl, ci, question, respuesta.to_string());
             return;
         }
 
@@ -57,9 +88,82 @@ impl QuizApp {
         self.apply_grading_result(cw, cl, ci, grading_result);
     }
 
+    /// Corrige la pregunta de opciones actual con las opciones marcadas.
+    pub fn procesar_eleccion(&mut self) {
+        if self.progress().selected_options.is_empty() {
+            self.message = "⚠ Debes marcar una opción antes de enviar.".into();
+            return;
+        }
+        let Some((cw, cl, ci)) = self.current_position() else {
+            self.message = "Error interno: no hay pregunta seleccionada.".into();
+            return;
+        };
+
+        self.submitted_at = Some(history::now_ms());
+        let selected = self.progress().selected_options.clone();
+        let grade = self.quiz.modules[cw].levels[cl].questions[ci].grade_choice(&selected);
+        self.apply_grading_result(cw, cl, ci, client_side_result(grade.correct));
+        for feedback in grade.feedback {
+            self.message.push_str(&format!("\n• {feedback}"));
+        }
+    }
+
+    /// Corrige la pregunta `fill_blanks` actual hueco a hueco.
+    pub fn procesar_huecos(&mut self) {
+        if self
+            .progress()
+            .blank_inputs
+            .values()
+            .all(|v| v.trim().is_empty())
+        {
+            self.message = "⚠ Debes rellenar los huecos antes de enviar.".into();
+            return;
+        }
+        let Some((cw, cl, ci)) = self.current_position() else {
+            self.message = "Error interno: no hay pregunta seleccionada.".into();
+            return;
+        };
+
+        self.submitted_at = Some(history::now_ms());
+        let wrong = wrong_blanks(
+            &self.quiz.modules[cw].levels[cl].questions[ci],
+            &self.progress().blank_inputs,
+        );
+        self.apply_grading_result(cw, cl, ci, client_side_result(wrong.is_empty()));
+        if !wrong.is_empty() {
+            self.message = format!("❌ Huecos incorrectos: {}", wrong.join(", "));
+        }
+    }
+
+    /// Corrige el problema de Parsons actual: con juez, ejecutando el programa montado;
+    /// si no, comparando el orden de las líneas.
+    pub fn procesar_parsons(&mut self) {
+        if self.progress().parsons_answer.is_empty() {
+            self.message = "⚠ Debes colocar alguna línea antes de enviar.".into();
+            return;
+        }
+        let Some((cw, cl, ci)) = self.current_position() else {
+            self.message = "Error interno: no hay pregunta seleccionada.".into();
+            return;
+        };
+
+        let question = &self.quiz.modules[cw].levels[cl].questions[ci];
+        let answer = self.progress().parsons_answer.clone();
+        if parsons::uses_judge(question) {
+            let source = parsons::assemble(question, &answer);
+            self.procesar_respuesta(&source);
+        } else {
+            self.submitted_at = Some(history::now_ms());
+            let correct = parsons::is_correct_order(question, &answer);
+            self.apply_grading_result(cw, cl, ci, client_side_result(correct));
+        }
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn grade_question_sync(&self, q: &crate::model::Question, respuesta: &str) -> JudgeResult {
-        if q.uses_judge_pseudo() {
+        if q.kind == QuestionKind::PredictOutput {
+            grade_prediction(respuesta, q.expected_output.as_deref().unwrap_or_default())
+        } else if q.uses_judge_pseudo() {
             run_pseudo_tests(respuesta, &q.tests, &PseudoConfig::default(), &CJudge)
         } else if matches!(q.mode, Some(GradingMode::JudgeKotlin)) {
             grade_kotlin_question(q, respuesta)
@@ -74,25 +178,15 @@ impl QuizApp {
         } else if should_use_judge(q) {
             grade_c_question(q, respuesta)
         } else {
-            let user_code = normalize_code(respuesta);
-            let answer_code = normalize_code(&q.answer);
-            if user_code == answer_code {
-                JudgeResult::Accepted
-            } else {
-                JudgeResult::WrongAnswer {
-                    test_index: 0,
-                    input: String::new(),
-                    expected: String::new(),
-                    received: String::new(),
-                    diff: String::new(),
-                }
-            }
+            grade_normalize(q, respuesta, self.reveals_expected(q))
         }
     }
 
     #[cfg(target_arch = "wasm32")]
     fn grade_question_sync(&self, q: &crate::model::Question, respuesta: &str) -> JudgeResult {
-        if q.uses_judge_pseudo() {
+        if q.kind == QuestionKind::PredictOutput {
+            grade_prediction(respuesta, q.expected_output.as_deref().unwrap_or_default())
+        } else if q.uses_judge_pseudo() {
             run_pseudo_tests(respuesta, &q.tests, &PseudoConfig::default(), &CJudge)
         } else if matches!(q.mode, Some(GradingMode::JudgeKotlin)) {
             grade_kotlin_question(q, respuesta)
@@ -105,22 +199,16 @@ impl QuizApp {
         } else if should_use_judge(q) {
             grade_c_question(q, respuesta)
         } else {
-            let user_code = normalize_code(respuesta);
-            let answer_code = normalize_code(&q.answer);
-            if user_code == answer_code {
-                JudgeResult::Accepted
-            } else {
-                JudgeResult::WrongAnswer {
-                    test_index: 0,
-                    input: String::new(),
-                    expected: String::new(),
-                    received: String::new(),
-                    diff: String::new(),
-                }
-            }
+            grade_normalize(q, respuesta, self.reveals_expected(q))
         }
     }
 
+    /// ¿Puede el veredicto enseñar lo esperado? Solo cuando, con este fallo, ya se
+    /// podría ver la solución, y nunca en el examen.
+    fn reveals_expected(&self, q: &crate::model::Question) -> bool {
+        self.exam.is_none() && q.fails + 1 >= REVEAL_AFTER_FAILS
+    }
+
     fn apply_grading_result(
         &mut self,
         cw: usize,
@@ -128,7 +216,18 @@ impl QuizApp {
         ci: usize,
         grading_result: JudgeResult,
     ) {
-        let correcta = matches!(grading_result, JudgeResult::Accepted);
+        let grading_result = self.resolve_predicted_output(cw, cl, ci, grading_result);
+        self.record_diagnostics(cw, cl, ci, &grading_result);
+        self.record_attempt(cw, cl, ci, &grading_result);
+        if self.exam.is_some() {
+            self.apply_exam_result(&grading_result);
+            return;
+        }
+        if self.review.is_some() {
+            self.apply_review_result(cw, cl, ci, &grading_result);
+            return;
+        }
+        let correcta = matches!(grading_result, JudgeResult::Accepted { .. });
 
         {
             let q = &mut self.quiz.modules[cw].levels[cl].questions[ci];
@@ -143,7 +242,7 @@ impl QuizApp {
         let question_id = self.quiz.modules[cw].levels[cl].questions[ci].id.clone();
         let need_update_shown = {
             let prog = self.progress();
-            !prog.shown_this_round.contains(&(cl, ci))
+            !prog.shown_this_round.contains(&question_id)
         };
 
         let mut mark_pending = false;
@@ -151,12 +250,13 @@ impl QuizApp {
         {
             let prog = self.progress_mut();
             if need_update_shown {
-                prog.shown_this_round.push((cl, ci));
+                prog.shown_this_round.push(question_id.clone());
             }
             if correcta {
-                if let Some(id) = &question_id {
-                    prog.completed_ids.insert(id.clone());
-                }
+                prog.review_cards
+                    .entry(question_id.clone())
+                    .or_insert_with(|| ReviewCard::new(review::today()));
+                prog.completed_ids.insert(question_id);
                 prog.input.clear();
                 mark_pending = true;
                 curr_module = prog.current_module;
@@ -190,21 +290,105 @@ impl QuizApp {
         if correcta {
             self.update_input_prefill();
         }
-        self.message = if correcta {
-            "✅ ¡Correcto!".into()
+        self.message = verdict_message(&grading_result);
+    }
+
+    /// Guarda el envío en el historial de la pregunta. Los del examen no: el
+    /// historial es del recorrido normal, como las estadísticas.
+    fn record_attempt(&mut self, cw: usize, cl: usize, ci: usize, result: &JudgeResult) {
+        let now = history::now_ms();
+        let started = self.submitted_at.take().unwrap_or(now);
+        if self.exam.is_some() {
+            return;
+        }
+        let q = &self.quiz.modules[cw].levels[cl].questions[ci];
+        let attempt = Attempt {
+            timestamp: (now / 1_000.0) as u64,
+            code: self.submitted_answer(q),
+            verdict: Verdict::of(result),
+            duration_ms: (now - started).max(0.0) as u64,
+        };
+        let id = q.id.clone();
+        history::push(self.progress_mut().history.entry(id).or_default(), attempt);
+    }
+
+    /// Lo que el alumno envió en `q`, como texto: el código o la predicción, las
+    /// opciones marcadas, los huecos rellenos o el programa de Parsons montado.
+    fn submitted_answer(&self, q: &crate::model::Question) -> String {
+        let progress = self.progress();
+        if q.is_choice() {
+            let options = q.choice_options();
+            progress
+                .selected_options
+                .iter()
+                .filter_map(|&i| options.get(i))
+                .map(|option| format!("• {}", option.text))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else if q.kind == QuestionKind::FillBlanks {
+            let mut blanks: Vec<_> = progress.blank_inputs.iter().collect();
+            blanks.sort();
+            blanks
+                .into_iter()
+                .map(|(name, value)| format!("{name} = {value}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else if q.kind == QuestionKind::Parsons {
+            parsons::assemble(q, &progress.parsons_answer)
         } else {
-            match &grading_result {
-                JudgeResult::WrongAnswer { test_index, .. } if *test_index == 0 => {
-                    "❌ Incorrecto. Intenta de nuevo.".into()
-                }
-                _ => format_judge_message(&grading_result),
-            }
+            progress.input.clone()
+        }
+    }
+
+    /// Guarda los errores de compilación para marcarlos en el editor.
+    fn record_diagnostics(&mut self, cw: usize, cl: usize, ci: usize, result: &JudgeResult) {
+        let q = &self.quiz.modules[cw].levels[cl].questions[ci];
+        // Tras un acierto el editor se vacía: solo se marcan los errores.
+        let text = match result {
+            JudgeResult::CompileError { stderr } => stderr,
+            _ => "",
         };
+        let found = diagnostics::parse(q.language, text);
+        if found.is_empty() {
+            self.diagnostics.remove(&q.id);
+        } else {
+            self.diagnostics.insert(q.id.clone(), found);
+        }
+    }
+
+    /// En las `predict_output` con `compute_output` el juez devuelve la salida real
+    /// del snippet: se guarda (para la solución) y se compara con la predicción.
+    fn resolve_predicted_output(
+        &mut self,
+        cw: usize,
+        cl: usize,
+        ci: usize,
+        result: JudgeResult,
+    ) -> JudgeResult {
+        let q = &self.quiz.modules[cw].levels[cl].questions[ci];
+        if q.kind != QuestionKind::PredictOutput || !q.compute_output {
+            return result;
+        }
+        match computed_output(result) {
+            Ok(output) => {
+                let id = q.id.clone();
+                let verdict = grade_prediction(&self.progress().input, &output);
+                self.computed_outputs.insert(id, output);
+                verdict
+            }
+            Err(result) => result,
+        }
     }
 
     #[cfg(target_arch = "wasm32")]
-    fn start_remote_judge_submission(&mut self, cw: usize, cl: usize, ci: usize, source: String) {
-        let question = self.quiz.modules[cw].levels[cl].questions[ci].clone();
+    fn start_remote_judge_submission(
+        &mut self,
+        cw: usize,
+        cl: usize,
+        ci: usize,
+        question: crate::model::Question,
+        source: String,
+    ) {
         let (tx, rx) = std::sync::mpsc::channel::<JudgeResult>();
 
         self.remote_judge_pending = Some(PendingRemoteJudge { cw, cl, ci });
@@ -236,22 +420,32 @@ impl QuizApp {
     }
 
     pub fn saltar_pregunta(&mut self) {
+        if self.exam.is_some() {
+            self.siguiente_pregunta_examen();
+            self.message.clear();
+            return;
+        }
+        if self.review.is_some() {
+            self.saltar_repaso();
+            return;
+        }
         let (cw, cl, ci) = match self.current_position() {
             Some(pos) => pos,
             None => return,
         };
 
-        {
+        let question_id = {
             let q = &mut self.quiz.modules[cw].levels[cl].questions[ci];
             q.skips += 1;
             q.attempts += 1;
             q.saw_solution = false;
-        }
+            q.id.clone()
+        };
 
         {
             let prog = self.progress_mut();
-            if !prog.shown_this_round.contains(&(cl, ci)) {
-                prog.shown_this_round.push((cl, ci));
+            if !prog.shown_this_round.contains(&question_id) {
+                prog.shown_this_round.push(question_id);
             }
         }
 
@@ -272,6 +466,10 @@ impl QuizApp {
     /// Marca la solución vista y avanza a la siguiente pendiente dentro del nivel;
     /// si era la última, completa nivel/semana y puede ir al resumen.
     pub fn avanzar_a_siguiente_pregunta(&mut self) {
+        if self.review.is_some() {
+            self.repaso_con_solucion();
+            return;
+        }
         // 1) Extraer índices actuales
         let (cw, cl, ci) = match self.current_position() {
             Some(pos) => pos,
@@ -313,9 +511,7 @@ impl QuizApp {
         for lvl in &mut self.quiz.modules[wi].levels {
             for q in &mut lvl.questions {
                 if q.language == lang {
-                    if let Some(id) = q.mark_done_test() {
-                        ids.push(id);
-                    }
+                    ids.push(q.mark_done_test());
                 }
             }
         }
@@ -354,9 +550,7 @@ impl QuizApp {
         let mut ids = Vec::new();
         for q in &mut self.quiz.modules[wi].levels[li].questions {
             if q.language == lang {
-                if let Some(id) = q.mark_done_test() {
-                    ids.push(id);
-                }
+                ids.push(q.mark_done_test());
             }
         }
 
@@ -389,12 +583,8 @@ impl QuizApp {
 
         for (level_idx, level) in module.levels.iter().enumerate() {
             for (q_idx, q) in level.questions.iter().enumerate() {
-                if q.language == language {
-                    if let Some(id) = &q.id {
-                        if !self.progress().completed_ids.contains(id) {
-                            return Some((level_idx, q_idx));
-                        }
-                    }
+                if q.language == language && !self.progress().completed_ids.contains(&q.id) {
+                    return Some((level_idx, q_idx));
                 }
             }
         }
@@ -418,28 +608,22 @@ impl QuizApp {
 
         // Busca una pregunta pendiente no mostrada aún en esta ronda
         for (q_idx, q) in level.questions.iter().enumerate() {
-            if q.language == language {
-                if let Some(id) = &q.id {
-                    if !self.progress().completed_ids.contains(id)
-                        && !shown.contains(&(level_idx, q_idx))
-                    {
-                        // Marca como mostrada en esta ronda
-                        let progress = self.progress_mut();
-                        progress.shown_this_round.push((level_idx, q_idx));
-                        return Some(q_idx);
-                    }
-                }
+            if q.language == language
+                && !self.progress().completed_ids.contains(&q.id)
+                && !shown.contains(&q.id)
+            {
+                // Marca como mostrada en esta ronda
+                let progress = self.progress_mut();
+                progress.shown_this_round.push(q.id.clone());
+                return Some(q_idx);
             }
         }
 
         // Si todas ya fueron mostradas en esta ronda pero aún hay pendientes, arranca ronda nueva
-        let hay_pendientes = level.questions.iter().enumerate().any(|(_q_idx, q)| {
-            q.language == language
-                && q.id
-                    .as_ref()
-                    .map(|id| !self.progress().completed_ids.contains(id))
-                    .unwrap_or(false)
-        });
+        let hay_pendientes = level
+            .questions
+            .iter()
+            .any(|q| q.language == language && !self.progress().completed_ids.contains(&q.id));
 
         if hay_pendientes {
             {
@@ -450,17 +634,46 @@ impl QuizApp {
 
             // Busca de nuevo (ahora con shown_this_round vacío)
             for (q_idx, q) in level.questions.iter().enumerate() {
-                if q.language == language {
-                    if let Some(id) = &q.id {
-                        if !self.progress().completed_ids.contains(id) {
-                            let progress = self.progress_mut();
-                            progress.shown_this_round.push((level_idx, q_idx));
-                            return Some(q_idx);
-                        }
-                    }
+                if q.language == language && !self.progress().completed_ids.contains(&q.id) {
+                    let progress = self.progress_mut();
+                    progress.shown_this_round.push(q.id.clone());
+                    return Some(q_idx);
                 }
             }
         }
         None
     }
 }
+
+/// Mensaje para el alumno; los fallos corregidos en el cliente (`test_index: 0`)
+/// no tienen caso de prueba que enseñar.
+pub(super) fn verdict_message(result: &JudgeResult) -> String {
+    match result {
+        JudgeResult::WrongAnswer {
+            test_index: 0,
+            diff,
+            ..
+        } if !diff.is_empty() => format!("❌ Incorrecto.\n\n{diff}"),
+        JudgeResult::WrongAnswer { test_index, .. } if *test_index == 0 => {
+            "❌ Incorrecto. Intenta de nuevo.".into()
+        }
+        _ => format_judge_message(result),
+    }
+}
+
+/// Resultado de las preguntas que se corrigen en el cliente (opciones, huecos...).
+fn client_side_result(correct: bool) -> JudgeResult {
+    if correct {
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
+    } else {
+        JudgeResult::WrongAnswer {
+            test_index: 0,
+            input: String::new(),
+            expected: String::new(),
+            received: String::new(),
+            diff: String::new(),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+impl QuizApp {
+    /// En web descarga (una sola vez) los bancos de `banks_url` antes de elegir lenguaje.
+    /// En escritorio los ficheros se leen al seleccionar el lenguaje.
+    pub fn ensure_banks_prefetch(&mut self) {
+        if self.banks_prefetch_launched {
+            return;
+        }
+        self.banks_prefetch_launched = true;
+
+        #[cfg(target_arch = "wasm32")]
+        if crate::data::banks_url().is_some() {
+            let (tx, rx) = std::sync::mpsc::channel::<()>();
+            self.banks_prefetch_rx = Some(rx);
+            wasm_bindgen_futures::spawn_local(async move {
+                crate::data::prefetch_remote_banks().await;
+                let _ = tx.send(());
+            });
+        }
+    }
+
+    pub fn poll_banks_prefetch(&mut self) {
+        let done = self
+            .banks_prefetch_rx
+            .as_ref()
+            .is_some_and(|rx| rx.try_recv().is_ok());
+        if done {
+            self.banks_prefetch_rx = None;
+        }
+    }
+
+    pub fn is_banks_prefetch_pending(&self) -> bool {
+        self.banks_prefetch_rx.is_some()
+    }
+
+    /// Descarta el banco externo que falló y usa el integrado en el binario.
+    pub fn usar_banco_integrado(&mut self) {
+        let lang = self.selected_language.unwrap_or(Language::C);
+        let quiz = read_embedded_questions(lang);
+        self.aplicar_banco(lang, quiz);
+    }
+
+    /// Carga el banco externo descartando solo las preguntas que no se pueden leer.
+    /// Si el fallo no está en una pregunta concreta, se sigue mostrando el error.
+    pub fn cargar_banco_omitiendo_rotas(&mut self) {
+        let lang = self.selected_language.unwrap_or(Language::C);
+        match load_questions_skipping_broken(lang) {
+            Ok((quiz, skipped)) => {
+                self.aplicar_banco(lang, quiz);
+                self.skipped_questions = skipped;
+            }
+            Err(err) => self.bank_error = Some(err),
+        }
+    }
+
+    pub fn reintentar_banco(&mut self) {
+        let lang = self.selected_language.unwrap_or(Language::C);
+        self.seleccionar_lenguaje(lang);
+    }
+}
@@ -0,0 +1,78 @@
+use super::*;
+use crate::judge::judge_c::should_use_judge;
+use crate::judge::judge_capabilities::JudgeCapabilities;
+use crate::model::GradingMode;
+
+impl QuizApp {
+    /// Lanza (una sola vez) la comprobación de qué lenguajes se pueden evaluar:
+    /// en escritorio detecta los toolchains locales, en web pregunta al judge remoto.
+    pub fn ensure_capabilities_check(&mut self) {
+        if self.capabilities_check_launched {
+            return;
+        }
+        self.capabilities_check_launched = true;
+
+        let (tx, rx) = std::sync::mpsc::channel::<Option<JudgeCapabilities>>();
+        self.capabilities_rx = Some(rx);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(move || {
+            let caps = crate::judge::judge_capabilities::detect_local_capabilities();
+            let _ = tx.send(Some(caps));
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        wasm_bindgen_futures::spawn_local(async move {
+            // Si el judge no responde no deshabilitamos nada: el error saldrá al evaluar.
+            let caps = crate::judge::judge_remote::fetch_capabilities().await.ok();
+            let _ = tx.send(caps);
+        });
+    }
+
+    pub fn poll_capabilities(&mut self) {
+        let received = self
+            .capabilities_rx
+            .as_ref()
+            .and_then(|rx| rx.try_recv().ok());
+
+        if let Some(caps) = received {
+            self.capabilities = caps;
+            self.capabilities_rx = None;
+        }
+    }
+
+    pub fn is_capabilities_check_pending(&self) -> bool {
+        self.capabilities_rx.is_some()
+    }
+
+    /// Motivo por el que `lang` no se puede evaluar, si se conoce.
+    pub fn language_unavailable_reason(&self, lang: Language) -> Option<String> {
+        self.capabilities.as_ref()?.unavailable_reason(lang)
+    }
+
+    /// Motivo por el que `q` no se puede corregir aquí. Sólo afecta a las preguntas
+    /// que ejecutan código con un toolchain que falta; las `normalize` no lo necesitan.
+    pub fn judge_unavailable_reason(&self, q: &Question) -> Option<String> {
+        self.language_unavailable_reason(judge_language(q)?)
+    }
+}
+
+/// Lenguaje del judge que corrige `q`, o `None` si no se ejecuta nada (o, en
+/// escritorio, si lo corrige el judge remoto).
+fn judge_language(q: &Question) -> Option<Language> {
+    if q.uses_judge_pseudo() {
+        return Some(Language::C);
+    }
+    match q.mode {
+        Some(GradingMode::JudgeKotlin) => Some(Language::Kotlin),
+        Some(GradingMode::JudgeJava) => Some(Language::Java),
+        Some(GradingMode::JudgeRust) => Some(Language::Rust),
+        Some(GradingMode::JudgePython) => Some(Language::Python),
+        #[cfg(target_arch = "wasm32")]
+        _ if q.uses_judge_remote() => Some(q.language),
+        #[cfg(not(target_arch = "wasm32"))]
+        _ if q.uses_judge_remote() => None,
+        _ if should_use_judge(q) => Some(Language::C),
+        _ => None,
+    }
+}
@@ -19,10 +19,8 @@ impl QuizApp {
                 .unwrap_or(false);
 
             if has_questions {
-                let progress = self.progress_mut();
-                if next_module > progress.max_unlocked_module {
-                    progress.max_unlocked_module = next_module;
-                }
+                let key = self.quiz.modules[next_module].key();
+                self.progress_mut().unlocked_modules.insert(key);
             }
             self.recalculate_unlocked_modules(); // ¡Siempre llamar aquí!
         }
@@ -47,20 +45,8 @@ impl QuizApp {
 
             if has_questions {
                 // Desbloquea el siguiente nivel
-                let progress = self.progress_mut();
-                let levels = progress
-                    .unlocked_levels
-                    .entry(module_idx)
-                    .or_insert_with(|| vec![0]);
-                if !levels.contains(&next_level) {
-                    levels.push(next_level);
-                    levels.sort_unstable();
-                }
-
-                // Actualiza el máximo nivel desbloqueado de esta semana
-                let max_level = progress.max_unlocked_level.entry(module_idx).or_insert(0);
-                if next_level > *max_level {
-                    *max_level = next_level;
+                if let Some(key) = self.level_key_at(module_idx, next_level) {
+                    self.progress_mut().unlocked_levels.insert(key);
                 }
             } else {
                 // Si no hay más niveles, ¡completó la semana!
@@ -71,15 +57,15 @@ impl QuizApp {
 
     // Cambia el is_module_unlocked:
     pub fn is_module_unlocked(&self, module_idx: usize) -> bool {
-        self.progress().unlocked_modules.contains(&module_idx)
+        self.quiz
+            .modules
+            .get(module_idx)
+            .is_some_and(|module| self.progress().unlocked_modules.contains(&module.key()))
     }
 
     pub fn is_level_unlocked(&self, module: usize, level: usize) -> bool {
-        self.progress()
-            .unlocked_levels
-            .get(&module)
-            .map(|lvls| lvls.contains(&level))
-            .unwrap_or(false)
+        self.level_key_at(module, level)
+            .is_some_and(|key| self.progress().unlocked_levels.contains(&key))
     }
 
     // Una semana está completa si todas sus preguntas están respondidas correctamente
@@ -94,13 +80,7 @@ impl QuizApp {
                 .iter()
                 .flat_map(|level| &level.questions)
                 .filter(|q| q.language == language)
-                .all(|q| {
-                    if let Some(id) = &q.id {
-                        self.progress().completed_ids.contains(id)
-                    } else {
-                        false
-                    }
-                });
+                .all(|q| self.progress().completed_ids.contains(&q.id));
             return all_completed;
         }
         false
@@ -119,32 +99,44 @@ impl QuizApp {
                     .questions
                     .iter()
                     .filter(|q| q.language == language)
-                    .all(|q| {
-                        q.id.as_ref()
-                            .map(|id| self.progress().completed_ids.contains(id))
-                            .unwrap_or(false)
-                    })
+                    .all(|q| self.progress().completed_ids.contains(&q.id))
             })
             .unwrap_or(false)
     }
 
-    /// Desbloquea todas las semanas desde el índice 0 hasta max_unlocked_module (inclusive).
+    /// Desbloquea la primera semana y todas las anteriores a la última desbloqueada
+    /// (p. ej. una semana nueva añadida en medio del banco).
     pub fn recalculate_unlocked_modules(&mut self) {
-        let prog = self.progress_mut();
-        prog.unlocked_modules = (0..=prog.max_unlocked_module).collect();
+        let keys: Vec<String> = self.quiz.modules.iter().map(Module::key).collect();
+        let progress = self.progress_mut();
+        let last = keys
+            .iter()
+            .rposition(|key| progress.unlocked_modules.contains(key))
+            .unwrap_or(0);
+        progress
+            .unlocked_modules
+            .extend(keys.into_iter().take(last + 1));
     }
 
-    /// Recalcula los niveles desbloqueados de cada semana según el máximo desbloqueado.
-    /// Debe llamarse tras modificar max_unlocked_level o al restaurar el progreso.
+    /// Lo mismo con los niveles de una semana: el primero y los anteriores al
+    /// último desbloqueado. Debe llamarse al desbloquear niveles o al restaurar el progreso.
     pub fn recalculate_unlocked_levels(&mut self, module_idx: usize) {
+        let Some(module) = self.quiz.modules.get(module_idx) else {
+            return;
+        };
+        let keys: Vec<String> = module
+            .levels
+            .iter()
+            .map(|level| module.level_key(level))
+            .collect();
         let progress = self.progress_mut();
-        // ¿Hasta qué nivel está desbloqueado en esta semana?
-        let max_level = *progress.max_unlocked_level.get(&module_idx).unwrap_or(&0);
-        let mut unlocked = vec![];
-        for lvl in 0..=max_level {
-            unlocked.push(lvl);
-        }
-        progress.unlocked_levels.insert(module_idx, unlocked);
+        let last = keys
+            .iter()
+            .rposition(|key| progress.unlocked_levels.contains(key))
+            .unwrap_or(0);
+        progress
+            .unlocked_levels
+            .extend(keys.into_iter().take(last + 1));
     }
 
     pub fn nuevas_preguntas_en_semana(&self, module_idx: usize, language: Language) -> usize {
@@ -158,11 +150,7 @@ impl QuizApp {
                     .iter()
                     .flat_map(|lvl| &lvl.questions)
                     .filter(|q| q.language == language)
-                    .filter(|q| {
-                        q.id.as_ref()
-                            .map(|id| !completed.contains(id))
-                            .unwrap_or(false)
-                    })
+                    .filter(|q| !completed.contains(&q.id))
                     .count()
             })
             .unwrap_or(0)
@@ -0,0 +1,179 @@
+//! Examen en curso: las preguntas sorteadas se responden en la vista del quiz sin
+//! pistas, soluciones ni teoría y con un tiempo límite global. El resultado se
+//! guarda en `exam_results`; el progreso del recorrido normal no cambia.
+
+use super::*;
+use crate::app::actions::verdict_message;
+use crate::exam::{ExamAnswer, ExamResult};
+use crate::review;
+
+#[derive(Clone, Debug)]
+pub struct ExamSession {
+    /// `(módulo, nivel, pregunta)` de cada pregunta sorteada.
+    pub questions: Vec<(usize, usize, usize)>,
+    pub answers: Vec<ExamAnswer>,
+    /// Índice en `questions` de la pregunta actual.
+    pub current: usize,
+    /// Instantes (reloj de egui, en segundos) de inicio, de fin y del último frame.
+    started_at: f64,
+    deadline: f64,
+    now: f64,
+}
+
+impl ExamSession {
+    pub fn remaining_secs(&self) -> u64 {
+        (self.deadline - self.now).max(0.0).ceil() as u64
+    }
+}
+
+impl QuizApp {
+    /// Preguntas del lenguaje actual en los niveles elegidos para el examen.
+    pub fn exam_pool(&self) -> Vec<(usize, usize, usize)> {
+        let lang = self.selected_language.unwrap_or(Language::C);
+        let mut pool = Vec::new();
+        for (wi, module) in self.quiz.modules.iter().enumerate() {
+            for (li, level) in module.levels.iter().enumerate() {
+                if !self.exam_config.levels.contains(&module.level_key(level)) {
+                    continue;
+                }
+                for (qi, q) in level.questions.iter().enumerate() {
+                    if q.language == lang {
+                        pool.push((wi, li, qi));
+                    }
+                }
+            }
+        }
+        pool
+    }
+
+    pub fn abrir_examen(&mut self) {
+        self.state = AppState::ExamSetup;
+        self.message.clear();
+    }
+
+    /// Sortea las preguntas y arranca el cronómetro; `now` es el reloj de egui.
+    pub fn empezar_examen(&mut self, now: f64) {
+        let questions = self.exam_config.draw(self.exam_pool());
+        if questions.is_empty() {
+            self.message = "Elige al menos un nivel con preguntas.".into();
+            return;
+        }
+        let answers = questions
+            .iter()
+            .map(|&(wi, li, qi)| {
+                let module = &self.quiz.modules[wi];
+                let level = &module.levels[li];
+                ExamAnswer {
+                    question_id: level.questions[qi].id.clone(),
+                    module: module.number,
+                    level: level.number,
+                    solved: false,
+                    attempts: 0,
+                }
+            })
+            .collect();
+        self.exam = Some(ExamSession {
+            questions,
+            answers,
+            current: 0,
+            started_at: now,
+            deadline: now + f64::from(self.exam_config.minutes) * 60.0,
+            now,
+        });
+        self.begin_session_position();
+        self.message.clear();
+        self.serve_exam_question();
+    }
+
+    /// Coloca en el progreso la pregunta actual del examen.
+    fn serve_exam_question(&mut self) {
+        let Some(&(wi, li, qi)) = self.exam.as_ref().map(|s| &s.questions[s.current]) else {
+            return;
+        };
+        {
+            let progress = self.progress_mut();
+            progress.current_module = Some(wi);
+            progress.current_level = Some(li);
+            progress.current_in_level = Some(qi);
+            progress.show_solution = false;
+        }
+        self.update_input_prefill();
+        self.state = AppState::Exam;
+    }
+
+    /// Pasa a la siguiente pregunta sin resolver, volviendo al principio al
+    /// llegar al final; si están todas resueltas, entrega.
+    pub(super) fn siguiente_pregunta_examen(&mut self) {
+        let Some(session) = self.exam.as_mut() else {
+            return;
+        };
+        let total = session.questions.len();
+        let next = (1..=total)
+            .map(|step| (session.current + step) % total)
+            .find(|&i| !session.answers[i].solved);
+        match next {
+            Some(i) => {
+                session.current = i;
+                self.serve_exam_question();
+            }
+            None => self.entregar_examen(false),
+        }
+    }
+
+    /// Cuenta el intento sin tocar las estadísticas de la pregunta (de ellas
+    /// dependen las pistas y la solución del recorrido normal).
+    pub(super) fn apply_exam_result(&mut self, result: &JudgeResult) {
+        let correct = matches!(result, JudgeResult::Accepted { .. });
+        self.message = verdict_message(result);
+        let Some(session) = self.exam.as_mut() else {
+            return;
+        };
+        let answer = &mut session.answers[session.current];
+        answer.attempts += 1;
+        if correct {
+            answer.solved = true;
+            self.siguiente_pregunta_examen();
+        }
+    }
+
+    /// Termina el examen, guarda su resultado y muestra el informe.
+    pub fn entregar_examen(&mut self, timed_out: bool) {
+        let Some(session) = self.exam.take() else {
+            return;
+        };
+        // Un veredicto remoto que llegue ahora no debe contar en el recorrido normal.
+        self.remote_judge_pending = None;
+        self.remote_judge_rx = None;
+        let time_limit_secs = u64::from(self.exam_config.minutes) * 60;
+        let used_secs =
+            ((session.now - session.started_at).max(0.0).round() as u64).min(time_limit_secs);
+        self.exam_results.push(ExamResult {
+            language: self.selected_language.unwrap_or(Language::C),
+            seed: self.exam_config.seed.clone(),
+            day: review::today(),
+            time_limit_secs,
+            used_secs,
+            timed_out,
+            answers: session.answers,
+        });
+        self.end_session_position();
+        self.progress_mut().show_solution = false;
+        self.state = AppState::ExamReport;
+        self.message = if timed_out {
+            "⏰ Se acabó el tiempo: el examen se entregó automáticamente.".into()
+        } else {
+            String::new()
+        };
+    }
+
+    /// Actualiza el cronómetro y entrega el examen al agotarse el tiempo.
+    pub fn tick_exam(&mut self, now: f64) {
+        let Some(session) = self.exam.as_mut() else {
+            return;
+        };
+        session.now = now;
+        if now >= session.deadline {
+            self.entregar_examen(true);
+        }
+    }
+}
@@ -1,6 +1,15 @@
-use crate::data::read_questions_for_language;
+use crate::data::{
+    BankLoadError, load_questions_for_language, load_questions_skipping_broken,
+    read_embedded_questions, read_questions_for_language,
+};
+use crate::diagnostics::Diagnostic;
+use crate::exam::{ExamConfig, ExamResult};
+use crate::history::Attempt;
 use crate::judge::judge_c::JudgeResult;
+use crate::judge::judge_capabilities::JudgeCapabilities;
 use crate::model::{AppState, Language, Level, Module, Question, Quiz};
+use crate::parsons::Placed;
+use crate::review::ReviewCard;
 use eframe::egui;
 use egui_commonmark::CommonMarkCache;
 use serde::{Deserialize, Serialize};
@@ -8,16 +17,22 @@ use std::collections::{HashMap, HashSet};
 
 // Submódulos
 pub mod actions;
+pub mod banks;
+pub mod capabilities;
 pub mod completion;
+pub mod exam_session;
 pub mod navigation;
 pub mod progress;
 pub mod queries;
 pub mod resets;
+pub mod review_session;
 pub mod updates;
 pub mod view_models;
 
 // Re-export de view models
 pub use crate::view_models::{LevelInfo, ModuleInfo, QuestionRow};
+pub use exam_session::ExamSession;
+pub use review_session::ReviewSession;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum LevelEntry {
@@ -32,44 +47,175 @@ pub struct QuizProgress {
     pub current_module: Option<usize>, // Índice de la semana (en el vector de modules)
     pub current_level: Option<usize>,  // Índice del nivel dentro de la semana seleccionada
     pub current_in_level: Option<usize>, // Índice de la pregunta dentro del nivel actual
-    pub unlocked_modules: Vec<usize>,
-    pub unlocked_levels: HashMap<usize, Vec<usize>>, // semana -> [niveles desbloqueados]
-    pub max_unlocked_module: usize,
-    pub max_unlocked_level: HashMap<usize, usize>,
+    /// Semanas desbloqueadas, por [`Module::key`].
+    #[serde(deserialize_with = "legacy::unlocked_modules")]
+    pub unlocked_modules: HashSet<String>,
+    /// Niveles desbloqueados, por [`Module::level_key`].
+    #[serde(deserialize_with = "legacy::unlocked_levels")]
+    pub unlocked_levels: HashSet<String>,
     pub input: String,
+    /// Opciones marcadas en la pregunta de opciones actual.
+    #[serde(default)]
+    pub selected_options: Vec<usize>,
+    /// Texto de cada hueco de la pregunta `fill_blanks` actual.
+    #[serde(default)]
+    pub blank_inputs: HashMap<String, String>,
+    /// Problema de Parsons actual: bloques sin colocar y líneas colocadas.
+    #[serde(default)]
+    pub parsons_pool: Vec<usize>,
+    #[serde(default)]
+    pub parsons_answer: Vec<Placed>,
     pub finished: bool,
     pub round: usize,
-    pub shown_this_round: Vec<(usize, usize)>, // Ahora es mejor guardar pares (nivel, pregunta)
+    /// `id`s de las preguntas ya mostradas en la ronda actual.
+    #[serde(deserialize_with = "legacy::shown_this_round")]
+    pub shown_this_round: Vec<String>,
     pub show_solution: bool,
-    pub seen_level_theory: HashSet<(usize, usize)>,
+    /// Niveles cuya teoría ya se vio, por [`Module::level_key`].
+    #[serde(deserialize_with = "legacy::seen_level_theory")]
+    pub seen_level_theory: HashSet<String>,
+    /// Tarjetas de repaso espaciado de las preguntas completadas, por `id`.
+    #[serde(default)]
+    pub review_cards: HashMap<String, ReviewCard>,
+    /// Envíos de cada pregunta, por `id`, del más antiguo al más reciente.
+    #[serde(default)]
+    pub history: HashMap<String, Vec<Attempt>>,
+}
+
+/// Progreso guardado por versiones que usaban índices `(nivel, pregunta)` /
+/// `(módulo, nivel)` en lugar de claves estables.
+mod legacy {
+    use crate::model::{level_key, module_key};
+    use serde::{Deserialize, Deserializer};
+    use std::collections::{HashMap, HashSet};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Key {
+        Id(String),
+        Indices(usize, usize),
+    }
+
+    /// La ronda en curso no se puede traducir a `id`s: se empieza de nuevo.
+    pub fn shown_this_round<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+        let keys = Vec::<Key>::deserialize(d)?;
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| match key {
+                Key::Id(id) => Some(id),
+                Key::Indices(..) => None,
+            })
+            .collect())
+    }
+
+    /// Los índices antiguos coinciden con `number - 1` en los bancos de entonces.
+    pub fn seen_level_theory<'de, D: Deserializer<'de>>(d: D) -> Result<HashSet<String>, D::Error> {
+        let keys = Vec::<Key>::deserialize(d)?;
+        Ok(keys
+            .into_iter()
+            .map(|key| match key {
+                Key::Id(id) => id,
+                Key::Indices(module, level) => level_key(module + 1, level + 1),
+            })
+            .collect())
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Unlocked<T> {
+        Keys(HashSet<String>),
+        Indices(T),
+    }
+
+    /// Índice usado como clave de mapa: número en RON, texto en JSON.
+    #[derive(Deserialize, PartialEq, Eq, Hash)]
+    #[serde(untagged)]
+    enum MapIndex {
+        Number(usize),
+        Text(String),
+    }
+
+    impl MapIndex {
+        fn value(&self) -> Option<usize> {
+            match self {
+                MapIndex::Number(n) => Some(*n),
+                MapIndex::Text(text) => text.parse().ok(),
+            }
+        }
+    }
+
+    /// Semanas desbloqueadas por índice; como en la teoría vista, el índice es `number - 1`.
+    pub fn unlocked_modules<'de, D: Deserializer<'de>>(d: D) -> Result<HashSet<String>, D::Error> {
+        Ok(match Unlocked::<Vec<usize>>::deserialize(d)? {
+            Unlocked::Keys(keys) => keys,
+            Unlocked::Indices(modules) => modules.into_iter().map(|m| module_key(m + 1)).collect(),
+        })
+    }
+
+    /// Niveles desbloqueados como `semana -> [niveles]`, por índices.
+    pub fn unlocked_levels<'de, D: Deserializer<'de>>(d: D) -> Result<HashSet<String>, D::Error> {
+        Ok(
+            match Unlocked::<HashMap<MapIndex, Vec<usize>>>::deserialize(d)? {
+                Unlocked::Keys(keys) => keys,
+                Unlocked::Indices(levels) => levels
+                    .into_iter()
+                    .filter_map(|(module, levels)| Some((module.value()?, levels)))
+                    .flat_map(|(module, levels)| {
+                        levels
+                            .into_iter()
+                            .map(move |level| level_key(module + 1, level + 1))
+                    })
+                    .collect(),
+            },
+        )
+    }
 }
 
 impl Default for QuizProgress {
     fn default() -> Self {
-        let mut unlocked_levels = HashMap::new();
-        unlocked_levels.insert(0, vec![0]); // Solo nivel 1 desbloqueado para la semana 1
-        let mut max_unlocked_level = HashMap::new();
-        max_unlocked_level.insert(0, 0); // Solo nivel 1 desbloqueado para la semana 1
-
         Self {
             completed_ids: HashSet::new(),
             current_module: Some(0),
             current_level: Some(0),
             current_in_level: None,
-            unlocked_modules: vec![0], // Solo semana 1 desbloqueada
-            unlocked_levels,
-            max_unlocked_module: 0,
-            max_unlocked_level,
+            // Solo el nivel 1 de la semana 1
+            unlocked_modules: HashSet::from([crate::model::module_key(1)]),
+            unlocked_levels: HashSet::from([crate::model::level_key(1, 1)]),
             input: String::new(),
+            selected_options: Vec::new(),
+            blank_inputs: HashMap::new(),
+            parsons_pool: Vec::new(),
+            parsons_answer: Vec::new(),
             finished: false,
             round: 1,
             shown_this_round: vec![],
             show_solution: false,
             seen_level_theory: HashSet::new(),
+            review_cards: HashMap::new(),
+            history: HashMap::new(),
         }
     }
 }
 
+/// Posición del alumno en el recorrido normal (y lo que tenía escrito), apartada
+/// mientras un repaso o un examen usan la vista del quiz.
+#[derive(Clone, Debug, Default)]
+pub struct SavedPosition {
+    module: Option<usize>,
+    level: Option<usize>,
+    in_level: Option<usize>,
+    input: String,
+}
+
+impl SavedPosition {
+    fn swap(&mut self, progress: &mut QuizProgress) {
+        std::mem::swap(&mut self.module, &mut progress.current_module);
+        std::mem::swap(&mut self.level, &mut progress.current_level);
+        std::mem::swap(&mut self.in_level, &mut progress.current_in_level);
+        std::mem::swap(&mut self.input, &mut progress.input);
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PendingRemoteJudge {
     pub cw: usize,
@@ -80,6 +226,8 @@ pub struct PendingRemoteJudge {
 #[derive(Serialize, Deserialize)]
 pub struct QuizApp {
     pub progresses: HashMap<Language, QuizProgress>,
+    /// Se recarga del banco al elegir lenguaje; lo persistente está en `progresses`.
+    #[serde(skip)]
     pub quiz: Quiz,
     pub selected_language: Option<Language>,
     pub message: String,
@@ -101,6 +249,48 @@ pub struct QuizApp {
     pub remote_judge_pending: Option<PendingRemoteJudge>,
     #[serde(skip)]
     pub remote_judge_rx: Option<std::sync::mpsc::Receiver<JudgeResult>>,
+    /// Instante (ver [`crate::history::now_ms`]) del envío que se está corrigiendo.
+    #[serde(skip)]
+    pub submitted_at: Option<f64>,
+    /// Salida real de las preguntas `predict_output` con `compute_output`, por `id`.
+    #[serde(skip)]
+    pub computed_outputs: HashMap<String, String>,
+    /// Diagnósticos del último envío de cada pregunta, ya en líneas del alumno.
+    #[serde(skip)]
+    pub diagnostics: HashMap<String, Vec<Diagnostic>>,
+    /// Línea a la que debe saltar el editor en el siguiente frame.
+    #[serde(skip)]
+    pub jump_to_line: Option<usize>,
+    /// Sesión de repaso en curso (ver [`review_session`]).
+    #[serde(skip)]
+    pub review: Option<ReviewSession>,
+    /// Examen en curso (ver [`exam_session`]).
+    #[serde(skip)]
+    pub exam: Option<ExamSession>,
+    /// Última configuración de examen usada.
+    #[serde(default)]
+    pub exam_config: ExamConfig,
+    /// Exámenes entregados; van aparte de `progresses` porque no completan preguntas.
+    #[serde(default)]
+    pub exam_results: Vec<ExamResult>,
+    /// Posición del recorrido normal mientras dura un repaso o un examen.
+    #[serde(skip)]
+    pub saved_position: Option<SavedPosition>,
+    #[serde(skip)]
+    pub capabilities: Option<JudgeCapabilities>,
+    #[serde(skip)]
+    pub capabilities_rx: Option<std::sync::mpsc::Receiver<Option<JudgeCapabilities>>>,
+    #[serde(skip)]
+    pub capabilities_check_launched: bool,
+    #[serde(skip)]
+    pub bank_error: Option<BankLoadError>,
+    /// Preguntas descartadas al cargar el banco actual omitiendo las rotas.
+    #[serde(skip)]
+    pub skipped_questions: Vec<BankLoadError>,
+    #[serde(skip)]
+    pub banks_prefetch_rx: Option<std::sync::mpsc::Receiver<()>>,
+    #[serde(skip)]
+    pub banks_prefetch_launched: bool,
 }
 
 impl QuizApp {
@@ -113,7 +303,7 @@ impl QuizApp {
         progresses.insert(Language::Rust, QuizProgress::default());
         progresses.insert(Language::Python, QuizProgress::default());
 
-        let quiz = read_questions_for_language(Language::C);
+        let quiz = read_embedded_questions(Language::C);
 
         // Inicializa el struct principal
         let mut quiz_app = Self {
@@ -130,6 +320,22 @@ impl QuizApp {
             has_saved_progress: false,
             remote_judge_pending: None,
             remote_judge_rx: None,
+            submitted_at: None,
+            computed_outputs: HashMap::new(),
+            diagnostics: HashMap::new(),
+            jump_to_line: None,
+            review: None,
+            exam: None,
+            exam_config: ExamConfig::default(),
+            exam_results: Vec::new(),
+            saved_position: None,
+            capabilities: None,
+            capabilities_rx: None,
+            capabilities_check_launched: false,
+            bank_error: None,
+            skipped_questions: Vec::new(),
+            banks_prefetch_rx: None,
+            banks_prefetch_launched: false,
         };
 
         // --- Esto es igual que antes ---
@@ -165,27 +371,53 @@ impl QuizApp {
             has_saved_progress: false,
             remote_judge_pending: None,
             remote_judge_rx: None,
+            submitted_at: None,
+            computed_outputs: HashMap::new(),
+            diagnostics: HashMap::new(),
+            jump_to_line: None,
+            review: None,
+            exam: None,
+            exam_config: ExamConfig::default(),
+            exam_results: Vec::new(),
+            saved_position: None,
+            capabilities: None,
+            capabilities_rx: None,
+            capabilities_check_launched: false,
+            bank_error: None,
+            skipped_questions: Vec::new(),
+            banks_prefetch_rx: None,
+            banks_prefetch_launched: false,
         }
     }
 
     /// Entrypoint para cambiar idioma y reconstruir el banco filtrado
     pub fn seleccionar_lenguaje(&mut self, lang: Language) {
+        match load_questions_for_language(lang) {
+            Ok(quiz) => self.aplicar_banco(lang, quiz),
+            Err(err) => {
+                self.selected_language = Some(lang);
+                self.bank_error = Some(err);
+                self.state = AppState::BankError;
+            }
+        }
+    }
+
+    fn aplicar_banco(&mut self, lang: Language, mut quiz: Quiz) {
         self.selected_language = Some(lang);
+        self.bank_error = None;
+        self.skipped_questions.clear();
         self.progresses
             .entry(lang)
             .or_insert_with(QuizProgress::default);
 
+        quiz.migrate_completed(&mut self.progress_mut().completed_ids);
         let prev_completed = self.progress().completed_ids.clone();
-        let mut quiz = read_questions_for_language(lang);
 
         for module in &mut quiz.modules {
             for level in &mut module.levels {
                 for (i, q) in level.questions.iter_mut().enumerate() {
                     q.number = i + 1;
-                    q.is_done =
-                        q.id.as_ref()
-                            .map(|id| prev_completed.contains(id))
-                            .unwrap_or(false);
+                    q.is_done = prev_completed.contains(&q.id);
                 }
             }
         }
@@ -196,3 +428,93 @@ impl QuizApp {
         self.has_saved_progress = true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_progress_saved_with_index_keys() {
+        let mut saved = serde_json::to_value(QuizProgress::default()).unwrap();
+        saved["completed_ids"] = serde_json::json!(["c-m1-l1-q1"]);
+        saved["shown_this_round"] = serde_json::json!([[0, 2], [1, 0]]);
+        saved["seen_level_theory"] = serde_json::json!([[0, 0], [2, 1]]);
+        saved["unlocked_modules"] = serde_json::json!([0, 1]);
+        saved["unlocked_levels"] = serde_json::json!({ "0": [0, 1], "1": [0] });
+        saved["max_unlocked_module"] = serde_json::json!(1);
+
+        let progress: QuizProgress = serde_json::from_value(saved).unwrap();
+        assert!(progress.completed_ids.contains("c-m1-l1-q1"));
+        assert!(progress.shown_this_round.is_empty());
+        assert_eq!(
+            progress.seen_level_theory,
+            HashSet::from(["m1-l1".to_string(), "m3-l2".to_string()])
+        );
+        assert_eq!(
+            progress.unlocked_modules,
+            HashSet::from(["m1".to_string(), "m2".to_string()])
+        );
+        assert_eq!(
+            progress.unlocked_levels,
+            HashSet::from(["m1-l1".into(), "m1-l2".into(), "m2-l1".into()])
+        );
+    }
+
+    /// App con un banco de una sola pregunta, que queda como pregunta actual.
+    fn app_with(question: Question) -> QuizApp {
+        let mut app = QuizApp::new_for_language(question.language);
+        app.quiz = Quiz {
+            modules: vec![Module {
+                number: 1,
+                explanation: String::new(),
+                levels: vec![Level {
+                    number: 1,
+                    explanation: HashMap::new(),
+                    questions: vec![question],
+                }],
+            }],
+            ..Default::default()
+        };
+        app.progress_mut().current_in_level = Some(0);
+        app
+    }
+
+    #[test]
+    fn records_the_chosen_true_false_option() {
+        let mut app = app_with(Question {
+            id: "c-m1-l1-null".into(),
+            kind: crate::model::QuestionKind::TrueFalse,
+            prompt: "`NULL` se puede desreferenciar.".into(),
+            answer: "falso".into(),
+            ..Default::default()
+        });
+        app.progress_mut().selected_options = vec![0];
+        app.procesar_eleccion();
+        let attempts = &app.progress().history["c-m1-l1-null"];
+        assert_eq!(attempts.len(), 1);
+        assert_eq!(attempts[0].code, "• Verdadero");
+        assert_eq!(attempts[0].verdict, crate::history::Verdict::WrongAnswer);
+    }
+
+    #[test]
+    fn exam_feedback_never_shows_the_expected_tokens() {
+        let question = Question {
+            id: "py-m1-l1-bucle".into(),
+            language: Language::Python,
+            prompt: "Imprime del 0 al 9.".into(),
+            answer: "for i in range(10): print(i)".into(),
+            fails: 2,
+            ..Default::default()
+        };
+        let wrong = "for i in range(1): print(i)";
+        let revealed = crate::equivalence::grade_normalize(&question, wrong, true);
+        assert!(matches!(revealed, JudgeResult::WrongAnswer { diff, .. } if diff.contains("`10`")));
+
+        let mut app = app_with(question);
+        app.exam_config.levels = vec!["m1-l1".into()];
+        app.empezar_examen(0.0);
+        app.procesar_respuesta(wrong);
+        assert!(app.message.starts_with('❌'), "{}", app.message);
+        assert!(!app.message.contains("`10`"), "{}", app.message);
+    }
+}
@@ -2,6 +2,9 @@ use super::*;
 
 impl QuizApp {
     pub fn cambiar_lenguaje(&mut self) {
+        if self.review.is_some() {
+            self.terminar_repaso();
+        }
         self.has_saved_progress = true;
         self.state = AppState::LanguageSelect;
     }
@@ -17,12 +20,9 @@ impl QuizApp {
         };
 
         // Obtén los niveles desbloqueados para esta semana
-        let unlocked_levels = self
-            .progress()
-            .unlocked_levels
-            .get(&module_idx)
-            .cloned()
-            .unwrap_or_else(|| vec![0]);
+        let unlocked_levels: Vec<usize> = (0..module.levels.len())
+            .filter(|&level_idx| self.is_level_unlocked(module_idx, level_idx))
+            .collect();
 
         // Busca el primer nivel desbloqueado con preguntas pendientes
         let mut first_pending_level = 0;
@@ -31,14 +31,10 @@ impl QuizApp {
         for &lvl_idx in &unlocked_levels {
             if let Some(level) = module.levels.get(lvl_idx) {
                 for q in &level.questions {
-                    if q.language == language {
-                        if let Some(id) = &q.id {
-                            if !self.progress().completed_ids.contains(id) {
-                                first_pending_level = lvl_idx;
-                                found_pending = true;
-                                break;
-                            }
-                        }
+                    if q.language == language && !self.progress().completed_ids.contains(&q.id) {
+                        first_pending_level = lvl_idx;
+                        found_pending = true;
+                        break;
                     }
                 }
             }
@@ -80,13 +76,9 @@ impl QuizApp {
         // 1) Buscar primera pregunta pendiente de ESTE lenguaje
         let mut first_pending_question: Option<usize> = None;
         for (q_idx, q) in level.questions.iter().enumerate() {
-            if q.language == language {
-                if let Some(id) = &q.id {
-                    if !self.progress().completed_ids.contains(id) {
-                        first_pending_question = Some(q_idx);
-                        break;
-                    }
-                }
+            if q.language == language && !self.progress().completed_ids.contains(&q.id) {
+                first_pending_question = Some(q_idx);
+                break;
             }
         }
 
@@ -122,10 +114,9 @@ impl QuizApp {
         //    - Restart: forzar mostrar
         //    - Menu: nunca mostrar (entra directo al quiz)
         let should_show_theory = match entry {
-            LevelEntry::Flow => !self
-                .progress()
-                .seen_level_theory
-                .contains(&(module_idx, level_idx)),
+            LevelEntry::Flow => self
+                .level_key_at(module_idx, level_idx)
+                .is_some_and(|key| !self.progress().seen_level_theory.contains(&key)),
             LevelEntry::Restart => true,
             LevelEntry::Menu => false,
         };
@@ -163,13 +154,10 @@ impl QuizApp {
 
             if let Some(wi) = self.quiz.modules.iter().enumerate().find_map(|(wi, wk)| {
                 // ¿Algún nivel dentro de wk tiene una pregunta no completada?
-                let has_pending = wk.levels.iter().flat_map(|lvl| &lvl.questions).any(|q| {
-                    q.language == lang
-                        && q.id
-                            .as_ref()
-                            .map(|id| !self.progress().completed_ids.contains(id))
-                            .unwrap_or(false)
-                });
+                let has_pending =
+                    wk.levels.iter().flat_map(|lvl| &lvl.questions).any(|q| {
+                        q.language == lang && !self.progress().completed_ids.contains(&q.id)
+                    });
                 if has_pending { Some(wi) } else { None }
             }) {
                 // select_module usará wi para posicionarse en (li,qi)
@@ -216,16 +204,10 @@ impl QuizApp {
             None => return,
         };
 
-        // 2) Asegurar estructuras auxiliares
-        {
-            let prog = self.progress_mut();
-            prog.max_unlocked_level.entry(module_idx).or_insert(0);
-        }
-
-        // 3) Recalcular niveles desbloqueados (usa borrow mutable interno)
+        // 2) Recalcular niveles desbloqueados (usa borrow mutable interno)
         self.recalculate_unlocked_levels(module_idx);
 
-        // 4) Preservar posición si es válida; si no, fijar primer nivel desbloqueado
+        // 3) Preservar posición si es válida; si no, fijar primer nivel desbloqueado
         let (must_init_level, new_level_opt) = {
             let prog = self.progress();
             let levels_len = self.quiz.modules[module_idx].levels.len();
@@ -234,24 +216,14 @@ impl QuizApp {
             let keep_current = prog.current_module == Some(module_idx)
                 && prog
                     .current_level
-                    .map(|li| {
-                        li < levels_len
-                            && prog
-                                .unlocked_levels
-                                .get(&module_idx)
-                                .map(|v| v.contains(&li))
-                                .unwrap_or(false)
-                    })
-                    .unwrap_or(false);
+                    .is_some_and(|li| li < levels_len && self.is_level_unlocked(module_idx, li));
 
             if keep_current {
                 (false, None) // no tocar ni level ni in_level
             } else {
                 // elegir el primer nivel desbloqueado (o 0 si no hay info)
-                let first_lvl = prog
-                    .unlocked_levels
-                    .get(&module_idx)
-                    .and_then(|v| v.first().copied())
+                let first_lvl = (0..levels_len)
+                    .find(|&li| self.is_level_unlocked(module_idx, li))
                     .unwrap_or(0);
                 (true, Some(first_lvl))
             }
@@ -269,7 +241,7 @@ impl QuizApp {
                 prog.current_in_level = None;
             }
         }
-        // 5) Cambiar estado y limpiar mensaje
+        // 4) Cambiar estado y limpiar mensaje
         self.state = AppState::LevelMenu;
         self.message.clear();
     }
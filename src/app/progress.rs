@@ -1,4 +1,6 @@
 use super::*;
+use crate::model::QuestionKind;
+use crate::parsons::initial_pool;
 
 impl QuizApp {
     // Accesores seguros
@@ -28,23 +30,18 @@ impl QuizApp {
         if self.selected_language.is_none() {
             return;
         }
-        let valid_ids: HashSet<String> = self.all_question_ids();
-        {
-            let prog = self.progress_mut();
-            prog.completed_ids.retain(|id| valid_ids.contains(id));
-        }
-        let completed = self.progress().completed_ids.clone();
+        // Los ids que ya no están en el banco se conservan: pueden volver o
+        // heredarse más adelante mediante `aliases`/`replaced_by`.
+        let mut completed = std::mem::take(&mut self.progress_mut().completed_ids);
+        self.quiz.migrate_completed(&mut completed);
         for module in &mut self.quiz.modules {
             for level in &mut module.levels {
                 for q in &mut level.questions {
-                    let already = q.is_done;
-                    q.is_done =
-                        q.id.as_ref()
-                            .map(|id| completed.contains(id))
-                            .unwrap_or(already);
+                    q.is_done = completed.contains(&q.id);
                 }
             }
         }
+        self.progress_mut().completed_ids = completed;
     }
 
     pub fn update_input_prefill(&mut self) {
@@ -53,17 +50,48 @@ impl QuizApp {
         let level_idx = progress.current_level;
         let q_idx = progress.current_in_level;
 
-        let prefill = module_idx
+        let question = module_idx
             .and_then(|w| self.quiz.modules.get(w))
             .and_then(|module| level_idx.and_then(|l| module.levels.get(l)))
-            .and_then(|level| q_idx.and_then(|q| level.questions.get(q)))
-            .and_then(|q| q.input_prefill.clone());
+            .and_then(|level| q_idx.and_then(|q| level.questions.get(q)));
+        let prefill = question.and_then(|q| q.input_prefill.clone());
+        let parsons_pool = question
+            .filter(|q| q.kind == QuestionKind::Parsons)
+            .map(initial_pool)
+            .unwrap_or_default();
 
         let progress = self.progress_mut();
+        progress.selected_options.clear();
+        progress.blank_inputs.clear();
+        progress.parsons_pool = parsons_pool;
+        progress.parsons_answer.clear();
         if let Some(text) = prefill {
             progress.input = text;
         } else {
             progress.input.clear();
         }
     }
+
+    /// Aparta la posición del recorrido normal antes de un repaso o un examen.
+    pub(crate) fn begin_session_position(&mut self) {
+        let mut saved = SavedPosition::default();
+        saved.swap(self.progress_mut());
+        self.saved_position = Some(saved);
+    }
+
+    /// Recupera la posición apartada con [`Self::begin_session_position`].
+    pub(crate) fn end_session_position(&mut self) {
+        if let Some(mut saved) = self.saved_position.take() {
+            saved.swap(self.progress_mut());
+        }
+    }
+
+    /// Intercambia la posición apartada con la del progreso; al guardar se llama
+    /// antes y después de serializar para persistir la del recorrido normal.
+    pub fn swap_session_position(&mut self) {
+        if let Some(mut saved) = self.saved_position.take() {
+            saved.swap(self.progress_mut());
+            self.saved_position = Some(saved);
+        }
+    }
 }
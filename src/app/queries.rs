@@ -40,13 +40,19 @@ impl QuizApp {
             .collect()
     }
 
+    /// Clave estable del nivel `level_idx` del módulo `module_idx` (ver [`Module::level_key`]).
+    pub fn level_key_at(&self, module_idx: usize, level_idx: usize) -> Option<String> {
+        let module = self.quiz.modules.get(module_idx)?;
+        Some(module.level_key(module.levels.get(level_idx)?))
+    }
+
     pub fn all_question_ids(&self) -> HashSet<String> {
         self.quiz
             .modules
             .iter()
             .flat_map(|w| &w.levels)
             .flat_map(|l| &l.questions)
-            .filter_map(|q| q.id.clone())
+            .map(|q| q.id.clone())
             .collect()
     }
 }
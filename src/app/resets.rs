@@ -3,8 +3,12 @@ use super::*;
 impl QuizApp {
     pub fn reset_progress(&mut self) {
         if let Some(language) = self.selected_language {
-            // 1) reconstruye sólo el estado para este idioma
+            // 1) reconstruye sólo el estado para este idioma (los exámenes se conservan)
+            let exam_config = std::mem::take(&mut self.exam_config);
+            let exam_results = std::mem::take(&mut self.exam_results);
             *self = QuizApp::new_for_language(language);
+            self.exam_config = exam_config;
+            self.exam_results = exam_results;
 
             // 2) vuelve a “sembrar” el progreso vacío para el otro idioma,
             //    así tu HashMap siempre tiene ambas claves
@@ -60,7 +64,7 @@ impl QuizApp {
             .flat_map(|w| &w.levels)
             .flat_map(|lvl| &lvl.questions)
             .filter(|q| q.language == lang)
-            .filter_map(|q| q.id.clone())
+            .map(|q| q.id.clone())
             .collect();
 
         // 2) Buscar primera pregunta pendiente en esa semana
@@ -81,6 +85,8 @@ impl QuizApp {
             let prog = self.progress_mut();
             for id in ids_to_remove {
                 prog.completed_ids.remove(&id);
+                prog.review_cards.remove(&id);
+                prog.history.remove(&id);
             }
             prog.round = 1;
             prog.shown_this_round.clear();
@@ -117,7 +123,7 @@ impl QuizApp {
             .into_iter()
             .flat_map(|lvl| &lvl.questions)
             .filter(|q| q.language == lang)
-            .filter_map(|q| q.id.clone())
+            .map(|q| q.id.clone())
             .collect();
 
         // 2) Buscar la primera pregunta pendiente en ese nivel
@@ -141,6 +147,8 @@ impl QuizApp {
             let prog = self.progress_mut();
             for id in ids_to_remove {
                 prog.completed_ids.remove(&id);
+                prog.review_cards.remove(&id);
+                prog.history.remove(&id);
             }
             prog.round = 1;
             prog.shown_this_round.clear();
@@ -167,12 +175,20 @@ impl QuizApp {
     }
 
     pub fn volver_niveles(&mut self) {
+        if self.review.is_some() {
+            self.terminar_repaso();
+            return;
+        }
         self.has_saved_progress = true;
         self.state = AppState::LevelMenu;
         self.message.clear();
     }
 
     pub fn ver_progreso(&mut self) {
+        if self.review.is_some() {
+            self.terminar_repaso();
+            return;
+        }
         self.state = AppState::LevelSummary;
         self.message.clear();
     }
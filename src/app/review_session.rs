@@ -0,0 +1,168 @@
+//! Sesión de repaso espaciado: sirve en la vista del quiz las preguntas completadas
+//! a las que les toca repaso (ver [`crate::review`]) y las reprograma según el
+//! resultado.
+
+use super::*;
+use crate::app::actions::verdict_message;
+use crate::review::{self, MAX_SESSION, ReviewCard};
+
+#[derive(Clone, Debug)]
+pub struct ReviewSession {
+    /// Preguntas que quedan, `(módulo, nivel, pregunta)`; la primera es la actual.
+    pub queue: Vec<(usize, usize, usize)>,
+    /// Preguntas ya repasadas en esta sesión.
+    pub reviewed: usize,
+    /// Intentos fallidos en la pregunta actual.
+    fails: u32,
+}
+
+impl QuizApp {
+    /// Preguntas completadas de los módulos desbloqueados a las que les toca
+    /// repaso, las más atrasadas primero. Las completadas antes de existir el
+    /// repaso no tienen tarjeta y cuentan como pendientes.
+    pub fn due_reviews(&self) -> Vec<(usize, usize, usize)> {
+        let Some(progress) = self.progress_opt() else {
+            return Vec::new();
+        };
+        let lang = self.selected_language.unwrap_or(Language::C);
+        let today = review::today();
+        let mut due: Vec<(u64, (usize, usize, usize))> = Vec::new();
+        for (wi, module) in self.quiz.modules.iter().enumerate() {
+            if !progress.unlocked_modules.contains(&module.key()) {
+                continue;
+            }
+            for (li, level) in module.levels.iter().enumerate() {
+                for (qi, q) in level.questions.iter().enumerate() {
+                    if q.language != lang || !progress.completed_ids.contains(&q.id) {
+                        continue;
+                    }
+                    let card_due = progress.review_cards.get(&q.id).map_or(0, |card| card.due);
+                    if card_due <= today {
+                        due.push((card_due, (wi, li, qi)));
+                    }
+                }
+            }
+        }
+        due.sort_by_key(|(card_due, _)| *card_due);
+        due.into_iter().map(|(_, position)| position).collect()
+    }
+
+    /// "Repaso": aparta la posición actual y sirve la primera pregunta pendiente.
+    pub fn empezar_repaso(&mut self) {
+        let mut queue = self.due_reviews();
+        if queue.is_empty() {
+            self.message = "No tienes preguntas pendientes de repaso.".into();
+            return;
+        }
+        queue.truncate(MAX_SESSION);
+        self.review = Some(ReviewSession {
+            queue,
+            reviewed: 0,
+            fails: 0,
+        });
+        self.begin_session_position();
+        self.message.clear();
+        self.serve_review();
+    }
+
+    /// Sale del repaso y devuelve al alumno a la pantalla de inicio.
+    pub fn terminar_repaso(&mut self) {
+        let Some(session) = self.review.as_ref() else {
+            return;
+        };
+        let reviewed = session.reviewed;
+        self.end_session_position();
+        self.review = None;
+        self.progress_mut().show_solution = false;
+        self.state = AppState::Welcome;
+        self.message = if reviewed == 0 {
+            String::new()
+        } else {
+            format!("✅ Repaso terminado: {reviewed} preguntas repasadas.")
+        };
+    }
+
+    /// Coloca en el progreso la primera pregunta de la cola, o termina si no quedan.
+    fn serve_review(&mut self) {
+        let Some(&(wi, li, qi)) = self.review.as_ref().and_then(|s| s.queue.first()) else {
+            self.terminar_repaso();
+            return;
+        };
+        if let Some(session) = self.review.as_mut() {
+            session.fails = 0;
+        }
+        // Pista y solución dependen de los fallos en esta sesión, no de los anteriores.
+        self.quiz.modules[wi].levels[li].questions[qi].fails = 0;
+        {
+            let progress = self.progress_mut();
+            progress.current_module = Some(wi);
+            progress.current_level = Some(li);
+            progress.current_in_level = Some(qi);
+            progress.show_solution = false;
+        }
+        self.update_input_prefill();
+        self.state = AppState::Quiz;
+    }
+
+    /// Reprograma la pregunta actual con `quality` y pasa a la siguiente.
+    fn finish_review(&mut self, quality: u8) {
+        let Some(session) = self.review.as_mut() else {
+            return;
+        };
+        let (wi, li, qi) = session.queue.remove(0);
+        session.reviewed += 1;
+        let id = self.quiz.modules[wi].levels[li].questions[qi].id.clone();
+        let today = review::today();
+        self.progress_mut()
+            .review_cards
+            .entry(id)
+            .or_insert_with(|| ReviewCard::new(today))
+            .review(quality, today);
+        self.serve_review();
+    }
+
+    pub(super) fn apply_review_result(
+        &mut self,
+        cw: usize,
+        cl: usize,
+        ci: usize,
+        result: &JudgeResult,
+    ) {
+        let correct = matches!(result, JudgeResult::Accepted { .. });
+        {
+            let q = &mut self.quiz.modules[cw].levels[cl].questions[ci];
+            q.attempts += 1;
+            if !correct {
+                q.fails += 1;
+            }
+        }
+        self.message = verdict_message(result);
+        let Some(session) = self.review.as_mut() else {
+            return;
+        };
+        if correct {
+            let quality = review::quality(session.fails, false);
+            self.finish_review(quality);
+        } else {
+            session.fails += 1;
+        }
+    }
+
+    /// "Siguiente pregunta" tras ver la solución: cuenta como no recordada.
+    pub(super) fn repaso_con_solucion(&mut self) {
+        let fails = self.review.as_ref().map_or(0, |s| s.fails);
+        self.finish_review(review::quality(fails, true));
+        self.message.clear();
+    }
+
+    /// Saltar en el repaso la deja pendiente para la próxima sesión.
+    pub(super) fn saltar_repaso(&mut self) {
+        if let Some(session) = self.review.as_mut() {
+            session.queue.remove(0);
+        }
+        self.serve_review();
+        if self.review.is_some() {
+            self.message = "⏩ Pregunta saltada. Seguirá pendiente de repaso.".into();
+        }
+    }
+}
@@ -38,11 +38,7 @@ impl QuizApp {
                         .questions
                         .iter()
                         .filter(|q| q.language == lang)
-                        .filter(|q| {
-                            q.id.as_ref()
-                                .map(|id| !self.progress().completed_ids.contains(id))
-                                .unwrap_or(false)
-                        })
+                        .filter(|q| !self.progress().completed_ids.contains(&q.id))
                         .count();
                     LevelInfo {
                         idx: li,
@@ -71,10 +67,7 @@ impl QuizApp {
                     .enumerate()
                     .filter(|(_, q)| q.language == lang)
                 {
-                    let done =
-                        q.id.as_ref()
-                            .map(|id| self.progress().completed_ids.contains(id))
-                            .unwrap_or(false);
+                    let done = self.progress().completed_ids.contains(&q.id);
                     rows.push(QuestionRow {
                         level_number: lvl.number,
                         question_index_1based: qi + 1,
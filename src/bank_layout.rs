@@ -0,0 +1,177 @@
+//! Bancos de preguntas repartidos en un directorio por lenguaje:
+//!
+//! ```text
+//! data/<lenguaje>/module_<n>.yaml           un módulo: number, explanation, levels[].questions
+//! data/<lenguaje>/module_<n>_level_<m>.md   teoría (markdown) del nivel <m> del módulo <n>
+//! data/<lenguaje>/replaced_by.yaml          opcional: ids retirados -> ids que heredan su progreso
+//! ```
+//!
+//! [`read_bank`] los une en un único YAML con el mismo formato que los bancos de un
+//! solo fichero (`quiz_questions_<lenguaje>.yaml`), que se siguen aceptando.
+//!
+//! Lo usa `build.rs` (para integrar los bancos en el binario) además de la app,
+//! así que solo depende de `std` y `serde_yaml`.
+
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directorio de cada lenguaje y su nombre en el YAML (`language:`, claves de `explanation`).
+pub const LANGUAGE_DIRS: &[(&str, &str)] = &[
+    ("c", "C"),
+    ("pseudocode", "Pseudocode"),
+    ("kotlin", "Kotlin"),
+    ("java", "Java"),
+    ("rust", "Rust"),
+    ("python", "Python"),
+    ("git_github", "GitGithub"),
+];
+
+/// Banco compartido por C y pseudocódigo antes de separarlo por lenguaje.
+pub const LEGACY_MODES_FILE: &str = "quiz_questions_modes.yaml";
+
+pub const EMPTY_BANK: &str = "modules: []\n";
+
+pub fn bank_file_name(key: &str) -> String {
+    format!("quiz_questions_{key}.yaml")
+}
+
+/// Busca el banco de `key` en `data_dir`: primero el directorio `<key>/`, luego
+/// `quiz_questions_<key>.yaml` y, para C/pseudocódigo, el antiguo banco común.
+/// Devuelve `(origen, yaml)` o `None` si no hay ninguno.
+pub fn read_bank(data_dir: &Path, key: &str) -> Result<Option<(String, String)>, String> {
+    let dir = data_dir.join(key);
+    if dir.is_dir() {
+        return merge_bank_dir(&dir).map(|yaml| Some((dir.display().to_string(), yaml)));
+    }
+
+    let mut candidates = vec![data_dir.join(bank_file_name(key))];
+    if matches!(key, "c" | "pseudocode") {
+        candidates.push(data_dir.join(LEGACY_MODES_FILE));
+    }
+    match candidates.into_iter().find(|path| path.is_file()) {
+        Some(path) => read_bank_file(&path).map(|yaml| Some((path.display().to_string(), yaml))),
+        None => Ok(None),
+    }
+}
+
+/// Lee un banco indicado por ruta, sea un fichero o un directorio por lenguaje.
+pub fn read_bank_path(path: &Path) -> Result<String, String> {
+    if path.is_dir() {
+        merge_bank_dir(path)
+    } else {
+        read_bank_file(path)
+    }
+}
+
+fn read_bank_file(path: &Path) -> Result<String, String> {
+    let content = read(path)?;
+    // Un fichero vacío es un banco sin preguntas (p. ej. un lenguaje aún sin contenido).
+    Ok(if content.trim().is_empty() {
+        EMPTY_BANK.to_string()
+    } else {
+        content
+    })
+}
+
+/// Une `module_<n>.yaml` (ordenados por `n`) y la teoría de cada nivel en un único YAML.
+pub fn merge_bank_dir(dir: &Path) -> Result<String, String> {
+    let key = dir.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let language = LANGUAGE_DIRS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, name)| *name)
+        .ok_or_else(|| format!("{}: lenguaje desconocido `{key}`", dir.display()))?;
+
+    let mut module_files = Vec::new();
+    let entries = fs::read_dir(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    for entry in entries {
+        let path = entry.map_err(|e| format!("{}: {e}", dir.display()))?.path();
+        if let Some(number) = module_file_number(&path) {
+            module_files.push((number, path));
+        }
+    }
+    module_files.sort();
+
+    let mut modules = Vec::new();
+    for (file_number, path) in module_files {
+        let mut module: Value =
+            serde_yaml::from_str(&read(&path)?).map_err(|e| format!("{}: {e}", path.display()))?;
+        let Some(fields) = module.as_mapping_mut() else {
+            return Err(format!(
+                "{}: se esperaba un módulo (mapa YAML)",
+                path.display()
+            ));
+        };
+        let number = match fields.get("number").and_then(Value::as_u64) {
+            Some(n) => n,
+            None => {
+                fields.insert("number".into(), file_number.into());
+                file_number
+            }
+        };
+
+        if let Some(levels) = fields.get_mut("levels").and_then(Value::as_sequence_mut) {
+            for level in levels {
+                attach_theory(dir, language, number, level)?;
+            }
+        }
+        modules.push(module);
+    }
+
+    let mut bank = Mapping::new();
+    bank.insert("modules".into(), Value::Sequence(modules));
+    let replaced_by = dir.join("replaced_by.yaml");
+    if replaced_by.is_file() {
+        let map: Value = serde_yaml::from_str(&read(&replaced_by)?)
+            .map_err(|e| format!("{}: {e}", replaced_by.display()))?;
+        bank.insert("replaced_by".into(), map);
+    }
+
+    serde_yaml::to_string(&bank).map_err(|e| format!("{}: {e}", dir.display()))
+}
+
+/// `module_<n>.yaml` -> `n`.
+fn module_file_number(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    name.strip_prefix("module_")?
+        .strip_suffix(".yaml")?
+        .parse()
+        .ok()
+}
+
+fn theory_path(dir: &Path, module: u64, level: u64) -> PathBuf {
+    dir.join(format!("module_{module}_level_{level}.md"))
+}
+
+/// Pone el contenido de `module_<n>_level_<m>.md` en `explanation.<Lenguaje>`.
+fn attach_theory(dir: &Path, language: &str, module: u64, level: &mut Value) -> Result<(), String> {
+    let Some(fields) = level.as_mapping_mut() else {
+        return Ok(());
+    };
+    if fields.get("explanation").is_none_or(Value::is_null) {
+        fields.insert("explanation".into(), Value::Mapping(Mapping::new()));
+    }
+
+    let Some(number) = fields.get("number").and_then(Value::as_u64) else {
+        return Ok(());
+    };
+    let path = theory_path(dir, module, number);
+    if path.is_file() {
+        let theory = read(&path)?;
+        // Un fichero vacío no es teoría: el nivel se abre sin pantalla de teoría.
+        if theory.trim().is_empty() {
+            return Ok(());
+        }
+        fields
+            .get_mut("explanation")
+            .and_then(Value::as_mapping_mut)
+            .ok_or_else(|| format!("{}: `explanation` debe ser un mapa", path.display()))?
+            .insert(language.into(), theory.into());
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("No se pudo leer {}: {e}", path.display()))
+}
@@ -0,0 +1,786 @@
+//! Valida los bancos de preguntas YAML y genera el informe `<banco>_validation.md`.
+//!
+//! Uso: `summer_quiz_bank [--write] [--strict] [--endpoint URL] [banco.yaml|directorio...]`
+//!
+//! - Sin ficheros, valida todos los bancos de `src/data`: los `quiz_questions_*.yaml`
+//!   y los directorios por lenguaje (`src/data/c/`, ...).
+//! - `--write` guarda el informe junto a cada banco además de imprimirlo.
+//! - `--endpoint` evalúa las respuestas de referencia con `POST /api/judge/batch`
+//!   de un judge server en lugar de con los compiladores locales.
+//! - `--strict` cuenta como error los lenguajes sin toolchain disponible.
+//!
+//! Sale con código 1 si algún banco tiene problemas y 2 si no se pudo ejecutar; los
+//! bancos del repositorio deben pasar sin problemas, así que sirve de comprobación en CI.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use summer_quiz::bank_layout::{LANGUAGE_DIRS, read_bank_path};
+use summer_quiz::blanks::template_problems;
+use summer_quiz::data::parse_bank;
+use summer_quiz::judge::{
+    judge_c::{JudgeResult, grade_c_question},
+    judge_capabilities::{JudgeCapabilities, capability_key, detect_local_capabilities},
+    judge_java::grade_java_question,
+    judge_kt::grade_kotlin_question,
+    judge_pseudo::{CJudge, PseudoConfig, run_pseudo_tests},
+    judge_python::grade_python_question,
+    judge_rust::grade_rust_question,
+    protocol::{JudgeBatchRequest, JudgeBatchResponse, JudgeRequest},
+};
+use summer_quiz::model::{Equivalence, GradingMode, Language, Question, QuestionKind, Quiz};
+use summer_quiz::parsons::{self, parsons_problems};
+use summer_quiz::predict::prediction_problems;
+use summer_quiz::requirements::requirement_problems;
+
+const DEFAULT_DATA_DIR: &str = "src/data";
+
+struct Options {
+    banks: Vec<PathBuf>,
+    write: bool,
+    strict: bool,
+    endpoint: Option<String>,
+}
+
+/// Pregunta localizada dentro del banco, para poder citarla en el informe.
+struct Located {
+    question: Question,
+    module: usize,
+    level: usize,
+    index: usize,
+}
+
+impl Located {
+    fn label(&self) -> String {
+        let id = match self.question.id.trim() {
+            "" => "(sin id)",
+            id => id,
+        };
+        format!(
+            "`{id}` (módulo {}, nivel {}, pregunta {})",
+            self.module,
+            self.level,
+            self.index + 1
+        )
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    name: String,
+    parse_error: Option<String>,
+    total: usize,
+    modes: BTreeMap<&'static str, usize>,
+    /// sección -> entradas
+    problems: BTreeMap<String, Vec<String>>,
+    skipped: Vec<String>,
+}
+
+impl Report {
+    fn push(&mut self, section: impl Into<String>, entry: String) {
+        self.problems.entry(section.into()).or_default().push(entry);
+    }
+
+    fn problem_count(&self) -> usize {
+        self.problems.values().map(Vec::len).sum::<usize>()
+            + usize::from(self.parse_error.is_some())
+    }
+
+    fn to_markdown(&self, sections: &[String]) -> String {
+        let mut out = format!("# {} validation\n\n", self.name);
+        if let Some(err) = &self.parse_error {
+            out.push_str(&format!("- parse_error: {err}\n"));
+            return out;
+        }
+
+        out.push_str(&format!("- total_questions: {}\n", self.total));
+        for (mode, count) in &self.modes {
+            out.push_str(&format!("- {mode}: {count}\n"));
+        }
+        for section in sections {
+            let count = self.problems.get(section.as_str()).map_or(0, Vec::len);
+            out.push_str(&format!("- {section}: {count}\n"));
+        }
+        if !self.skipped.is_empty() {
+            out.push_str(&format!("- skipped: {}\n", self.skipped.len()));
+        }
+
+        for section in sections {
+            out.push_str(&format!("\n## {section}\n"));
+            match self.problems.get(section.as_str()) {
+                Some(entries) if !entries.is_empty() => {
+                    for entry in entries {
+                        out.push_str(&format!("- {entry}\n"));
+                    }
+                }
+                _ => out.push_str("- none\n"),
+            }
+        }
+
+        if !self.skipped.is_empty() {
+            out.push_str("\n## skipped\n");
+            for entry in &self.skipped {
+                out.push_str(&format!("- {entry}\n"));
+            }
+        }
+        out
+    }
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{err}");
+            eprintln!(
+                "Uso: summer_quiz_bank [--write] [--strict] [--endpoint URL] [banco.yaml|directorio...]"
+            );
+            std::process::exit(2);
+        }
+    };
+
+    let capabilities = match &options.endpoint {
+        Some(_) => None,
+        None => Some(detect_local_capabilities()),
+    };
+
+    // Los ids deben ser únicos entre todos los bancos (el progreso se guarda por id).
+    let mut seen_ids: HashMap<String, String> = HashMap::new();
+    let mut total_problems = 0;
+
+    for path in &options.banks {
+        let (mut report, questions) = match validate_bank(path, &mut seen_ids) {
+            Ok(validated) => validated,
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(2);
+            }
+        };
+        judge_answers(&options, capabilities.as_ref(), &questions, &mut report);
+        report.name = bank_name(path);
+
+        let markdown = report.to_markdown(&sections_for(&report));
+        println!("{markdown}");
+        if options.write {
+            let out = path.with_file_name(format!("{}_validation.md", report.name));
+            if let Err(err) = std::fs::write(&out, &markdown) {
+                eprintln!("No se pudo escribir {}: {err}", out.display());
+                std::process::exit(2);
+            }
+        }
+        total_problems += report.problem_count();
+    }
+
+    if total_problems > 0 {
+        eprintln!("❌ {total_problems} problema(s) en los bancos de preguntas.");
+        std::process::exit(1);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        banks: Vec::new(),
+        write: false,
+        strict: false,
+        endpoint: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--write" => options.write = true,
+            "--strict" => options.strict = true,
+            "--endpoint" => {
+                options.endpoint = Some(args.next().ok_or("--endpoint necesita una URL")?);
+            }
+            flag if flag.starts_with("--") => return Err(format!("Opción desconocida: {flag}")),
+            path => options.banks.push(PathBuf::from(path)),
+        }
+    }
+
+    if options.banks.is_empty() {
+        let entries = std::fs::read_dir(DEFAULT_DATA_DIR)
+            .map_err(|e| format!("No se pudo leer {DEFAULT_DATA_DIR}: {e}"))?;
+        options.banks = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| is_bank_path(p))
+            .collect();
+        options.banks.sort();
+    }
+
+    Ok(options)
+}
+
+/// `quiz_questions_*.yaml` o un directorio de lenguaje (`c/`, `kotlin/`...).
+fn is_bank_path(path: &Path) -> bool {
+    let Some(name) = path.file_name().map(|n| n.to_string_lossy()) else {
+        return false;
+    };
+    if path.is_dir() {
+        LANGUAGE_DIRS.iter().any(|(key, _)| name == *key)
+    } else {
+        name.starts_with("quiz_questions_") && name.ends_with(".yaml")
+    }
+}
+
+fn bank_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Secciones del informe en orden fijo; siempre aparecen aunque estén vacías.
+fn sections_for(report: &Report) -> Vec<String> {
+    let mut sections: Vec<String> = [
+        "missing_ids",
+        "duplicate_ids",
+        "migration_conflicts",
+        "mode_language_mismatch",
+        "judge_without_tests",
+        "judge_with_empty_output",
+        "invalid_choices",
+        "invalid_blanks",
+        "invalid_predictions",
+        "invalid_parsons",
+        "ignored_alternatives",
+        "invalid_requirements",
+    ]
+    .map(String::from)
+    .to_vec();
+    for mode in report.modes.keys() {
+        if *mode != "normalize" {
+            sections.push(compile_errors_section(mode));
+            sections.push(failures_section(mode));
+        }
+    }
+    for section in report.problems.keys() {
+        if !sections.contains(section) {
+            sections.push(section.clone());
+        }
+    }
+    sections
+}
+
+fn validate_bank(
+    path: &Path,
+    seen_ids: &mut HashMap<String, String>,
+) -> Result<(Report, Vec<Located>), String> {
+    let content = read_bank_path(path)?;
+
+    let mut report = Report::default();
+
+    let quiz: Quiz = match parse_bank(&path.display().to_string(), &content) {
+        Ok(quiz) => quiz,
+        Err(err) => {
+            report.parse_error = Some(err.to_string());
+            return Ok((report, Vec::new()));
+        }
+    };
+
+    let bank = bank_name(path);
+    let mut questions = Vec::new();
+    for module in &quiz.modules {
+        for level in &module.levels {
+            for (index, question) in level.questions.iter().enumerate() {
+                let mut question = question.clone();
+                // Los Parsons con juez se validan con el programa montado en orden.
+                if question.kind == QuestionKind::Parsons && question.answer.is_empty() {
+                    question.answer = parsons::solution(&question);
+                }
+                questions.push(Located {
+                    question,
+                    module: module.number,
+                    level: level.number,
+                    index,
+                });
+            }
+        }
+    }
+
+    report.total = questions.len();
+    for located in &questions {
+        let q = &located.question;
+        let mode = effective_mode(q);
+        *report.modes.entry(mode_name(&mode)).or_default() += 1;
+
+        match q.id.trim() {
+            "" => report.push("missing_ids", located.label()),
+            id => {
+                if let Some(previous) = seen_ids.insert(id.to_string(), bank.clone()) {
+                    report.push(
+                        "duplicate_ids",
+                        format!("{} (ya usado en {previous})", located.label()),
+                    );
+                }
+            }
+        }
+
+        if let Some(expected) = expected_language(&mode)
+            && !expected.contains(&q.language)
+        {
+            report.push(
+                "mode_language_mismatch",
+                format!(
+                    "{}: `{}` con language {:?}",
+                    located.label(),
+                    mode_name(&mode),
+                    q.language
+                ),
+            );
+        }
+
+        if let Some(problem) = q.choice_problem() {
+            report.push("invalid_choices", format!("{}: {problem}", located.label()));
+        }
+        if q.kind == QuestionKind::FillBlanks {
+            for problem in template_problems(q) {
+                report.push("invalid_blanks", format!("{}: {problem}", located.label()));
+            }
+        }
+        if q.kind == QuestionKind::PredictOutput {
+            for problem in prediction_problems(q) {
+                report.push(
+                    "invalid_predictions",
+                    format!("{}: {problem}", located.label()),
+                );
+            }
+        }
+        if q.kind == QuestionKind::Parsons {
+            for problem in parsons_problems(q) {
+                report.push("invalid_parsons", format!("{}: {problem}", located.label()));
+            }
+        }
+
+        for problem in requirement_problems(q) {
+            report.push(
+                "invalid_requirements",
+                format!("{}: {problem}", located.label()),
+            );
+        }
+        // `answers` y `equivalences` solo se usan al comparar código sin juez (`renaming`, en C).
+        if (!q.answers.is_empty() || !q.equivalences.is_empty())
+            && (q.kind != QuestionKind::Code
+                || mode != GradingMode::Normalize
+                || q.language != Language::C && q.equivalences.contains(&Equivalence::Renaming))
+        {
+            report.push("ignored_alternatives", located.label());
+        }
+
+        if is_judged(&mode) {
+            if q.tests.is_empty() {
+                report.push("judge_without_tests", located.label());
+            } else if q.tests.iter().any(|t| t.output.trim().is_empty()) {
+                report.push("judge_with_empty_output", located.label());
+            }
+        }
+    }
+
+    check_migrations(&quiz, &questions, &mut report);
+    Ok((report, questions))
+}
+
+/// `aliases` y `replaced_by` solo tienen sentido con `id`s que ya no están en el banco,
+/// y los reemplazos deben apuntar a preguntas que sí existen.
+fn check_migrations(quiz: &Quiz, questions: &[Located], report: &mut Report) {
+    let live: HashSet<&str> = questions.iter().map(|l| l.question.id.as_str()).collect();
+
+    for located in questions {
+        for alias in &located.question.aliases {
+            if live.contains(alias.as_str()) {
+                report.push(
+                    "migration_conflicts",
+                    format!(
+                        "{}: el alias `{alias}` sigue siendo un id del banco",
+                        located.label()
+                    ),
+                );
+            }
+        }
+    }
+
+    let mut replaced: Vec<_> = quiz.replaced_by.iter().collect();
+    replaced.sort();
+    for (old, new_ids) in replaced {
+        if live.contains(old.as_str()) {
+            report.push(
+                "migration_conflicts",
+                format!("replaced_by: `{old}` sigue siendo un id del banco"),
+            );
+        }
+        for new_id in new_ids.iter().filter(|id| !live.contains(id.as_str())) {
+            report.push(
+                "migration_conflicts",
+                format!("replaced_by: `{old}` -> `{new_id}` no existe en el banco"),
+            );
+        }
+    }
+}
+
+/// Modo con el que la app evalúa la pregunta (`mode` ausente = C con tests o normalize).
+fn effective_mode(q: &Question) -> GradingMode {
+    match &q.mode {
+        Some(mode) => mode.clone(),
+        None if q.uses_judge_c() => GradingMode::JudgeC,
+        None => GradingMode::Normalize,
+    }
+}
+
+fn mode_name(mode: &GradingMode) -> &'static str {
+    match mode {
+        GradingMode::Normalize => "normalize",
+        GradingMode::JudgeC => "judge_c",
+        GradingMode::JudgePseudo => "judge_pseudo",
+        GradingMode::JudgeKotlin => "judge_kotlin",
+        GradingMode::JudgeJava => "judge_java",
+        GradingMode::JudgeRust => "judge_rust",
+        GradingMode::JudgePython => "judge_python",
+        GradingMode::JudgeRemote => "judge_remote",
+    }
+}
+
+fn compile_errors_section(mode: &str) -> String {
+    format!("{mode}_compile_errors")
+}
+
+fn failures_section(mode: &str) -> String {
+    format!("{mode}_failures")
+}
+
+fn expected_language(mode: &GradingMode) -> Option<&'static [Language]> {
+    match mode {
+        GradingMode::JudgeC => Some(&[Language::C]),
+        GradingMode::JudgePseudo => Some(&[Language::Pseudocode]),
+        GradingMode::JudgeKotlin => Some(&[Language::Kotlin]),
+        GradingMode::JudgeJava => Some(&[Language::Java]),
+        GradingMode::JudgeRust => Some(&[Language::Rust]),
+        GradingMode::JudgePython => Some(&[Language::Python]),
+        GradingMode::Normalize | GradingMode::JudgeRemote => None,
+    }
+}
+
+fn is_judged(mode: &GradingMode) -> bool {
+    !matches!(mode, GradingMode::Normalize)
+}
+
+/// Lenguaje del judge server para las preguntas que puede evaluar en remoto.
+fn remote_language(mode: &GradingMode, language: Language) -> Option<&'static str> {
+    match mode {
+        GradingMode::JudgeC => Some("c"),
+        GradingMode::JudgeKotlin => Some("kotlin"),
+        GradingMode::JudgeJava => Some("java"),
+        GradingMode::JudgeRust => Some("rust"),
+        GradingMode::JudgePython => Some("python"),
+        GradingMode::JudgeRemote => match language {
+            Language::Pseudocode => None,
+            other => capability_key(other),
+        },
+        GradingMode::Normalize | GradingMode::JudgePseudo => None,
+    }
+}
+
+fn grade_locally(q: &Question, mode: &GradingMode) -> JudgeResult {
+    match mode {
+        GradingMode::JudgePseudo => {
+            run_pseudo_tests(&q.answer, &q.tests, &PseudoConfig::default(), &CJudge)
+        }
+        GradingMode::JudgeKotlin => grade_kotlin_question(q, &q.answer),
+        GradingMode::JudgeJava => grade_java_question(q, &q.answer),
+        GradingMode::JudgeRust => grade_rust_question(q, &q.answer),
+        GradingMode::JudgePython => grade_python_question(q, &q.answer),
+        GradingMode::JudgeRemote => match q.language {
+            Language::Kotlin => grade_kotlin_question(q, &q.answer),
+            Language::Java => grade_java_question(q, &q.answer),
+            Language::Rust => grade_rust_question(q, &q.answer),
+            Language::Python => grade_python_question(q, &q.answer),
+            _ => grade_c_question(q, &q.answer),
+        },
+        _ => grade_c_question(q, &q.answer),
+    }
+}
+
+/// Evalúa la respuesta de referencia de cada pregunta con tests y anota
+/// errores de compilación y casos fallidos en el informe.
+fn judge_answers(
+    options: &Options,
+    capabilities: Option<&JudgeCapabilities>,
+    questions: &[Located],
+    report: &mut Report,
+) {
+    let mut pending = Vec::new();
+    for located in questions {
+        let q = &located.question;
+        let mode = effective_mode(q);
+        if !is_judged(&mode) || q.tests.is_empty() {
+            continue;
+        }
+
+        let language = match mode {
+            GradingMode::JudgeRemote => q.language,
+            GradingMode::JudgePseudo => Language::Pseudocode,
+            _ => expected_language(&mode)
+                .and_then(|l| l.first().copied())
+                .unwrap_or(q.language),
+        };
+        if let Some(reason) = capabilities.and_then(|c| c.unavailable_reason(language)) {
+            let first_line = reason.lines().next().unwrap_or_default().to_string();
+            if options.strict {
+                report.push(
+                    failures_section(mode_name(&mode)),
+                    format!("{}: {first_line}", located.label()),
+                );
+            } else {
+                report
+                    .skipped
+                    .push(format!("{}: {first_line}", located.label()));
+            }
+            continue;
+        }
+        pending.push((located, mode));
+    }
+
+    let results: Vec<JudgeResult> = match &options.endpoint {
+        Some(endpoint) => grade_remotely(endpoint, &pending),
+        None => grade_in_parallel(&pending),
+    };
+
+    for ((located, mode), result) in pending.iter().zip(results) {
+        let mode = mode_name(mode);
+        let label = located.label();
+        match result {
+            JudgeResult::Accepted { .. } => {}
+            JudgeResult::CompileError { stderr } => report.push(
+                compile_errors_section(mode),
+                format!("{label}: {}", first_lines(&stderr, 3)),
+            ),
+            JudgeResult::WrongAnswer {
+                test_index,
+                expected,
+                received,
+                ..
+            } => report.push(
+                failures_section(mode),
+                format!(
+                    "{label}: wrong answer en el caso #{test_index} (esperado {:?}, recibido {:?})",
+                    expected, received
+                ),
+            ),
+            JudgeResult::Timeout {
+                test_index,
+                timeout_ms,
+                ..
+            } => report.push(
+                failures_section(mode),
+                format!("{label}: timeout en el caso #{test_index} ({timeout_ms} ms)"),
+            ),
+            JudgeResult::RuntimeError {
+                test_index,
+                exit_code,
+                ..
+            } => report.push(
+                failures_section(mode),
+                format!(
+                    "{label}: runtime error en el caso #{test_index} (exit code {exit_code:?})"
+                ),
+            ),
+            JudgeResult::InfrastructureError { message }
+            | JudgeResult::RequirementViolation { message } => report.push(
+                failures_section(mode),
+                format!("{label}: {}", first_lines(&message, 1)),
+            ),
+        }
+    }
+}
+
+fn grade_in_parallel(pending: &[(&Located, GradingMode)]) -> Vec<JudgeResult> {
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(2)
+        .min(pending.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<JudgeResult>>> = Mutex::new(vec![None; pending.len()]);
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((located, mode)) = pending.get(i) else {
+                        break;
+                    };
+                    let result = grade_locally(&located.question, mode);
+                    results.lock().unwrap_or_else(|e| e.into_inner())[i] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .map(|r| {
+            r.unwrap_or(JudgeResult::InfrastructureError {
+                message: "La evaluación no terminó.".into(),
+            })
+        })
+        .collect()
+}
+
+fn grade_remotely(endpoint: &str, pending: &[(&Located, GradingMode)]) -> Vec<JudgeResult> {
+    // El pseudocódigo se transpila en el cliente; el servidor solo recibe C.
+    let mut results: Vec<Option<JudgeResult>> = vec![None; pending.len()];
+    let mut remote = Vec::new();
+    for (i, (located, mode)) in pending.iter().enumerate() {
+        let q = &located.question;
+        match remote_language(mode, q.language) {
+            Some(language) => remote.push((
+                i,
+                JudgeRequest::new(
+                    language,
+                    &q.answer,
+                    q.tests.clone(),
+                    q.judge_harness.clone(),
+                    Some(q.id.clone()),
+                ),
+            )),
+            None => results[i] = Some(grade_locally(q, mode)),
+        }
+    }
+
+    if !remote.is_empty() {
+        let (indices, submissions): (Vec<usize>, Vec<JudgeRequest>) = remote.into_iter().unzip();
+        match post_batch(endpoint, JudgeBatchRequest::new(submissions)) {
+            Ok(response) => {
+                for (i, item) in indices.into_iter().zip(response.results) {
+                    results[i] = Some(item.outcome.into());
+                }
+            }
+            Err(message) => {
+                for i in indices {
+                    results[i] = Some(JudgeResult::InfrastructureError {
+                        message: message.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|r| {
+            r.unwrap_or(JudgeResult::InfrastructureError {
+                message: "El judge no devolvió resultado para este envío.".into(),
+            })
+        })
+        .collect()
+}
+
+fn post_batch(endpoint: &str, batch: JudgeBatchRequest) -> Result<JudgeBatchResponse, String> {
+    let url = format!("{}/api/judge/batch", endpoint.trim().trim_end_matches('/'));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(None)
+        .build()
+        .map_err(|e| format!("No se pudo crear el cliente HTTP: {e}"))?;
+
+    let mut request = client.post(&url).json(&batch);
+    if let Ok(token) = std::env::var("SUMMER_QUIZ_JUDGE_TOKEN")
+        && !token.trim().is_empty()
+    {
+        request = request.bearer_auth(token.trim());
+    }
+
+    let response = request
+        .send()
+        .map_err(|e| format!("Error conectando con {url}: {e}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        return Err(format!("{url} devolvió HTTP {status}: {}", body.trim()));
+    }
+    response
+        .json::<JudgeBatchResponse>()
+        .map_err(|e| format!("Respuesta JSON inválida de {url}: {e}"))
+}
+
+fn first_lines(text: &str, n: usize) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .take(n)
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"modules:
+- number: 1
+  explanation: Fixture
+  levels:
+  - number: 1
+    explanation: {}
+    questions:
+    - id: c-fixture-ok
+      language: C
+      module: 1
+      mode: judge_c
+      prompt: Suma
+      answer: |
+        #include <stdio.h>
+        int main(void) { int a, b; scanf("%d %d", &a, &b); printf("%d\n", a + b); return 0; }
+      tests:
+      - input: "2 3\n"
+        output: "5\n"
+    - id: c-fixture-roto
+      language: C
+      module: 1
+      mode: judge_c
+      prompt: Resta
+      answer: |
+        #include <stdio.h>
+        int main(void) { int a, b; scanf("%d %d", &a, &b); printf("%d\n", a + b); return 0; }
+      tests:
+      - input: "2 3\n"
+        output: "-1\n"
+    - id: c-fixture-ok
+      language: C
+      module: 1
+      prompt: Duplicada
+      answer: int x;
+"#;
+
+    #[test]
+    fn flags_the_broken_question_of_a_fixture_bank() {
+        let dir = std::env::temp_dir().join(format!("summer_quiz_bank_cli_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("quiz_questions_fixture.yaml");
+        std::fs::write(&path, FIXTURE).unwrap();
+
+        let (mut report, questions) = validate_bank(&path, &mut HashMap::new()).unwrap();
+        assert_eq!(report.parse_error, None);
+        assert_eq!(report.total, 3);
+        assert_eq!(report.problems["duplicate_ids"].len(), 1);
+        assert!(report.problems["duplicate_ids"][0].contains("pregunta 3"));
+
+        let options = Options {
+            banks: vec![path.clone()],
+            write: false,
+            strict: false,
+            endpoint: None,
+        };
+        let capabilities = detect_local_capabilities();
+        judge_answers(&options, Some(&capabilities), &questions, &mut report);
+        if capabilities.unavailable_reason(Language::C).is_none() {
+            let failures = &report.problems["judge_c_failures"];
+            assert_eq!(failures.len(), 1);
+            assert!(failures[0].contains("c-fixture-roto"));
+            assert_eq!(report.problem_count(), 2);
+        } else {
+            assert_eq!(report.skipped.len(), 2);
+        }
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
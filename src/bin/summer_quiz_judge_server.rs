@@ -1,103 +1,482 @@
-use axum::http::StatusCode;
+use axum::extract::{DefaultBodyLimit, FromRef, Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::{Json, Router};
-use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
 use tower_http::cors::CorsLayer;
 
 use summer_quiz::judge::{
-    judge_c::{grade_c_question, JudgeResult},
+    judge_auth::{JudgeAuth, mint_token, token_secret_from_env},
+    judge_c::{JudgeResult, grade_c_question},
+    judge_capabilities::{
+        JudgeCapabilities, MAX_BATCH_REQUEST_BYTES, MAX_BATCH_SIZE, MAX_REQUEST_BYTES,
+        detect_local_capabilities,
+    },
     judge_java::grade_java_question,
     judge_kt::grade_kotlin_question,
+    judge_metrics::JudgeMetrics,
     judge_python::grade_python_question,
     judge_rust::grade_rust_question,
+    judge_utils::take_judge_timings,
+    protocol::{
+        JudgeBatchItem, JudgeBatchRequest, JudgeBatchResponse, JudgeOutcome, JudgeRequest,
+        JudgeResponse, PROTOCOL_VERSION,
+    },
 };
-use summer_quiz::model::{GradingMode, JudgeTestCase, Language, Question};
+use summer_quiz::model::{GradingMode, Language, Question};
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Cada cuánto se vuelven a detectar los toolchains (instalar kotlinc no requiere reiniciar).
+const CAPABILITIES_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Clone)]
+struct ServerState {
+    metrics: Arc<JudgeMetrics>,
+    capabilities: Arc<CapabilitiesCache>,
+    /// Limita las compilaciones/ejecuciones simultáneas (peticiones sueltas y lotes).
+    workers: Arc<Semaphore>,
+    /// Huecos de `workers` que pueden ocupar (o esperar) a la vez los envíos de
+    /// lotes, para que un lote grande no deje en cola a las peticiones sueltas.
+    /// `None` con un solo worker: entonces no se aceptan lotes.
+    batch_slots: Option<Arc<Semaphore>>,
+}
+
+impl ServerState {
+    fn new(workers: usize, auth_required: bool) -> Self {
+        Self {
+            metrics: Arc::new(JudgeMetrics::new()),
+            capabilities: Arc::new(CapabilitiesCache {
+                auth_required,
+                workers,
+                cached: Mutex::new(None),
+            }),
+            workers: Arc::new(Semaphore::new(workers)),
+            batch_slots: batch_slots(workers),
+        }
+    }
+}
+
+impl FromRef<ServerState> for Arc<JudgeMetrics> {
+    fn from_ref(state: &ServerState) -> Self {
+        state.metrics.clone()
+    }
+}
+
+struct CapabilitiesCache {
+    auth_required: bool,
+    workers: usize,
+    cached: Mutex<Option<(Instant, JudgeCapabilities)>>,
+}
 
-#[derive(Debug, Deserialize)]
-struct JudgeRequest {
+impl CapabilitiesCache {
+    async fn get(&self) -> JudgeCapabilities {
+        if let Some((at, caps)) = self
+            .cached
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            && at.elapsed() < CAPABILITIES_TTL
+        {
+            return caps.clone();
+        }
+
+        // Lanzar los compiladores con `--version` bloquea; fuera del runtime async.
+        let mut caps = tokio::task::spawn_blocking(detect_local_capabilities)
+            .await
+            .unwrap_or_else(|e| panic!("La detección de toolchains falló: {e}"));
+        caps.auth_required = self.auth_required;
+        caps.limits.workers = self.workers;
+
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((Instant::now(), caps.clone()));
+        caps
+    }
+}
+
+/// Datos de la evaluación que el handler deja en la respuesta para el log de la petición.
+#[derive(Debug, Clone)]
+struct JudgeLogFields {
     language: String,
-    source: String,
-    tests: Vec<JudgeTestCase>,
-    harness: Option<String>,
-    #[allow(dead_code)]
-    question_id: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-#[serde(tag = "status", rename_all = "snake_case")]
-enum JudgeResponse {
-    Accepted,
-    CompileError {
-        stderr: String,
-    },
-    WrongAnswer {
-        test_index: usize,
-        input: String,
-        expected: String,
-        received: String,
-        diff: String,
-    },
-    Timeout {
-        test_index: usize,
-        input: String,
-        timeout_ms: u64,
-    },
-    RuntimeError {
-        test_index: usize,
-        input: String,
-        stderr: String,
-        exit_code: Option<i32>,
-    },
-    InfrastructureError {
-        message: String,
-    },
+    verdict: &'static str,
+    compile_ms: Option<u128>,
+    run_ms: Option<u128>,
 }
 
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("mint-token") {
+        run_mint_token(&args[1..]);
+        return;
+    }
+
     let bind = std::env::var("JUDGE_BIND").unwrap_or_else(|_| "0.0.0.0:8787".to_string());
 
-    let app = Router::new()
+    let auth =
+        JudgeAuth::from_env().unwrap_or_else(|e| panic!("Configuración de auth inválida: {e}"));
+    let auth_enabled = auth.is_enabled();
+    let auth = Arc::new(auth);
+    let workers = std::env::var("JUDGE_WORKERS")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(2)
+        });
+    let state = ServerState::new(workers, auth_enabled);
+    let metrics = state.metrics.clone();
+
+    let judge_routes = Router::new()
         .route("/api/judge/sync", post(handle_judge))
         .route("/api/judge", post(handle_judge))
         .route("/judge/sync", post(handle_judge))
         .route("/judge", post(handle_judge))
-        .route("/health", get(|| async { "ok" }))
-        .layer(CorsLayer::permissive());
+        .layer(DefaultBodyLimit::max(MAX_REQUEST_BYTES))
+        .merge(
+            Router::new()
+                .route("/api/judge/batch", post(handle_judge_batch))
+                .route("/judge/batch", post(handle_judge_batch))
+                .layer(DefaultBodyLimit::max(MAX_BATCH_REQUEST_BYTES)),
+        )
+        .route_layer(middleware::from_fn_with_state(auth, require_auth));
+
+    let app = Router::new()
+        .merge(judge_routes)
+        .route("/health", get(health))
+        .route("/capabilities", get(capabilities))
+        .route("/api/capabilities", get(capabilities))
+        .route("/metrics", get(render_metrics))
+        .layer(middleware::from_fn_with_state(metrics, observe_request))
+        .layer(CorsLayer::permissive())
+        .with_state(state);
 
     let listener = tokio::net::TcpListener::bind(&bind)
         .await
         .unwrap_or_else(|e| panic!("No se pudo abrir {bind}: {e}"));
 
-    println!("summer_quiz judge server escuchando en http://{bind}");
+    log_event(
+        "info",
+        "listening",
+        serde_json::json!({ "bind": bind, "auth": auth_enabled, "workers": workers }),
+    );
 
-    axum::serve(listener, app)
-        .await
-        .expect("server error");
+    axum::serve(listener, app).await.expect("server error");
+}
+
+/// Uso: `summer_quiz_judge_server mint-token <sujeto> [ttl_segundos]`
+/// (requiere `JUDGE_TOKEN_SECRET` o `JUDGE_TOKEN_SECRET_FILE`).
+fn run_mint_token(args: &[String]) {
+    let subject = args.first().map(String::as_str).unwrap_or("classroom");
+    let ttl_secs = args
+        .get(1)
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(12 * 60 * 60);
+
+    let secret = match token_secret_from_env() {
+        Ok(Some(secret)) => secret,
+        Ok(None) => {
+            eprintln!("Define JUDGE_TOKEN_SECRET o JUDGE_TOKEN_SECRET_FILE para firmar tokens.");
+            std::process::exit(2);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+
+    match mint_token(&secret, subject, ttl_secs) {
+        Ok(token) => println!("{token}"),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    }
+}
+
+async fn require_auth(
+    State(auth): State<Arc<JudgeAuth>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    let header = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    match auth.check_header(header) {
+        Ok(()) => Ok(next.run(request).await),
+        Err(err) => Err((StatusCode::UNAUTHORIZED, err.message().to_string())),
+    }
+}
+
+/// Asigna (o respeta) un `x-request-id`, lo devuelve en la respuesta y escribe
+/// una línea de log JSON por petición.
+async fn observe_request(
+    State(metrics): State<Arc<JudgeMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty() && v.len() <= 64)
+        .map(str::to_string)
+        .unwrap_or_else(new_request_id);
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+
+    let mut response = next.run(request).await;
+    let status = response.status().as_u16();
+    metrics.record_http_status(status);
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    let mut fields = serde_json::json!({
+        "request_id": request_id,
+        "method": method,
+        "path": path,
+        "status": status,
+        "duration_ms": started.elapsed().as_millis(),
+    });
+    if let Some(judge) = response.extensions().get::<JudgeLogFields>() {
+        fields["language"] = judge.language.clone().into();
+        fields["verdict"] = judge.verdict.into();
+        fields["compile_ms"] = judge.compile_ms.map(|v| v as u64).into();
+        fields["run_ms"] = judge.run_ms.map(|v| v as u64).into();
+    }
+    let level = if status >= 500 { "error" } else { "info" };
+    log_event(level, "request", fields);
+
+    response
+}
+
+/// `ok` si todos los toolchains están disponibles, `degraded` si falta alguno
+/// y 503 si no se puede evaluar ningún lenguaje.
+async fn health(State(state): State<ServerState>) -> Response {
+    let caps = state.capabilities.get().await;
+    let missing: Vec<&str> = caps
+        .languages
+        .iter()
+        .filter(|l| !l.available)
+        .map(|l| l.language.as_str())
+        .collect();
+
+    let (status, label) = if caps.all_available() {
+        (StatusCode::OK, "ok")
+    } else if caps.any_available() {
+        (StatusCode::OK, "degraded")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    };
+
+    (
+        status,
+        Json(serde_json::json!({
+            "status": label,
+            "protocol_version": caps.protocol_version,
+            "missing": missing,
+        })),
+    )
+        .into_response()
+}
+
+async fn capabilities(State(state): State<ServerState>) -> Json<JudgeCapabilities> {
+    Json(state.capabilities.get().await)
+}
+
+async fn render_metrics(State(metrics): State<Arc<JudgeMetrics>>) -> impl IntoResponse {
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        metrics.render(),
+    )
+}
+
+fn new_request_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{:x}-{seq:x}", unix_millis())
+}
+
+fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// Escribe un evento de log estructurado (una línea JSON) en stdout.
+fn log_event(level: &str, event: &str, fields: serde_json::Value) {
+    let mut line = serde_json::json!({
+        "ts_ms": unix_millis() as u64,
+        "level": level,
+        "event": event,
+    });
+    if let (Some(line), serde_json::Value::Object(fields)) = (line.as_object_mut(), fields) {
+        line.extend(fields);
+    }
+    println!("{line}");
 }
 
 async fn handle_judge(
+    State(state): State<ServerState>,
     Json(payload): Json<JudgeRequest>,
-) -> Result<Json<JudgeResponse>, (StatusCode, String)> {
-    // spawn_blocking: compilation/execution is CPU-bound and blocking;
-    // this prevents it from stalling the async runtime so other requests
-    // (including CORS preflight) are handled concurrently.
-    let result = tokio::task::spawn_blocking(move || evaluate(payload))
-        .await
-        .map_err(|e| {
+) -> Result<Response, (StatusCode, String)> {
+    let (result, log) = judge_one(&state, payload).await?;
+    let mut response = Json(result).into_response();
+    response.extensions_mut().insert(log);
+    Ok(response)
+}
+
+/// Evalúa muchos envíos en paralelo (respetando `JUDGE_WORKERS`) y devuelve
+/// los veredictos en el mismo orden en que llegaron.
+async fn handle_judge_batch(
+    State(state): State<ServerState>,
+    Json(batch): Json<JudgeBatchRequest>,
+) -> Result<Json<JudgeBatchResponse>, (StatusCode, String)> {
+    if batch.submissions.len() > MAX_BATCH_SIZE {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "El lote tiene {} envíos; el máximo es {MAX_BATCH_SIZE}.",
+                batch.submissions.len()
+            ),
+        ));
+    }
+
+    let Some(slots) = state.batch_slots.clone() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Con un solo worker (JUDGE_WORKERS=1) no se aceptan lotes: \
+             ocuparían el worker de los envíos de los alumnos."
+                .into(),
+        ));
+    };
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, submission) in batch.submissions.into_iter().enumerate() {
+        let state = state.clone();
+        let slots = slots.clone();
+        tasks.spawn(async move {
+            let question_id = submission.question_id.clone();
+            let outcome = match judge_batch_item(&state, &slots, submission).await {
+                Ok((response, _)) => response.outcome,
+                Err((_, message)) => JudgeOutcome::InfrastructureError { message },
+            };
+            (
+                index,
+                JudgeBatchItem {
+                    question_id,
+                    outcome,
+                },
+            )
+        });
+    }
+
+    let mut results: Vec<Option<JudgeBatchItem>> = vec![None; tasks.len()];
+    while let Some(joined) = tasks.join_next().await {
+        let (index, item) = joined.map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Task panicked: {e}"),
             )
         })?;
+        results[index] = Some(item);
+    }
 
-    Ok(Json(result))
+    Ok(Json(JudgeBatchResponse {
+        protocol_version: PROTOCOL_VERSION,
+        results: results.into_iter().flatten().collect(),
+    }))
+}
+
+/// Como [`judge_one`], pero esperando antes un hueco de lote.
+async fn judge_batch_item(
+    state: &ServerState,
+    slots: &Semaphore,
+    payload: JudgeRequest,
+) -> Result<(JudgeResponse, JudgeLogFields), (StatusCode, String)> {
+    let _slot = slots.acquire().await.map_err(|e| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("Judge no disponible: {e}"),
+        )
+    })?;
+    judge_one(state, payload).await
+}
+
+/// Los lotes usan como mucho la mitad de los workers; con uno solo, ninguno.
+fn batch_slots(workers: usize) -> Option<Arc<Semaphore>> {
+    (workers >= 2).then(|| Arc::new(Semaphore::new(workers / 2)))
+}
+
+async fn judge_one(
+    state: &ServerState,
+    payload: JudgeRequest,
+) -> Result<(JudgeResponse, JudgeLogFields), (StatusCode, String)> {
+    // Solo etiquetas conocidas para no disparar la cardinalidad de las métricas.
+    let language = match payload.language.trim().to_ascii_lowercase().as_str() {
+        known @ ("c" | "kotlin" | "java" | "rust" | "python") => known.to_string(),
+        _ => "other".to_string(),
+    };
+
+    let _permit = state.workers.acquire().await.map_err(|e| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("Judge no disponible: {e}"),
+        )
+    })?;
+    let _job = state.metrics.start_job();
+
+    // spawn_blocking: compilation/execution is CPU-bound and blocking;
+    // this prevents it from stalling the async runtime so other requests
+    // (including CORS preflight) are handled concurrently.
+    let (result, timings) = tokio::task::spawn_blocking(move || {
+        take_judge_timings();
+        let result = evaluate(payload);
+        (result, take_judge_timings())
+    })
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Task panicked: {e}"),
+        )
+    })?;
+
+    let verdict = result.outcome.status();
+    state.metrics.record_judgement(&language, verdict, timings);
+
+    let log = JudgeLogFields {
+        language,
+        verdict,
+        compile_ms: timings.compile.map(|d| d.as_millis()),
+        run_ms: timings.run.map(|d| d.as_millis()),
+    };
+    Ok((result, log))
 }
 
 fn evaluate(payload: JudgeRequest) -> JudgeResponse {
     let question = match build_question(&payload) {
         Ok(q) => q,
-        Err(message) => return JudgeResponse::InfrastructureError { message },
+        Err(message) => {
+            return JudgeResponse::new(JudgeOutcome::InfrastructureError { message });
+        }
     };
 
     let result = match question.mode {
@@ -111,7 +490,7 @@ fn evaluate(payload: JudgeRequest) -> JudgeResponse {
         },
     };
 
-    map_result(result)
+    JudgeResponse::new(result.into())
 }
 
 fn build_question(payload: &JudgeRequest) -> Result<Question, String> {
@@ -130,64 +509,93 @@ fn build_question(payload: &JudgeRequest) -> Result<Question, String> {
 
     Ok(Question {
         language,
-        module: 0,
-        prompt: String::new(),
-        answer: String::new(),
-        hint: None,
-        number: 0,
-        input_prefill: None,
         mode: Some(mode),
         tests: payload.tests.clone(),
         judge_harness: payload.harness.clone(),
-        judge_endpoint: None,
-        is_done: false,
-        saw_solution: false,
-        attempts: 0,
-        fails: 0,
-        skips: 0,
-        id: None,
+        ..Default::default()
     })
 }
 
-fn map_result(result: JudgeResult) -> JudgeResponse {
-    match result {
-        JudgeResult::Accepted => JudgeResponse::Accepted,
-        JudgeResult::CompileError { stderr } => JudgeResponse::CompileError { stderr },
-        JudgeResult::WrongAnswer {
-            test_index,
-            input,
-            expected,
-            received,
-            diff,
-        } => JudgeResponse::WrongAnswer {
-            test_index,
-            input,
-            expected,
-            received,
-            diff,
-        },
-        JudgeResult::Timeout {
-            test_index,
-            input,
-            timeout_ms,
-        } => JudgeResponse::Timeout {
-            test_index,
-            input,
-            timeout_ms,
-        },
-        JudgeResult::RuntimeError {
-            test_index,
-            input,
-            stderr,
-            exit_code,
-        } => JudgeResponse::RuntimeError {
-            test_index,
-            input,
-            stderr,
-            exit_code,
-        },
-        JudgeResult::InfrastructureError { message } => {
-            JudgeResponse::InfrastructureError { message }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use summer_quiz::model::JudgeTestCase;
+
+    fn python(source: &str, id: &str) -> JudgeRequest {
+        let tests = vec![JudgeTestCase {
+            input: "2\n".into(),
+            output: "4\n".into(),
+        }];
+        JudgeRequest::new("python", source, tests, None, Some(id.into()))
+    }
+
+    #[tokio::test]
+    async fn batch_keeps_the_order_of_submissions() {
+        let state = ServerState::new(4, false);
+        let batch = JudgeBatchRequest::new(vec![
+            python("print(int(input()) * 2)", "doble"),
+            JudgeRequest::new("cobol", "x", vec![], None, Some("cobol".into())),
+            python("print(int(input()) + 1)", "siguiente"),
+            python("print(int(input()) * 2)", "doble-2"),
+        ]);
+        let Json(response) = handle_judge_batch(State(state), Json(batch)).await.unwrap();
+        let ids: Vec<_> = response
+            .results
+            .iter()
+            .map(|r| r.question_id.as_deref())
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                Some("doble"),
+                Some("cobol"),
+                Some("siguiente"),
+                Some("doble-2")
+            ]
+        );
+        let statuses: Vec<_> = response
+            .results
+            .iter()
+            .map(|r| r.outcome.status())
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                "accepted",
+                "infrastructure_error",
+                "wrong_answer",
+                "accepted"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn batches_leave_workers_for_single_submissions() {
+        assert!(ServerState::new(1, false).batch_slots.is_none());
+        let state = ServerState::new(4, false);
+        let slots = state.batch_slots.clone().unwrap();
+        assert_eq!(slots.available_permits(), 2);
+
+        // Con los huecos de lote ocupados, un envío del lote espera sin tomar workers...
+        let held = slots.clone().acquire_many_owned(2).await.unwrap();
+        let waiting = tokio::spawn({
+            let state = state.clone();
+            async move { judge_batch_item(&state, &slots, python("print(4)", "lote")).await }
+        });
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
         }
+        assert!(!waiting.is_finished());
+        assert_eq!(state.workers.available_permits(), 4);
+
+        // ...y un envío suelto se evalúa igualmente.
+        let (single, _) = judge_one(&state, python("print(4)", "suelto"))
+            .await
+            .unwrap();
+        assert_eq!(single.outcome.status(), "accepted");
+
+        drop(held);
+        let (batched, _) = waiting.await.unwrap().unwrap();
+        assert_eq!(batched.outcome.status(), "accepted");
     }
 }
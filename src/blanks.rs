@@ -0,0 +1,179 @@
+//! Preguntas `fill_blanks`: una plantilla de código con huecos `{{nombre}}`
+//! que el alumno rellena y que se corrigen uno a uno.
+
+use crate::code_utils::same_code;
+use crate::model::{Blank, BlankCheck, Language, Question};
+use regex::Regex;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    Gap(&'a str),
+}
+
+/// Trocea una línea (o toda la plantilla) en texto fijo y huecos.
+pub fn segments(template: &str) -> Vec<Segment<'_>> {
+    let mut out = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        if start > 0 {
+            out.push(Segment::Text(&rest[..start]));
+        }
+        out.push(Segment::Gap(rest[start + 2..start + 2 + len].trim()));
+        rest = &rest[start + 2 + len + 2..];
+    }
+    if !rest.is_empty() {
+        out.push(Segment::Text(rest));
+    }
+    out
+}
+
+pub fn check_blank(blank: &Blank, language: Language, input: &str) -> bool {
+    match blank.check {
+        BlankCheck::Normalize => same_code(language, input, &blank.answer),
+        BlankCheck::Exact => input.trim() == blank.answer.trim(),
+        BlankCheck::Regex => {
+            Regex::new(&format!("^(?:{})$", blank.answer)).is_ok_and(|re| re.is_match(input.trim()))
+        }
+    }
+}
+
+/// Nombres de los huecos mal rellenados, en el orden de `blanks`.
+pub fn wrong_blanks(question: &Question, inputs: &HashMap<String, String>) -> Vec<String> {
+    question
+        .blanks
+        .iter()
+        .filter(|blank| {
+            let input = inputs
+                .get(&blank.name)
+                .map(String::as_str)
+                .unwrap_or_default();
+            !check_blank(blank, question.language, input)
+        })
+        .map(|blank| blank.name.clone())
+        .collect()
+}
+
+/// La plantilla con cada hueco sustituido por su `solution` o, si no tiene, su `answer`.
+pub fn solution(question: &Question) -> String {
+    let template = question.template.as_deref().unwrap_or_default();
+    segments(template)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.to_string(),
+            Segment::Gap(name) => question
+                .blanks
+                .iter()
+                .find(|blank| blank.name == name)
+                .map(|blank| {
+                    blank
+                        .solution
+                        .clone()
+                        .unwrap_or_else(|| blank.answer.clone())
+                })
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Problemas de definición de una pregunta `fill_blanks` (para `summer_quiz_bank`).
+pub fn template_problems(question: &Question) -> Vec<String> {
+    let Some(template) = question.template.as_deref() else {
+        return vec!["falta `template`".into()];
+    };
+    let gaps: Vec<&str> = segments(template)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Gap(name) => Some(name),
+            Segment::Text(_) => None,
+        })
+        .collect();
+
+    let mut problems = Vec::new();
+    if gaps.is_empty() {
+        problems.push("la plantilla no tiene huecos `{{nombre}}`".into());
+    }
+    // Cada hueco es un campo de texto identificado por su nombre.
+    for (i, gap) in gaps.iter().enumerate() {
+        if gaps[..i].contains(gap) {
+            problems.push(format!("hueco `{gap}` repetido en la plantilla"));
+        } else if !question.blanks.iter().any(|blank| blank.name == *gap) {
+            problems.push(format!("hueco `{gap}` sin entrada en `blanks`"));
+        }
+    }
+    for (i, blank) in question.blanks.iter().enumerate() {
+        if question.blanks[..i]
+            .iter()
+            .any(|other| other.name == blank.name)
+        {
+            problems.push(format!("`{}` repetido en `blanks`", blank.name));
+        }
+        if !gaps.contains(&blank.name.as_str()) {
+            problems.push(format!("`{}` no aparece en la plantilla", blank.name));
+        }
+        if blank.check == BlankCheck::Regex
+            && let Err(err) = Regex::new(&blank.answer)
+        {
+            problems.push(format!("regex de `{}` inválida: {err}", blank.name));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grades_each_blank_with_its_check() {
+        let question: Question = serde_yaml::from_str(
+            r#"
+id: c-blanks
+language: C
+module: 1
+kind: fill_blanks
+prompt: Completa el bucle.
+template: "for (int i = {{inicio}}; {{cond}}; i++) {\n    printf(\"%d\", {{var}});\n}"
+blanks:
+- { name: inicio, answer: "0", check: exact }
+- { name: cond, answer: "i < 10" }
+- { name: var, answer: "i|\\(i\\)", check: regex, solution: i }
+"#,
+        )
+        .unwrap();
+        assert!(template_problems(&question).is_empty());
+
+        let inputs: HashMap<String, String> = [("inicio", "0"), ("cond", "i<10"), ("var", "j")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(wrong_blanks(&question, &inputs), ["var"]);
+        assert_eq!(
+            solution(&question),
+            "for (int i = 0; i < 10; i++) {\n    printf(\"%d\", i);\n}"
+        );
+
+        let repeated = Question {
+            template: Some("{{a}} + {{a}}".into()),
+            blanks: [question.blanks[0].clone(), question.blanks[0].clone()]
+                .into_iter()
+                .map(|blank| Blank {
+                    name: "a".into(),
+                    ..blank
+                })
+                .collect(),
+            ..question
+        };
+        assert_eq!(
+            template_problems(&repeated),
+            [
+                "hueco `a` repetido en la plantilla",
+                "`a` repetido en `blanks`"
+            ]
+        );
+    }
+}
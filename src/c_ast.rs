@@ -0,0 +1,752 @@
+//! Analizador de C para el subconjunto del curso: directivas `#include`/`#define`,
+//! `typedef` de `enum` y `struct`, prototipos y funciones, declaraciones y sentencias.
+//!
+//! Las expresiones no se analizan: se guardan como tokens. Sirve para corregir las
+//! preguntas `normalize` por estructura (ver [`crate::structural`]).
+
+use crate::code_utils::normalize_code;
+use crate::equivalence;
+use crate::lexer::{Token, TokenKind};
+use crate::model::{Equivalence, Language};
+use std::collections::HashSet;
+
+pub type Expr = Vec<Token>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Init {
+    Expr(Expr),
+    /// `{a, b, {c}}`
+    List(Vec<Init>),
+}
+
+/// Una variable, campo o parámetro (`int a, b;` son dos).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    /// Tipo base con calificadores, p. ej. `const float` o `struct tNodo`.
+    pub ty: String,
+    pub pointers: usize,
+    /// Vacío en los parámetros sin nombre de un prototipo.
+    pub name: String,
+    pub dims: Vec<Expr>,
+    pub init: Option<Init>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub ret: String,
+    pub ret_pointers: usize,
+    pub name: String,
+    pub params: Vec<Declaration>,
+    /// `None` en los prototipos.
+    pub body: Option<Vec<Stmt>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stmt {
+    Decl(Declaration),
+    Expr(Expr),
+    Block(Vec<Stmt>),
+    If {
+        cond: Expr,
+        then: Box<Stmt>,
+        otherwise: Option<Box<Stmt>>,
+    },
+    While {
+        cond: Expr,
+        body: Box<Stmt>,
+    },
+    DoWhile {
+        body: Box<Stmt>,
+        cond: Expr,
+    },
+    For {
+        init: Vec<Stmt>,
+        cond: Expr,
+        step: Expr,
+        body: Box<Stmt>,
+    },
+    Switch {
+        value: Expr,
+        body: Box<Stmt>,
+    },
+    /// `case valor:` o, sin valor, `default:`.
+    Case(Option<Expr>),
+    Return(Option<Expr>),
+    Break,
+    Continue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Include(String),
+    Define {
+        name: String,
+        value: String,
+    },
+    /// Otras directivas, tal cual.
+    Directive(String),
+    Enum {
+        name: String,
+        values: Vec<String>,
+    },
+    Struct {
+        name: String,
+        fields: Vec<Declaration>,
+    },
+    /// `typedef int tId;`
+    Typedef(Declaration),
+    Function(Function),
+    Decl(Declaration),
+    /// Sentencias sueltas, fuera de cualquier función.
+    Stmt(Stmt),
+}
+
+/// Analiza `code` tras aplicar las reglas de equivalencia a sus tokens.
+pub fn parse(code: &str, rules: &[Equivalence]) -> Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+    let mut rest = String::new();
+    for line in code.lines() {
+        match line.trim_start().strip_prefix('#') {
+            Some(directive) => items.push(directive_item(directive)),
+            None => rest.push_str(line),
+        }
+        rest.push('\n');
+    }
+    let tokens = equivalence::apply(normalize_code(Language::C, &rest), rules);
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        types: HashSet::new(),
+    };
+    while !parser.at_end() {
+        parser.item(&mut items)?;
+    }
+    Ok(items)
+}
+
+fn directive_item(directive: &str) -> Item {
+    let tokens = normalize_code(Language::C, directive);
+    let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+    match texts.as_slice() {
+        ["include", header @ ..] if !header.is_empty() => {
+            Item::Include(header.concat().trim_matches(['<', '>', '"']).to_string())
+        }
+        ["define", name, ..] => Item::Define {
+            name: name.to_string(),
+            value: show(&tokens[2..]),
+        },
+        _ => Item::Directive(format!("#{}", show(&tokens))),
+    }
+}
+
+/// Tokens como texto legible, p. ej. `a[i] + f(x, &y)`.
+pub fn show(tokens: &[Token]) -> String {
+    let mut out = String::new();
+    let mut previous: Option<&Token> = None;
+    for token in tokens {
+        let text = token.text.as_str();
+        let punct = token.kind == TokenKind::Punct;
+        let glued = match previous {
+            None => true,
+            Some(prev) if prev.kind == TokenKind::Punct => {
+                matches!(prev.text.as_str(), "(" | "[" | "." | "->" | "!" | "~")
+                    || matches!(prev.text.as_str(), "&" | "*" | "-" | "++" | "--")
+                        && unary(out.trim_end(), prev)
+                    || punct && matches!(text, "(" | ")" | "[" | "]" | "," | ";" | ".")
+            }
+            Some(_) => {
+                punct
+                    && matches!(
+                        text,
+                        "(" | ")" | "[" | "]" | "," | ";" | "." | "->" | "++" | "--"
+                    )
+            }
+        };
+        if !glued {
+            out.push(' ');
+        }
+        out.push_str(text);
+        previous = Some(token);
+    }
+    out
+}
+
+/// ¿`op` (ya escrito al final de `written`) es un operador unario?
+fn unary(written: &str, op: &Token) -> bool {
+    let before = written[..written.len() - op.text.len()].trim_end();
+    before.is_empty()
+        || before.ends_with([
+            '(', '[', ',', '=', '!', '&', '|', '+', '-', '*', '/', '<', '>', '?', ':',
+        ])
+}
+
+const TYPE_WORDS: &[&str] = &[
+    "const", "static", "volatile", "extern", "unsigned", "signed", "short", "long", "int", "char",
+    "float", "double", "void", "bool", "_Bool", "size_t", "FILE", "int8_t", "int16_t", "int32_t",
+    "int64_t", "uint8_t", "uint16_t", "uint32_t", "uint64_t",
+];
+
+const KEYWORDS: &[&str] = &[
+    "if", "else", "for", "while", "do", "return", "break", "continue", "switch", "case", "default",
+    "sizeof", "goto", "typedef", "struct", "enum", "union",
+];
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    /// Nombres definidos con `typedef` hasta ahora.
+    types: HashSet<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'a Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn peek_is(&self, offset: usize, text: &str) -> bool {
+        self.peek_at(offset)
+            .is_some_and(|t| t.text == text && t.kind != TokenKind::Literal)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.peek_is(0, text) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), String> {
+        if self.eat(text) {
+            Ok(())
+        } else {
+            Err(match self.peek_at(0) {
+                Some(found) => format!("se esperaba `{text}` antes de `{}`", found.text),
+                None => format!("se esperaba `{text}` al final"),
+            })
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        match self.peek_at(0) {
+            Some(t) if t.kind == TokenKind::Ident => {
+                self.pos += 1;
+                Ok(t.text.clone())
+            }
+            Some(t) => Err(format!("se esperaba un nombre antes de `{}`", t.text)),
+            None => Err("se esperaba un nombre al final".into()),
+        }
+    }
+
+    /// Tokens hasta uno de `stops` fuera de paréntesis, corchetes y llaves (sin consumirlo).
+    fn until(&mut self, stops: &[&str]) -> Expr {
+        let start = self.pos;
+        let mut depth = 0usize;
+        while let Some(token) = self.peek_at(0) {
+            if token.kind == TokenKind::Punct {
+                match token.text.as_str() {
+                    "(" | "[" | "{" => depth += 1,
+                    ")" | "]" | "}" if depth > 0 => depth -= 1,
+                    text if depth == 0 && stops.contains(&text) => break,
+                    ")" | "]" | "}" => break,
+                    _ => {}
+                }
+            }
+            self.pos += 1;
+        }
+        self.tokens[start..self.pos].to_vec()
+    }
+
+    fn item(&mut self, items: &mut Vec<Item>) -> Result<(), String> {
+        if self.eat(";") {
+            return Ok(());
+        }
+        if self.eat("typedef") {
+            return self.typedef(items);
+        }
+        if (self.peek_is(0, "struct") || self.peek_is(0, "enum"))
+            && (self.peek_is(1, "{") || self.peek_is(2, "{"))
+        {
+            let keyword = self.ident()?;
+            let tag = if self.peek_is(0, "{") {
+                String::new()
+            } else {
+                format!("{keyword} {}", self.ident()?)
+            };
+            let item = self.aggregate(&keyword, tag)?;
+            self.expect(";")?;
+            items.push(item);
+            return Ok(());
+        }
+        if self.starts_declaration() {
+            let ty = self.base_type();
+            let pointers = self.pointers();
+            let name = self.ident()?;
+            if self.peek_is(0, "(") {
+                items.push(Item::Function(self.function(ty, pointers, name)?));
+                return Ok(());
+            }
+            for declaration in self.declarators(ty, pointers, name)? {
+                items.push(Item::Decl(declaration));
+            }
+            return Ok(());
+        }
+        items.push(Item::Stmt(self.statement()?));
+        Ok(())
+    }
+
+    fn typedef(&mut self, items: &mut Vec<Item>) -> Result<(), String> {
+        if (self.peek_is(0, "struct") || self.peek_is(0, "enum"))
+            && (self.peek_is(1, "{") || self.peek_is(2, "{"))
+        {
+            let keyword = self.ident()?;
+            if !self.peek_is(0, "{") {
+                self.ident()?;
+            }
+            let item = self.ident_after_block(&keyword)?;
+            if let Item::Enum { name, .. } | Item::Struct { name, .. } = &item {
+                self.types.insert(name.clone());
+            }
+            items.push(item);
+            return self.expect(";");
+        }
+        let ty = self.base_type();
+        let pointers = self.pointers();
+        let name = self.ident()?;
+        let dims = self.dims()?;
+        self.expect(";")?;
+        self.types.insert(name.clone());
+        items.push(Item::Typedef(Declaration {
+            ty,
+            pointers,
+            name,
+            dims,
+            init: None,
+        }));
+        Ok(())
+    }
+
+    /// Cuerpo de un `typedef struct {...} nombre` o `typedef enum {...} nombre`.
+    fn ident_after_block(&mut self, keyword: &str) -> Result<Item, String> {
+        let mut item = self.aggregate(keyword, String::new())?;
+        let name = self.ident()?;
+        match &mut item {
+            Item::Enum { name: n, .. } | Item::Struct { name: n, .. } => *n = name,
+            _ => {}
+        }
+        Ok(item)
+    }
+
+    fn aggregate(&mut self, keyword: &str, name: String) -> Result<Item, String> {
+        self.expect("{")?;
+        if keyword == "enum" {
+            let mut values = Vec::new();
+            while !self.eat("}") {
+                let value = self.until(&[",", "}"]);
+                if value.is_empty() {
+                    return Err("enumerado sin cerrar".into());
+                }
+                values.push(show(&value));
+                self.eat(",");
+            }
+            return Ok(Item::Enum { name, values });
+        }
+        let mut fields = Vec::new();
+        while !self.eat("}") {
+            if self.at_end() {
+                return Err("falta `}` al final del struct".into());
+            }
+            let ty = self.base_type();
+            if ty.is_empty() {
+                return Err("se esperaba el tipo de un campo".into());
+            }
+            let pointers = self.pointers();
+            let name = self.ident()?;
+            fields.extend(self.declarators(ty, pointers, name)?);
+        }
+        Ok(Item::Struct { name, fields })
+    }
+
+    /// ¿Empieza aquí una declaración (`tipo [*...] nombre` seguido de `= ; , [ ( )`)?
+    fn starts_declaration(&mut self) -> bool {
+        let start = self.pos;
+        let ty = self.base_type();
+        let _ = self.pointers();
+        let is_declaration = !ty.is_empty()
+            && self.peek_at(0).is_some_and(|t| t.kind == TokenKind::Ident)
+            && self
+                .peek_at(1)
+                .is_some_and(|t| ["=", ";", ",", "[", "(", ")"].contains(&t.text.as_str()));
+        self.pos = start;
+        is_declaration
+    }
+
+    /// Tipo base: palabras de tipo, `struct x`/`enum x` o un nombre de `typedef`.
+    /// Vacío si aquí no empieza un tipo.
+    fn base_type(&mut self) -> String {
+        let mut words: Vec<String> = Vec::new();
+        let mut named = false;
+        while let Some(token) = self.peek_at(0) {
+            if token.kind != TokenKind::Ident {
+                break;
+            }
+            let text = token.text.as_str();
+            if TYPE_WORDS.contains(&text) {
+                words.push(text.into());
+                self.pos += 1;
+            } else if matches!(text, "struct" | "enum" | "union")
+                && self.peek_at(1).is_some_and(|t| t.kind == TokenKind::Ident)
+            {
+                words.push(format!("{text} {}", self.tokens[self.pos + 1].text));
+                self.pos += 2;
+                named = true;
+            } else if !named
+                && !KEYWORDS.contains(&text)
+                && !words
+                    .iter()
+                    .any(|w| !matches!(w.as_str(), "const" | "static"))
+                && (self.types.contains(text) || self.typedef_name_shape())
+            {
+                words.push(text.into());
+                self.pos += 1;
+                named = true;
+            } else {
+                break;
+            }
+        }
+        words.join(" ")
+    }
+
+    /// Un nombre desconocido es un tipo si le sigue otro nombre (`tItem item`) o
+    /// punteros y un nombre (`tItem *p`, como en C).
+    fn typedef_name_shape(&self) -> bool {
+        let mut offset = 1;
+        while self.peek_is(offset, "*") {
+            offset += 1;
+        }
+        self.peek_at(offset)
+            .is_some_and(|t| t.kind == TokenKind::Ident && !KEYWORDS.contains(&t.text.as_str()))
+            && (offset == 1
+                || self
+                    .peek_at(offset + 1)
+                    .is_some_and(|t| ["=", ";", ",", "[", ")"].contains(&t.text.as_str())))
+    }
+
+    fn pointers(&mut self) -> usize {
+        let mut pointers = 0;
+        while self.eat("*") {
+            pointers += 1;
+        }
+        pointers
+    }
+
+    fn dims(&mut self) -> Result<Vec<Expr>, String> {
+        let mut dims = Vec::new();
+        while self.eat("[") {
+            dims.push(self.until(&["]"]));
+            self.expect("]")?;
+        }
+        Ok(dims)
+    }
+
+    fn init(&mut self) -> Result<Init, String> {
+        if !self.eat("{") {
+            return Ok(Init::Expr(self.until(&[",", ";", "}"])));
+        }
+        let mut list = Vec::new();
+        while !self.eat("}") {
+            if self.at_end() {
+                return Err("falta `}` al final de la inicialización".into());
+            }
+            list.push(self.init()?);
+            if !self.eat(",") && !self.peek_is(0, "}") {
+                return Err("se esperaba `,` o `}` en la inicialización".into());
+            }
+        }
+        Ok(Init::List(list))
+    }
+
+    /// Resto de una declaración cuyo primer nombre ya se leyó; consume el `;`.
+    fn declarators(
+        &mut self,
+        ty: String,
+        mut pointers: usize,
+        mut name: String,
+    ) -> Result<Vec<Declaration>, String> {
+        let mut declarations = Vec::new();
+        loop {
+            let dims = self.dims()?;
+            let init = if self.eat("=") {
+                Some(self.init()?)
+            } else {
+                None
+            };
+            declarations.push(Declaration {
+                ty: ty.clone(),
+                pointers,
+                name,
+                dims,
+                init,
+            });
+            if !self.eat(",") {
+                self.expect(";")?;
+                return Ok(declarations);
+            }
+            pointers = self.pointers();
+            name = self.ident()?;
+        }
+    }
+
+    fn function(
+        &mut self,
+        ret: String,
+        ret_pointers: usize,
+        name: String,
+    ) -> Result<Function, String> {
+        self.expect("(")?;
+        let mut params = Vec::new();
+        if self.peek_is(0, "void") && self.peek_is(1, ")") {
+            self.pos += 1;
+        }
+        while !self.eat(")") {
+            let ty = self.base_type();
+            if ty.is_empty() {
+                return Err(format!("se esperaba el tipo de un parámetro de `{name}`"));
+            }
+            let pointers = self.pointers();
+            let param = match self.peek_at(0) {
+                Some(t) if t.kind == TokenKind::Ident => self.ident()?,
+                _ => String::new(),
+            };
+            params.push(Declaration {
+                ty,
+                pointers,
+                name: param,
+                dims: self.dims()?,
+                init: None,
+            });
+            if !self.eat(",") && !self.peek_is(0, ")") {
+                return Err(format!(
+                    "se esperaba `,` o `)` en los parámetros de `{name}`"
+                ));
+            }
+        }
+        let body = if self.eat(";") {
+            None
+        } else {
+            self.expect("{")?;
+            Some(self.block_body()?)
+        };
+        Ok(Function {
+            ret,
+            ret_pointers,
+            name,
+            params,
+            body,
+        })
+    }
+
+    /// Sentencias hasta la `}` que cierra el bloque (ya abierto).
+    fn block_body(&mut self) -> Result<Vec<Stmt>, String> {
+        let mut body = Vec::new();
+        while !self.eat("}") {
+            if self.at_end() {
+                return Err("falta `}` al final de un bloque".into());
+            }
+            if self.eat(";") {
+                continue;
+            }
+            body.extend(self.statements()?);
+        }
+        Ok(body)
+    }
+
+    /// Una sentencia, o varias si es una declaración con varios nombres.
+    fn statements(&mut self) -> Result<Vec<Stmt>, String> {
+        if self.starts_declaration() {
+            let ty = self.base_type();
+            let pointers = self.pointers();
+            let name = self.ident()?;
+            Ok(self
+                .declarators(ty, pointers, name)?
+                .into_iter()
+                .map(Stmt::Decl)
+                .collect())
+        } else {
+            Ok(vec![self.statement()?])
+        }
+    }
+
+    fn condition(&mut self) -> Result<Expr, String> {
+        self.expect("(")?;
+        let cond = self.until(&[")"]);
+        self.expect(")")?;
+        Ok(cond)
+    }
+
+    fn body(&mut self) -> Result<Box<Stmt>, String> {
+        let mut statements = self.statements()?;
+        Ok(Box::new(if statements.len() == 1 {
+            statements.remove(0)
+        } else {
+            Stmt::Block(statements)
+        }))
+    }
+
+    fn statement(&mut self) -> Result<Stmt, String> {
+        if self.eat("{") {
+            return Ok(Stmt::Block(self.block_body()?));
+        }
+        if self.eat("if") {
+            let cond = self.condition()?;
+            let then = self.body()?;
+            let otherwise = if self.eat("else") {
+                Some(self.body()?)
+            } else {
+                None
+            };
+            return Ok(Stmt::If {
+                cond,
+                then,
+                otherwise,
+            });
+        }
+        if self.eat("while") {
+            let cond = self.condition()?;
+            return Ok(Stmt::While {
+                cond,
+                body: self.body()?,
+            });
+        }
+        if self.eat("do") {
+            let body = self.body()?;
+            self.expect("while")?;
+            let cond = self.condition()?;
+            self.expect(";")?;
+            return Ok(Stmt::DoWhile { body, cond });
+        }
+        if self.eat("for") {
+            self.expect("(")?;
+            let init = if self.eat(";") {
+                Vec::new()
+            } else if self.starts_declaration() {
+                self.statements()?
+            } else {
+                let init = self.until(&[";"]);
+                self.expect(";")?;
+                vec![Stmt::Expr(init)]
+            };
+            let cond = self.until(&[";"]);
+            self.expect(";")?;
+            let step = self.until(&[")"]);
+            self.expect(")")?;
+            return Ok(Stmt::For {
+                init,
+                cond,
+                step,
+                body: self.body()?,
+            });
+        }
+        if self.eat("switch") {
+            let value = self.condition()?;
+            return Ok(Stmt::Switch {
+                value,
+                body: self.body()?,
+            });
+        }
+        if self.eat("case") {
+            let value = self.until(&[":"]);
+            self.expect(":")?;
+            return Ok(Stmt::Case(Some(value)));
+        }
+        if self.peek_is(0, "default") && self.peek_is(1, ":") {
+            self.pos += 2;
+            return Ok(Stmt::Case(None));
+        }
+        let stmt = if self.eat("return") {
+            let value = self.until(&[";"]);
+            Stmt::Return((!value.is_empty()).then_some(value))
+        } else if self.eat("break") {
+            Stmt::Break
+        } else if self.eat("continue") {
+            Stmt::Continue
+        } else {
+            let expr = self.until(&[";"]);
+            if expr.is_empty() {
+                return Err(match self.peek_at(0) {
+                    Some(t) => format!("no se esperaba `{}`", t.text),
+                    None => "falta una sentencia al final".into(),
+                });
+            }
+            Stmt::Expr(expr)
+        };
+        self.expect(";")?;
+        Ok(stmt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_course_subset() {
+        let items = parse(
+            r#"
+#include <stdio.h>
+#define MAX 5
+
+typedef enum { PARADO, EN_VUELO = 2 } tEstado;
+typedef struct {
+    char nombre[MAX], inicial;
+    tEstado estado;
+} tDron;
+
+int contar(tDron *drones, int n);
+
+int main(void) {
+    tDron flota[MAX] = {{"a", 'a', PARADO}};
+    int i, total = 0;
+    for (i = 0; i < MAX; i++)
+        if (flota[i].estado == EN_VUELO) total++;
+    return 0;
+}
+"#,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(items[0], Item::Include("stdio.h".into()));
+        assert_eq!(
+            items[1],
+            Item::Define {
+                name: "MAX".into(),
+                value: "5".into()
+            }
+        );
+        assert!(
+            matches!(&items[2], Item::Enum { name, values } if name == "tEstado" && values[1] == "EN_VUELO = 2")
+        );
+        assert!(
+            matches!(&items[3], Item::Struct { name, fields } if name == "tDron" && fields.len() == 3)
+        );
+        assert!(
+            matches!(&items[4], Item::Function(f) if f.body.is_none() && f.params[0].pointers == 1)
+        );
+        let Item::Function(main) = &items[5] else {
+            panic!("{:?}", items[5]);
+        };
+        let body = main.body.as_ref().unwrap();
+        assert_eq!(body.len(), 5);
+        assert!(
+            matches!(&body[0], Stmt::Decl(d) if d.ty == "tDron" && matches!(d.init, Some(Init::List(_))))
+        );
+        assert!(matches!(&body[3], Stmt::For { body, .. } if matches!(**body, Stmt::If { .. })));
+
+        assert!(parse("int main() { if (x) }", &[]).is_err());
+    }
+}
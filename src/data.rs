@@ -1,15 +1,545 @@
-use crate::model::{Language, Quiz};
+use crate::bank_layout::bank_file_name;
+use crate::model::{Language, Question, Quiz};
+use serde_yaml::Value;
+use std::fmt;
 
-pub fn read_questions_for_language(language: Language) -> Quiz {
-    let file_content = match language {
-        Language::C | Language::Pseudocode | Language::GitGithub => {
-            include_str!("data/quiz_questions_modes.yaml")
+/// Error al cargar un banco de preguntas (externo o integrado).
+#[derive(Debug, Clone, Default)]
+pub struct BankLoadError {
+    /// Ruta, URL o nombre del banco integrado.
+    pub source: String,
+    pub message: String,
+    /// Posición (1-based) del error dentro del YAML, si se conoce.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// `id` de la pregunta que no se pudo leer.
+    pub question_id: Option<String>,
+}
+
+impl BankLoadError {
+    fn new(source: &str, message: impl Into<String>) -> Self {
+        Self {
+            source: source.to_string(),
+            message: message.into(),
+            ..Self::default()
         }
-        Language::Kotlin => include_str!("data/quiz_questions_kotlin.yaml"),
-        Language::Java => include_str!("data/quiz_questions_java.yaml"),
-        Language::Rust => include_str!("data/quiz_questions_rust.yaml"),
-        Language::Python => include_str!("data/quiz_questions_python.yaml"),
+    }
+
+    /// `fichero:línea:columna`, con lo que se sepa de la posición.
+    pub fn location(&self) -> String {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{}:{line}:{column}", self.source),
+            (Some(line), None) => format!("{}:{line}", self.source),
+            _ => self.source.clone(),
+        }
+    }
+}
+
+impl fmt::Display for BankLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.location())?;
+        if let Some(id) = &self.question_id {
+            write!(f, " (pregunta `{id}`)")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Directorio/fichero del banco de `language` (ver [`crate::bank_layout`]).
+pub fn bank_key(language: Language) -> &'static str {
+    match language {
+        Language::C => "c",
+        Language::Pseudocode => "pseudocode",
+        Language::Kotlin => "kotlin",
+        Language::Java => "java",
+        Language::Rust => "rust",
+        Language::Python => "python",
+        Language::GitGithub => "git_github",
+    }
+}
+
+/// Bancos unidos por `build.rs` a partir de `src/data`.
+fn embedded_bank(language: Language) -> &'static str {
+    macro_rules! bank {
+        ($file:literal) => {
+            include_str!(concat!(env!("OUT_DIR"), "/", $file))
+        };
+    }
+    match language {
+        Language::C => bank!("quiz_questions_c.yaml"),
+        Language::Pseudocode => bank!("quiz_questions_pseudocode.yaml"),
+        Language::Kotlin => bank!("quiz_questions_kotlin.yaml"),
+        Language::Java => bank!("quiz_questions_java.yaml"),
+        Language::Rust => bank!("quiz_questions_rust.yaml"),
+        Language::Python => bank!("quiz_questions_python.yaml"),
+        Language::GitGithub => bank!("quiz_questions_git_github.yaml"),
+    }
+}
+
+fn yaml_error(source: &str, err: &serde_yaml::Error) -> BankLoadError {
+    let location = err.location();
+    BankLoadError {
+        line: location.as_ref().map(|l| l.line()),
+        column: location.as_ref().map(|l| l.column()),
+        ..BankLoadError::new(source, err.to_string())
+    }
+}
+
+/// Parsea un banco completo. Si falla, el error indica línea, columna y, cuando
+/// el fallo está dentro de una pregunta, su `id`.
+pub fn parse_bank(source: &str, content: &str) -> Result<Quiz, BankLoadError> {
+    let err = match serde_yaml::from_str(content) {
+        Ok(quiz) => return Ok(quiz),
+        Err(err) => yaml_error(source, &err),
+    };
+
+    // YAML bien formado pero con alguna pregunta inválida: localizar cuál.
+    let question_id = serde_yaml::from_str::<Value>(content)
+        .ok()
+        .and_then(|mut root| {
+            let broken = remove_broken_questions(source, content, &mut root);
+            broken
+                .iter()
+                .filter(|b| b.line.is_some() && b.line <= err.line)
+                .max_by_key(|b| b.line)
+                .or(broken.first())
+                .and_then(|b| b.question_id.clone())
+        });
+    Err(BankLoadError { question_id, ..err })
+}
+
+/// Como [`parse_bank`], pero descarta las preguntas que no se pueden leer y
+/// devuelve el resto junto con un error por cada pregunta descartada.
+/// Solo falla si el YAML está mal formado o el error está fuera de las preguntas.
+pub fn parse_bank_skipping_broken(
+    source: &str,
+    content: &str,
+) -> Result<(Quiz, Vec<BankLoadError>), BankLoadError> {
+    let err = match parse_bank(source, content) {
+        Ok(quiz) => return Ok((quiz, Vec::new())),
+        Err(err) => err,
+    };
+    let mut root: Value = serde_yaml::from_str(content).map_err(|_| err.clone())?;
+    let broken = remove_broken_questions(source, content, &mut root);
+    let quiz = serde_yaml::from_value(root).map_err(|_| err)?;
+    Ok((quiz, broken))
+}
+
+/// Quita de `root` las preguntas que no deserializan y devuelve un error por cada una.
+fn remove_broken_questions(source: &str, content: &str, root: &mut Value) -> Vec<BankLoadError> {
+    let mut broken = Vec::new();
+    let modules = match root.get("modules") {
+        Some(_) => root.get_mut("modules"),
+        None => root.get_mut("weeks"),
+    };
+    let Some(modules) = modules.and_then(Value::as_sequence_mut) else {
+        return broken;
     };
 
-    serde_yaml::from_str(file_content).expect("No se pudo parsear el banco de preguntas YAML")
+    for module in modules {
+        let Some(levels) = module.get_mut("levels").and_then(Value::as_sequence_mut) else {
+            continue;
+        };
+        for level in levels {
+            let Some(questions) = level.get_mut("questions").and_then(Value::as_sequence_mut)
+            else {
+                continue;
+            };
+            questions.retain(|question| {
+                let Err(err) = serde_yaml::from_value::<Question>(question.clone()) else {
+                    return true;
+                };
+                let question_id = question
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                broken.push(BankLoadError {
+                    line: question_id.as_deref().and_then(|id| id_line(content, id)),
+                    question_id,
+                    ..BankLoadError::new(source, err.to_string())
+                });
+                false
+            });
+        }
+    }
+    broken
+}
+
+/// Línea (1-based) donde se declara `id: <id>`.
+fn id_line(content: &str, id: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| {
+            let line = line.trim_start().trim_start_matches("- ").trim_start();
+            line.strip_prefix("id:")
+                .map(|value| value.trim().trim_matches(['"', '\'']) == id)
+                .unwrap_or(false)
+        })
+        .map(|index| index + 1)
+}
+
+/// Banco compilado dentro del binario. Los tests garantizan que parsea entero;
+/// aun así, una pregunta rota solo se descarta en lugar de tumbar la app.
+pub fn read_embedded_questions(language: Language) -> Quiz {
+    let source = bank_file_name(bank_key(language));
+    match parse_bank_skipping_broken(&source, embedded_bank(language)) {
+        Ok((quiz, broken)) => {
+            for err in broken {
+                log::error!("Pregunta integrada descartada: {err}");
+            }
+            quiz
+        }
+        Err(err) => {
+            log::error!("Banco integrado inválido: {err}");
+            Quiz::default()
+        }
+    }
+}
+
+/// Carga el banco de `language` desde el directorio/URL configurado si allí existe,
+/// y si no, el integrado en el binario.
+pub fn load_questions_for_language(language: Language) -> Result<Quiz, BankLoadError> {
+    match external_bank(language)? {
+        Some((source, content)) => parse_bank(&source, &content),
+        None => Ok(read_embedded_questions(language)),
+    }
+}
+
+/// Como [`load_questions_for_language`], pero omitiendo las preguntas rotas.
+pub fn load_questions_skipping_broken(
+    language: Language,
+) -> Result<(Quiz, Vec<BankLoadError>), BankLoadError> {
+    match external_bank(language)? {
+        Some((source, content)) => parse_bank_skipping_broken(&source, &content),
+        None => Ok((read_embedded_questions(language), Vec::new())),
+    }
+}
+
+/// Igual que [`load_questions_for_language`], pero ante cualquier error usa el banco integrado.
+pub fn read_questions_for_language(language: Language) -> Quiz {
+    load_questions_for_language(language).unwrap_or_else(|_| read_embedded_questions(language))
+}
+
+/// Directorio con bancos propios (escritorio): `SUMMER_QUIZ_BANKS_DIR` o
+/// la carpeta `banks/` junto al ejecutable.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn external_banks_dir() -> Option<std::path::PathBuf> {
+    if let Some(dir) = std::env::var_os("SUMMER_QUIZ_BANKS_DIR").filter(|d| !d.is_empty()) {
+        return Some(dir.into());
+    }
+    let exe = std::env::current_exe().ok()?;
+    let dir = exe.parent()?.join("banks");
+    dir.is_dir().then_some(dir)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn external_bank(language: Language) -> Result<Option<(String, String)>, BankLoadError> {
+    match external_banks_dir() {
+        Some(dir) => bank_in_dir(&dir, language),
+        None => Ok(None),
+    }
+}
+
+/// `(origen, yaml)` del banco de `language` en `dir`, si lo tiene.
+#[cfg(not(target_arch = "wasm32"))]
+fn bank_in_dir(
+    dir: &std::path::Path,
+    language: Language,
+) -> Result<Option<(String, String)>, BankLoadError> {
+    crate::bank_layout::read_bank(dir, bank_key(language))
+        .map_err(|message| BankLoadError::new(&dir.display().to_string(), message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+include!(concat!(env!("OUT_DIR"), "/bank_images.rs"));
+
+/// Imagen citada por un enunciado o una teoría, con la ruta relativa a la carpeta
+/// de los bancos (`images/puntero.png`). En escritorio la carpeta de bancos propia
+/// tiene prioridad sobre las imágenes integradas de `src/data/images`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn bank_image(path: &str) -> Option<Vec<u8>> {
+    if path.split('/').any(|part| part == "..") {
+        return None;
+    }
+    if let Some(dir) = external_banks_dir()
+        && let Ok(bytes) = std::fs::read(dir.join(path))
+    {
+        return Some(bytes);
+    }
+    EMBEDDED_IMAGES
+        .iter()
+        .find(|(name, _)| *name == path)
+        .map(|(_, bytes)| bytes.to_vec())
+}
+
+#[cfg(target_arch = "wasm32")]
+mod remote {
+    use super::{BankLoadError, bank_key};
+    use crate::bank_layout::LEGACY_MODES_FILE;
+    use crate::bank_layout::bank_file_name;
+    use crate::model::Language;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::Response;
+
+    thread_local! {
+        /// fichero -> contenido descargado (o error). Ausente = usar el integrado.
+        static REMOTE_BANKS: RefCell<HashMap<String, Result<(String, String), BankLoadError>>> =
+            RefCell::new(HashMap::new());
+    }
+
+    /// URL base de los bancos (web): `?banks_url=`, `<meta name="summer-quiz-banks-url">`
+    /// o `localStorage["summer_quiz_banks_url"]`.
+    pub fn banks_url() -> Option<String> {
+        use crate::judge::judge_remote::{
+            value_from_local_storage, value_from_meta, value_from_querystring,
+        };
+        value_from_querystring("banks_url")
+            .or_else(|| value_from_meta("summer-quiz-banks-url"))
+            .or_else(|| value_from_local_storage("summer_quiz_banks_url"))
+    }
+
+    /// Descarga todos los bancos publicados en [`banks_url`]. Un 404 no es error:
+    /// ese lenguaje seguirá usando el banco integrado. Para C y pseudocódigo se
+    /// acepta también el antiguo banco común `quiz_questions_modes.yaml`.
+    pub async fn prefetch_remote_banks() {
+        let Some(base) = banks_url() else {
+            return;
+        };
+        let base = base.trim_end_matches('/').to_string();
+
+        for language in [
+            Language::C,
+            Language::Pseudocode,
+            Language::Kotlin,
+            Language::Java,
+            Language::Rust,
+            Language::Python,
+        ] {
+            let file = bank_file_name(bank_key(language));
+            let mut candidates = vec![file.as_str()];
+            if matches!(language, Language::C | Language::Pseudocode) {
+                candidates.push(LEGACY_MODES_FILE);
+            }
+
+            for candidate in candidates {
+                let url = format!("{base}/{candidate}");
+                let result = match fetch_text(&url).await {
+                    Ok(Some(content)) => Ok((url, content)),
+                    Ok(None) => continue,
+                    Err(message) => Err(BankLoadError::new(&url, message)),
+                };
+                REMOTE_BANKS.with(|banks| banks.borrow_mut().insert(file.clone(), result));
+                break;
+            }
+        }
+    }
+
+    pub fn external_bank(language: Language) -> Result<Option<(String, String)>, BankLoadError> {
+        REMOTE_BANKS.with(|banks| {
+            banks
+                .borrow()
+                .get(&bank_file_name(bank_key(language)))
+                .cloned()
+                .transpose()
+        })
+    }
+
+    /// Descarga una imagen de los bancos: de [`banks_url`] si está configurada y,
+    /// si no, relativa a la página (trunk copia `src/data/images` junto a la app).
+    pub async fn fetch_bank_image(path: &str) -> Result<Option<Vec<u8>>, String> {
+        if path.split('/').any(|part| part == "..") {
+            return Ok(None);
+        }
+        let url = match banks_url() {
+            Some(base) => format!("{}/{path}", base.trim_end_matches('/')),
+            None => path.to_string(),
+        };
+        let Some(response) = fetch(&url).await? else {
+            return Ok(None);
+        };
+        let buffer = JsFuture::from(
+            response
+                .array_buffer()
+                .map_err(|e| format!("No se pudo obtener body: {e:?}"))?,
+        )
+        .await
+        .map_err(|e| format!("No se pudo leer body: {e:?}"))?;
+        Ok(Some(js_sys::Uint8Array::new(&buffer).to_vec()))
+    }
+
+    async fn fetch_text(url: &str) -> Result<Option<String>, String> {
+        let Some(response) = fetch(url).await? else {
+            return Ok(None);
+        };
+        let text = JsFuture::from(
+            response
+                .text()
+                .map_err(|e| format!("No se pudo obtener body: {e:?}"))?,
+        )
+        .await
+        .map_err(|e| format!("No se pudo leer body: {e:?}"))?;
+        Ok(text.as_string())
+    }
+
+    /// GET `url`; `None` si es un 404.
+    async fn fetch(url: &str) -> Result<Option<Response>, String> {
+        use wasm_bindgen::JsCast;
+        use web_sys::{Request, RequestInit, RequestMode};
+
+        let window = web_sys::window().ok_or("No existe window en entorno WASM.")?;
+        let opts = RequestInit::new();
+        opts.set_method("GET");
+        opts.set_mode(RequestMode::Cors);
+        let request = Request::new_with_str_and_init(url, &opts)
+            .map_err(|e| format!("No se pudo crear request: {e:?}"))?;
+
+        let response: Response = JsFuture::from(window.fetch_with_request(&request))
+            .await
+            .map_err(|e| format!("NetworkError: {e:?}"))?
+            .dyn_into()
+            .map_err(|_| "Respuesta no es un Response válido.".to_string())?;
+
+        if response.status() == 404 {
+            return Ok(None);
+        }
+        if !response.ok() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        Ok(Some(response))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use remote::{banks_url, fetch_bank_image, prefetch_remote_banks};
+
+#[cfg(target_arch = "wasm32")]
+fn external_bank(language: Language) -> Result<Option<(String, String)>, BankLoadError> {
+    remote::external_bank(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_banks_parse() {
+        for language in [
+            Language::C,
+            Language::Pseudocode,
+            Language::Kotlin,
+            Language::Java,
+            Language::Rust,
+            Language::Python,
+            Language::GitGithub,
+        ] {
+            let quiz = parse_bank(&bank_file_name(bank_key(language)), embedded_bank(language))
+                .unwrap_or_else(|e| panic!("{e}"));
+            let questions = quiz
+                .modules
+                .iter()
+                .flat_map(|m| &m.levels)
+                .flat_map(|l| &l.questions);
+            for q in questions {
+                assert_eq!(q.language, language, "{}", q.id);
+            }
+        }
+    }
+
+    const BROKEN_BANK: &str = "\
+modules:
+- number: 1
+  explanation: M1
+  levels:
+  - number: 1
+    explanation: {}
+    questions:
+    - id: ok-1
+      language: C
+      module: 1
+      prompt: p
+      answer: a
+    - id: roto-2
+      language: C
+      module: uno
+      prompt: p
+      answer: a
+";
+
+    #[test]
+    fn reports_location_and_question_id() {
+        let err = parse_bank("banco.yaml", "modules: [\n").unwrap_err();
+        assert!(err.line.is_some() && err.column.is_some(), "{err}");
+        assert_eq!(err.question_id, None);
+
+        let err = parse_bank("banco.yaml", BROKEN_BANK).unwrap_err();
+        assert_eq!(err.question_id.as_deref(), Some("roto-2"));
+        assert_eq!(err.line, Some(15));
+        assert!(err.to_string().starts_with("banco.yaml:15:"), "{err}");
+    }
+
+    #[test]
+    fn skipping_broken_keeps_valid_questions() {
+        let (quiz, skipped) = parse_bank_skipping_broken("banco.yaml", BROKEN_BANK).unwrap();
+        let ids: Vec<_> = quiz.modules[0].levels[0]
+            .questions
+            .iter()
+            .map(|q| q.id.as_str())
+            .collect();
+        assert_eq!(ids, ["ok-1"]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].question_id.as_deref(), Some("roto-2"));
+        assert_eq!(skipped[0].line, Some(13));
+
+        // Un error fuera de las preguntas no se puede omitir.
+        let err = parse_bank_skipping_broken("banco.yaml", "modules: 3\n").unwrap_err();
+        assert_eq!(err.question_id, None);
+    }
+
+    #[test]
+    fn external_dir_overrides_and_reports_parse_errors() {
+        let dir = std::env::temp_dir().join(format!("summer_quiz_banks_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let load = || {
+            let (source, content) = bank_in_dir(&dir, Language::Rust)?.expect("banco en dir");
+            parse_bank(&source, &content)
+        };
+
+        // Sin fichero: se usará el banco integrado.
+        assert!(bank_in_dir(&dir, Language::Rust).unwrap().is_none());
+
+        std::fs::write(dir.join("quiz_questions_rust.yaml"), "modules: []\n").unwrap();
+        let quiz = load().unwrap();
+        assert!(quiz.modules.is_empty());
+
+        std::fs::write(dir.join("quiz_questions_rust.yaml"), "modules: [\n").unwrap();
+        let err = load().unwrap_err();
+        assert!(err.source.ends_with("quiz_questions_rust.yaml"));
+
+        // El directorio por lenguaje tiene prioridad sobre el fichero único.
+        let rust_dir = dir.join("rust");
+        std::fs::create_dir_all(&rust_dir).unwrap();
+        std::fs::write(
+            rust_dir.join("module_2.yaml"),
+            "explanation: M2\nlevels:\n- number: 1\n  questions:\n  - { id: r-2, language: Rust, module: 2, prompt: p, answer: a }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            rust_dir.join("module_1.yaml"),
+            "number: 1\nexplanation: M1\nlevels:\n- number: 1\n  questions: []\n",
+        )
+        .unwrap();
+        std::fs::write(rust_dir.join("module_2_level_1.md"), "## Teoría\n").unwrap();
+        let quiz = load().unwrap();
+        let numbers: Vec<_> = quiz.modules.iter().map(|m| m.number).collect();
+        assert_eq!(numbers, [1, 2]);
+        assert_eq!(
+            quiz.modules[1].levels[0].explanation[&Language::Rust],
+            "## Teoría\n"
+        );
+        assert!(quiz.modules[0].levels[0].explanation.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
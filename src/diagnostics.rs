@@ -0,0 +1,263 @@
+//! Diagnósticos de compilación (gcc/clang, rustc, javac, kotlinc, Python) con la
+//! línea que ocupan en el código del alumno, para marcarlos en el editor.
+//!
+//! Los jueces compilan el código envuelto (`wrap_as_main_body`, plantillas de
+//! `judge_harness`), así que antes de devolver la salida del compilador
+//! [`relocate`] reescribe sus ubicaciones; en el cliente [`parse`] la convierte en
+//! entradas estructuradas.
+
+use crate::model::Language;
+use regex::{Captures, Regex};
+
+/// Nombre de fichero con el que se cita la plantilla en los mensajes reubicados.
+const HARNESS_FILE: &str = "plantilla";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Línea del código del alumno (desde 1).
+    pub line: usize,
+    pub column: Option<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Nombre con el que aparece el código del alumno en los mensajes reubicados.
+pub fn user_file_name(language: Language) -> &'static str {
+    match language {
+        Language::C => "main.c",
+        Language::Rust => "main.rs",
+        Language::Java => "Main.java",
+        Language::Kotlin => "main.kt",
+        Language::Python => "main.py",
+        Language::Pseudocode | Language::GitGithub => "main",
+    }
+}
+
+/// Dónde empieza el código del alumno dentro del fuente que se compiló.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct UserSpan {
+    line_offset: usize,
+    /// Columnas que la plantilla añade delante de la primera línea del alumno.
+    column_offset: usize,
+    lines: usize,
+}
+
+impl UserSpan {
+    fn locate(source: &str, user_code: &str) -> Option<Self> {
+        if user_code.trim().is_empty() {
+            return None;
+        }
+        let start = source.find(user_code)?;
+        let prefix = &source[..start];
+        let last_line = prefix.rsplit('\n').next().unwrap_or_default();
+        Some(Self {
+            line_offset: prefix.matches('\n').count(),
+            column_offset: last_line.chars().count(),
+            lines: user_code.lines().count().max(1),
+        })
+    }
+
+    /// `(línea, columna)` en el código del alumno, o `None` si cae en la plantilla.
+    fn map(&self, line: usize, column: Option<usize>) -> Option<(usize, Option<usize>)> {
+        let user_line = line.checked_sub(self.line_offset)?;
+        if user_line == 0 || user_line > self.lines {
+            return None;
+        }
+        let column = match column {
+            Some(column) if user_line == 1 => {
+                Some(column.saturating_sub(self.column_offset).max(1))
+            }
+            other => other,
+        };
+        Some((user_line, column))
+    }
+}
+
+/// Reescribe en `text` las ubicaciones del fichero `compiled_file` (con o sin ruta)
+/// para que apunten al código del alumno: `main.c:3:9` si la línea es suya y
+/// `plantilla:12` si pertenece al código que la envuelve.
+pub fn relocate(
+    text: &str,
+    language: Language,
+    compiled_file: &str,
+    source: &str,
+    user_code: &str,
+) -> String {
+    let span = UserSpan::locate(source, user_code);
+    let user_file = user_file_name(language);
+    let path = format!(
+        r#"(?:file://)?(?:[A-Za-z]:)?[^\s"':]*{}"#,
+        regex::escape(compiled_file)
+    );
+    let location = |line: Option<&str>, column: Option<&str>| {
+        let Some(line) = line.and_then(|l| l.parse::<usize>().ok()) else {
+            return (user_file, None, None);
+        };
+        let column = column.and_then(|c| c.parse::<usize>().ok());
+        match span.and_then(|span| span.map(line, column)) {
+            Some((line, column)) => (user_file, Some(line), column),
+            None => (HARNESS_FILE, Some(line), column),
+        }
+    };
+
+    let python = Regex::new(&format!(r#"File "{path}", line (\d+)"#)).expect("regex válida");
+    let text = python.replace_all(text, |caps: &Captures| {
+        let (file, line, _) = location(caps.get(1).map(|m| m.as_str()), None);
+        format!("File \"{file}\", line {}", line.unwrap_or_default())
+    });
+
+    let generic = Regex::new(&format!(r"{path}(?::(\d+)(?::(\d+))?)?")).expect("regex válida");
+    generic
+        .replace_all(&text, |caps: &Captures| {
+            match location(
+                caps.get(1).map(|m| m.as_str()),
+                caps.get(2).map(|m| m.as_str()),
+            ) {
+                (file, Some(line), Some(column)) => format!("{file}:{line}:{column}"),
+                (file, Some(line), None) => format!("{file}:{line}"),
+                (file, None, _) => file.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+fn severity_of(word: &str) -> Severity {
+    match word {
+        "error" | "fatal error" | "e" => Severity::Error,
+        "note" | "help" => Severity::Note,
+        word if word.ends_with("Error") => Severity::Error,
+        _ => Severity::Warning,
+    }
+}
+
+/// Diagnósticos sobre el código del alumno que hay en una salida ya reubicada.
+pub fn parse(language: Language, text: &str) -> Vec<Diagnostic> {
+    let file = regex::escape(user_file_name(language));
+    // gcc/clang, javac, kotlinc (`e:`/`w:` en las versiones nuevas), pyflakes y
+    // los avisos de Python.
+    let located = Regex::new(&format!(
+        r"^(?:([ew]): )?{file}:(\d+)(?::(\d+))?:? (?:(fatal error|error|warning|note|\w+(?:Error|Warning)): )?(.*)$"
+    ))
+    .expect("regex válida");
+    let rust_header = Regex::new(r"^(error|warning)(?:\[\w+\])?: (.*)$").expect("regex válida");
+    let rust_arrow = Regex::new(&format!(r"^\s*--> {file}:(\d+):(\d+)")).expect("regex válida");
+    let python_frame =
+        Regex::new(&format!(r#"^\s*File "{file}", line (\d+)"#)).expect("regex válida");
+    let python_error = Regex::new(r"^(\w+(?:Error|Exception)): (.*)$").expect("regex válida");
+
+    let number = |caps: &Captures, i: usize| caps.get(i).and_then(|m| m.as_str().parse().ok());
+    let mut diagnostics = Vec::new();
+    let mut rust_pending: Option<(Severity, String)> = None;
+    let mut python_line = None;
+    for line in text.lines() {
+        if let Some(caps) = located.captures(line) {
+            let kind = caps
+                .get(4)
+                .or(caps.get(1))
+                .map_or("warning", |m| m.as_str());
+            let message = caps[5].trim();
+            let message = if kind.ends_with("Error") || kind.ends_with("Warning") {
+                format!("{kind}: {message}")
+            } else {
+                message.to_string()
+            };
+            if let Some(line) = number(&caps, 2) {
+                diagnostics.push(Diagnostic {
+                    line,
+                    column: number(&caps, 3),
+                    severity: severity_of(kind),
+                    message,
+                });
+            }
+        } else if let Some(caps) = rust_header.captures(line) {
+            rust_pending = Some((severity_of(&caps[1]), caps[2].trim().to_string()));
+        } else if let Some(caps) = rust_arrow.captures(line) {
+            if let (Some((severity, message)), Some(line)) = (rust_pending.take(), number(&caps, 1))
+            {
+                diagnostics.push(Diagnostic {
+                    line,
+                    column: number(&caps, 2),
+                    severity,
+                    message,
+                });
+            }
+        } else if let Some(caps) = python_frame.captures(line) {
+            python_line = number(&caps, 1);
+        } else if let Some(caps) = python_error.captures(line)
+            && let Some(line) = python_line.take()
+        {
+            diagnostics.push(Diagnostic {
+                line,
+                column: None,
+                severity: Severity::Error,
+                message: format!("{}: {}", &caps[1], caps[2].trim()),
+            });
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relocates_wrapped_c_and_parses_it() {
+        let user_code = "int x;\nprintf(\"%d\", y);";
+        let source = format!("#include <stdio.h>\nint main(void) {{\n    {user_code}\n}}\n");
+        let stderr = "/tmp/cache/ab12.c: In function 'main':\n\
+                      /tmp/cache/ab12.c:3:9: warning: unused variable 'x' [-Wunused-variable]\n\
+                      /tmp/cache/ab12.c:4:18: error: 'y' undeclared (first use in this function)\n\
+                      /tmp/cache/ab12.c:5:1: error: expected ';' before '}' token\n";
+        let relocated = relocate(stderr, Language::C, "ab12.c", &source, user_code);
+        assert_eq!(
+            relocated,
+            "main.c: In function 'main':\n\
+             main.c:1:5: warning: unused variable 'x' [-Wunused-variable]\n\
+             main.c:2:18: error: 'y' undeclared (first use in this function)\n\
+             plantilla:5:1: error: expected ';' before '}' token\n"
+        );
+        assert_eq!(
+            parse(Language::C, &relocated),
+            [
+                Diagnostic {
+                    line: 1,
+                    column: Some(5),
+                    severity: Severity::Warning,
+                    message: "unused variable 'x' [-Wunused-variable]".into(),
+                },
+                Diagnostic {
+                    line: 2,
+                    column: Some(18),
+                    severity: Severity::Error,
+                    message: "'y' undeclared (first use in this function)".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_rustc_and_python_diagnostics() {
+        let rustc = "warning: unused variable: `x`\n --> main.rs:1:16\n  |\n\
+                     error[E0425]: cannot find value `y` in this scope\n --> main.rs:2:5\n\
+                     error: aborting due to 1 previous error\n";
+        let lines: Vec<_> = parse(Language::Rust, rustc)
+            .into_iter()
+            .map(|d| (d.line, d.severity))
+            .collect();
+        assert_eq!(lines, [(1, Severity::Warning), (2, Severity::Error)]);
+
+        let python = "  File \"main.py\", line 2\n    if x\n        ^\nSyntaxError: expected ':'\n\
+                      main.py:1:1: 'os' imported but unused\n";
+        let parsed = parse(Language::Python, python);
+        assert_eq!(parsed[0].message, "SyntaxError: expected ':'");
+        assert_eq!((parsed[1].line, parsed[1].severity), (1, Severity::Warning));
+    }
+}
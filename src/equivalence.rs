@@ -0,0 +1,402 @@
+//! Reglas de equivalencia de las preguntas `normalize`: transforman la secuencia
+//! de tokens en una forma canónica antes de compararla con las respuestas de referencia.
+//!
+//! Son reglas sobre tokens, no sobre el árbol sintáctico, así que solo reescriben
+//! los casos sencillos e inequívocos; lo demás se cubre con varias `answers`.
+
+use crate::code_utils::normalize_code;
+use crate::judge::judge_c::JudgeResult;
+use crate::lexer::{Token, TokenKind};
+use crate::model::{Equivalence, Language, Question};
+use crate::structural::closest_differences;
+use crate::token_diff::{describe, distance};
+
+/// Diferencias que se enseñan como mucho al fallar.
+const MAX_DIFFERENCES: usize = 5;
+
+/// Forma canónica de `code` con las reglas dadas.
+pub fn canonical(language: Language, code: &str, rules: &[Equivalence]) -> Vec<Token> {
+    apply(normalize_code(language, code), rules)
+}
+
+/// Aplica las reglas a una secuencia de tokens ya normalizada.
+pub fn apply(mut tokens: Vec<Token>, rules: &[Equivalence]) -> Vec<Token> {
+    if rules.contains(&Equivalence::Increment) {
+        tokens = canonical_increments(tokens);
+    }
+    if rules.contains(&Equivalence::OptionalBraces) {
+        tokens = drop_optional_braces(tokens);
+    }
+    if rules.contains(&Equivalence::Commutative) {
+        sort_commutative_operands(&mut tokens);
+    }
+    tokens
+}
+
+/// Respuestas de referencia: `answer` y las alternativas de `answers`.
+pub fn reference_answers(question: &Question) -> impl Iterator<Item = &str> {
+    std::iter::once(question.answer.as_str())
+        .chain(question.answers.iter().map(String::as_str))
+        .filter(|answer| !answer.trim().is_empty())
+}
+
+/// ¿`code` equivale a alguna respuesta de referencia?
+pub fn matches_reference(question: &Question, code: &str) -> bool {
+    let rules = &question.equivalences;
+    let submitted = canonical(question.language, code, rules);
+    reference_answers(question)
+        .any(|answer| canonical(question.language, answer, rules) == submitted)
+}
+
+/// Corrige una pregunta `normalize`. Si no coincide token a token con ninguna
+/// referencia y es de C, se compara por estructura: puede coincidir igualmente y,
+/// si no, el `diff` del `WrongAnswer` dice qué es distinto. En los demás casos (o
+/// si el código no se puede analizar) el `diff` compara los tokens con la
+/// referencia más parecida. Lo esperado (tokens, valores, tipos) solo se enseña
+/// con `reveal`.
+pub fn grade_normalize(question: &Question, code: &str, reveal: bool) -> JudgeResult {
+    if matches_reference(question, code) {
+        return JudgeResult::Accepted {
+            warnings: String::new(),
+        };
+    }
+    let differences = match closest_differences(question, code, reveal) {
+        Some(differences) if differences.is_empty() => {
+            return JudgeResult::Accepted {
+                warnings: String::new(),
+            };
+        }
+        Some(differences) => differences,
+        None => closest_token_diff(question, code, reveal),
+    };
+    let mut diff: Vec<String> = differences
+        .iter()
+        .take(MAX_DIFFERENCES)
+        .map(|d| format!("• {d}"))
+        .collect();
+    if differences.len() > MAX_DIFFERENCES {
+        diff.push(format!("… y {} más.", differences.len() - MAX_DIFFERENCES));
+    }
+    JudgeResult::WrongAnswer {
+        test_index: 0,
+        input: String::new(),
+        expected: String::new(),
+        received: String::new(),
+        diff: diff.join("\n"),
+    }
+}
+
+/// Diferencias token a token con la referencia más parecida; los tokens que
+/// faltan solo se enseñan con `reveal`.
+fn closest_token_diff(question: &Question, code: &str, reveal: bool) -> Vec<String> {
+    let rules = &question.equivalences;
+    let submitted = canonical(question.language, code, rules);
+    reference_answers(question)
+        .map(|answer| canonical(question.language, answer, rules))
+        .min_by_key(|reference| distance(reference, &submitted))
+        .map(|reference| describe(&reference, &submitted, reveal))
+        .unwrap_or_default()
+}
+
+fn is(token: Option<&Token>, text: &str) -> bool {
+    token.is_some_and(|t| t.kind == TokenKind::Punct && t.text == text)
+}
+
+fn is_ident(token: Option<&Token>) -> bool {
+    token.is_some_and(|t| t.kind == TokenKind::Ident)
+}
+
+fn punct(text: &str) -> Token {
+    Token {
+        kind: TokenKind::Punct,
+        text: text.into(),
+    }
+}
+
+fn number(text: &str) -> Token {
+    Token {
+        kind: TokenKind::Number,
+        text: text.into(),
+    }
+}
+
+/// Donde puede empezar una sentencia (o la actualización de un `for`).
+fn statement_start(token: Option<&Token>) -> bool {
+    match token {
+        None => true,
+        Some(t) => match t.kind {
+            TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent => true,
+            TokenKind::Punct => matches!(t.text.as_str(), ";" | "{" | "}" | ")" | ","),
+            _ => false,
+        },
+    }
+}
+
+/// Donde puede terminar una sentencia.
+fn statement_end(token: Option<&Token>) -> bool {
+    match token {
+        None => true,
+        Some(t) => match t.kind {
+            TokenKind::Newline | TokenKind::Dedent => true,
+            TokenKind::Punct => matches!(t.text.as_str(), ";" | ")" | "}" | ","),
+            _ => false,
+        },
+    }
+}
+
+/// `i++`, `++i`, `i = i + 1` y `i := i + 1` como sentencia pasan a ser `i += 1` (y lo mismo con `-`).
+fn canonical_increments(tokens: Vec<Token>) -> Vec<Token> {
+    let mut out: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        let at = |k: usize| tokens.get(i + k);
+        let starts = statement_start(out.last());
+        let rewrite = if !starts {
+            None
+        } else if is_ident(at(0)) && (is(at(1), "++") || is(at(1), "--")) && statement_end(at(2)) {
+            Some((at(0).cloned(), &at(1).unwrap().text[..1], 2))
+        } else if (is(at(0), "++") || is(at(0), "--")) && is_ident(at(1)) && statement_end(at(2)) {
+            Some((at(1).cloned(), &at(0).unwrap().text[..1], 2))
+        } else if is_ident(at(0))
+            && (is(at(1), "=") || is(at(1), ":="))
+            && at(2).map(|t| &t.text) == at(0).map(|t| &t.text)
+            && (is(at(3), "+") || is(at(3), "-"))
+            && at(4).is_some_and(|t| t.kind == TokenKind::Number && t.text == "1")
+            && statement_end(at(5))
+        {
+            Some((at(0).cloned(), at(3).unwrap().text.as_str(), 5))
+        } else {
+            None
+        };
+
+        match rewrite {
+            Some((Some(target), sign, consumed)) => {
+                out.push(target);
+                out.push(punct(&format!("{sign}=")));
+                out.push(number("1"));
+                i += consumed;
+            }
+            _ => {
+                out.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Quita las llaves de los bloques de `if`/`else`/`for`/`while` con una sola sentencia terminada en `;`.
+fn drop_optional_braces(mut tokens: Vec<Token>) -> Vec<Token> {
+    let mut i = 0;
+    while i < tokens.len() {
+        if is(tokens.get(i), "{")
+            && introduces_block(&tokens, i)
+            && let Some(close) = matching(&tokens, i, "{", "}")
+        {
+            let body = &tokens[i + 1..close];
+            let single_statement = body.len() > 1
+                && is(body.last(), ";")
+                && body.iter().filter(|t| is(Some(t), ";")).count() == 1
+                && !body.iter().any(|t| is(Some(t), "{"));
+            if single_statement {
+                tokens.remove(close);
+                tokens.remove(i);
+                continue;
+            }
+        }
+        i += 1;
+    }
+    tokens
+}
+
+/// ¿La llave en `open` abre el cuerpo de un `if`/`for`/`while` o de un `else`?
+fn introduces_block(tokens: &[Token], open: usize) -> bool {
+    let Some(prev) = open.checked_sub(1) else {
+        return false;
+    };
+    if tokens[prev].kind == TokenKind::Ident && tokens[prev].text == "else" {
+        return true;
+    }
+    if !is(tokens.get(prev), ")") {
+        return false;
+    }
+    let mut depth = 0;
+    for j in (0..=prev).rev() {
+        if is(tokens.get(j), ")") {
+            depth += 1;
+        } else if is(tokens.get(j), "(") {
+            depth -= 1;
+            if depth == 0 {
+                return j > 0
+                    && tokens[j - 1].kind == TokenKind::Ident
+                    && matches!(tokens[j - 1].text.as_str(), "if" | "for" | "while");
+            }
+        }
+    }
+    false
+}
+
+fn matching(tokens: &[Token], open: usize, open_text: &str, close_text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (j, token) in tokens.iter().enumerate().skip(open) {
+        if is(Some(token), open_text) {
+            depth += 1;
+        } else if is(Some(token), close_text) {
+            depth -= 1;
+            if depth == 0 {
+                return Some(j);
+            }
+        }
+    }
+    None
+}
+
+/// Precedencia de los operadores binarios (mayor = liga más fuerte).
+fn precedence(token: &Token) -> Option<u8> {
+    let text = token.text.as_str();
+    match token.kind {
+        TokenKind::Ident => match text {
+            "and" => Some(2),
+            "or" => Some(1),
+            _ => None,
+        },
+        TokenKind::Punct => match text {
+            "*" | "/" | "%" => Some(10),
+            "+" | "-" => Some(9),
+            "<<" | ">>" => Some(8),
+            "<" | ">" | "<=" | ">=" => Some(7),
+            "==" | "!=" | "<>" => Some(6),
+            "&" => Some(5),
+            "^" => Some(4),
+            "|" => Some(3),
+            "&&" => Some(2),
+            "||" => Some(1),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_commutative(token: &Token) -> bool {
+    matches!(
+        token.text.as_str(),
+        "==" | "!=" | "<>" | "+" | "*" | "&" | "|" | "^" | "&&" | "||" | "and" | "or"
+    ) && precedence(token).is_some()
+}
+
+fn is_atom(token: Option<&Token>) -> bool {
+    token.is_some_and(|t| {
+        matches!(
+            t.kind,
+            TokenKind::Ident | TokenKind::Number | TokenKind::Literal
+        ) && precedence(t).is_none()
+    })
+}
+
+/// ¿Este token separa el operando de un operador con precedencia `prec`?
+fn is_boundary(token: Option<&Token>, prec: u8, left: bool) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    if let Some(other) = precedence(token) {
+        return other < prec;
+    }
+    match token.kind {
+        TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent => true,
+        TokenKind::Ident => left && token.text == "return",
+        TokenKind::Punct => {
+            let openers = [
+                "(", "[", ",", ";", "{", "}", "=", ":=", ":", "?", "+=", "-=",
+            ];
+            let closers = [")", "]", ",", ";", "}", "?", ":"];
+            if left {
+                openers.contains(&token.text.as_str())
+            } else {
+                closers.contains(&token.text.as_str())
+            }
+        }
+        _ => false,
+    }
+}
+
+/// `b == a` pasa a `a == b` cuando los dos operandos son simples (identificador,
+/// número o literal) y ningún operador vecino liga más fuerte.
+fn sort_commutative_operands(tokens: &mut [Token]) {
+    for op in 1..tokens.len().saturating_sub(1) {
+        if !is_commutative(&tokens[op]) {
+            continue;
+        }
+        let prec = precedence(&tokens[op]).unwrap_or_default();
+        let (left, right) = (op - 1, op + 1);
+        if is_atom(tokens.get(left))
+            && is_atom(tokens.get(right))
+            && is_boundary(left.checked_sub(1).and_then(|j| tokens.get(j)), prec, true)
+            && is_boundary(tokens.get(right + 1), prec, false)
+            && tokens[right].text < tokens[left].text
+        {
+            tokens.swap(left, right);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same(code_a: &str, code_b: &str, rules: &[Equivalence]) -> bool {
+        canonical(Language::C, code_a, rules) == canonical(Language::C, code_b, rules)
+    }
+
+    #[test]
+    fn rules_canonicalize_simple_cases() {
+        let rules = [Equivalence::Increment];
+        assert!(same(
+            "for (i = 0; i < n; i++) x--;",
+            "for (i = 0; i < n; i += 1) x = x - 1;",
+            &rules
+        ));
+        assert!(same("++i;", "i = i + 1;", &rules));
+        assert!(!same("a = i++;", "a = i += 1;", &rules));
+        assert!(!same("i++;", "i += 1;", &[]));
+
+        let rules = [Equivalence::OptionalBraces];
+        assert!(same(
+            "if (a > 0) { printf(\"%d\", a); } else { a = 0; }",
+            "if (a > 0) printf(\"%d\", a); else a = 0;",
+            &rules
+        ));
+        assert!(!same("if (a) { b; c; }", "if (a) b; c;", &rules));
+        assert!(!same(
+            "int main() { return 0; }",
+            "int main() return 0;",
+            &rules
+        ));
+
+        let rules = [Equivalence::Commutative];
+        assert!(same(
+            "if (0 == x && y != 1) z = b * a;",
+            "if (x == 0 && 1 != y) z = a * b;",
+            &rules
+        ));
+        assert!(!same("z = c + b * a;", "z = b + c * a;", &rules));
+        assert!(!same("z = a - b;", "z = b - a;", &rules));
+    }
+
+    #[test]
+    fn any_reference_answer() {
+        let question: Question = serde_yaml::from_str(
+            r#"
+id: c-alt
+language: C
+module: 1
+prompt: p
+answer: "int a = 1; int b = 2;"
+answers: ["int b = 2; int a = 1;"]
+equivalences: [increment]
+"#,
+        )
+        .unwrap();
+        assert!(matches_reference(&question, "int b=2;\nint a=1;"));
+        assert!(!matches_reference(&question, "int a = 2; int b = 1;"));
+    }
+}
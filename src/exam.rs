@@ -0,0 +1,119 @@
+//! Modo examen: configuración, sorteo reproducible de preguntas y resultados.
+//! Los resultados se guardan aparte del progreso (`QuizProgress`): un examen no
+//! completa preguntas ni desbloquea niveles.
+
+use crate::model::Language;
+use crate::shuffle::{seed_from, shuffle};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ExamConfig {
+    /// Niveles de los que se sortean preguntas, por [`crate::model::level_key`].
+    pub levels: Vec<String>,
+    pub questions: usize,
+    pub minutes: u32,
+    /// Con la misma semilla y el mismo banco, todos reciben el mismo examen.
+    pub seed: String,
+}
+
+impl Default for ExamConfig {
+    fn default() -> Self {
+        Self {
+            levels: Vec::new(),
+            questions: 10,
+            minutes: 30,
+            seed: "examen".into(),
+        }
+    }
+}
+
+impl ExamConfig {
+    /// Sortea `questions` elementos de `pool` barajándolo con la semilla.
+    pub fn draw<T>(&self, mut pool: Vec<T>) -> Vec<T> {
+        shuffle(&mut pool, seed_from(&self.seed));
+        pool.truncate(self.questions);
+        pool
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExamAnswer {
+    pub question_id: String,
+    pub module: usize,
+    pub level: usize,
+    pub solved: bool,
+    pub attempts: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExamResult {
+    pub language: Language,
+    pub seed: String,
+    /// Día (desde el 1970-01-01) en que se hizo.
+    pub day: u64,
+    pub time_limit_secs: u64,
+    pub used_secs: u64,
+    /// Se entregó al agotarse el tiempo.
+    pub timed_out: bool,
+    pub answers: Vec<ExamAnswer>,
+}
+
+impl ExamResult {
+    pub fn solved(&self) -> usize {
+        self.answers.iter().filter(|answer| answer.solved).count()
+    }
+
+    /// Nota sobre 10, con un decimal.
+    pub fn grade(&self) -> f32 {
+        if self.answers.is_empty() {
+            return 0.0;
+        }
+        let grade = self.solved() as f32 * 10.0 / self.answers.len() as f32;
+        (grade * 10.0).round() / 10.0
+    }
+}
+
+/// `mm:ss` para el cronómetro y el informe.
+pub fn clock(secs: u64) -> String {
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// Fecha `aaaa-mm-dd` de un día contado desde el 1970-01-01 (calendario civil).
+pub fn date(day: u64) -> String {
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_draws_the_same_exam() {
+        let config = ExamConfig {
+            questions: 4,
+            seed: "semana-3".into(),
+            ..ExamConfig::default()
+        };
+        let pool: Vec<u32> = (0..20).collect();
+        let drawn = config.draw(pool.clone());
+        assert_eq!(drawn.len(), 4);
+        assert_eq!(drawn, config.draw(pool.clone()));
+        let other = ExamConfig {
+            seed: "semana-4".into(),
+            ..config.clone()
+        };
+        assert_ne!(drawn, other.draw(pool));
+        assert_eq!(config.draw(vec![1, 2]).len(), 2);
+        assert_eq!(date(20_744), "2026-10-18");
+    }
+}
@@ -0,0 +1,127 @@
+//! Historial de envíos por pregunta: cuándo, qué se envió, qué veredicto tuvo y
+//! cuánto tardó la corrección. Se guarda en el progreso de cada lenguaje.
+
+use crate::exam::date;
+use crate::judge::judge_c::JudgeResult;
+use serde::{Deserialize, Serialize};
+
+/// Envíos que se conservan por pregunta; al pasarse se descartan los más antiguos.
+pub const MAX_ATTEMPTS: usize = 30;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer,
+    CompileError,
+    Timeout,
+    RuntimeError,
+    InfrastructureError,
+    RequirementViolation,
+}
+
+impl Verdict {
+    pub fn of(result: &JudgeResult) -> Self {
+        match result {
+            JudgeResult::Accepted { .. } => Verdict::Accepted,
+            JudgeResult::WrongAnswer { .. } => Verdict::WrongAnswer,
+            JudgeResult::CompileError { .. } => Verdict::CompileError,
+            JudgeResult::Timeout { .. } => Verdict::Timeout,
+            JudgeResult::RuntimeError { .. } => Verdict::RuntimeError,
+            JudgeResult::InfrastructureError { .. } => Verdict::InfrastructureError,
+            JudgeResult::RequirementViolation { .. } => Verdict::RequirementViolation,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Verdict::Accepted => "✅ Correcto",
+            Verdict::WrongAnswer => "❌ Incorrecto",
+            Verdict::CompileError => "⛔ Error de compilación",
+            Verdict::Timeout => "⏱ Tiempo excedido",
+            Verdict::RuntimeError => "💥 Error en ejecución",
+            Verdict::InfrastructureError => "⚠ Error del juez",
+            Verdict::RequirementViolation => "🚫 No cumple los requisitos",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Attempt {
+    /// Segundos desde el 1970-01-01 (UTC).
+    pub timestamp: u64,
+    /// Lo enviado: el código, o las opciones/huecos en forma de texto.
+    pub code: String,
+    pub verdict: Verdict,
+    /// Milisegundos desde el envío hasta el veredicto.
+    pub duration_ms: u64,
+}
+
+impl Attempt {
+    /// `aaaa-mm-dd hh:mm` (UTC).
+    pub fn when(&self) -> String {
+        let secs = self.timestamp % 86_400;
+        format!(
+            "{} {:02}:{:02}",
+            date(self.timestamp / 86_400),
+            secs / 3_600,
+            secs % 3_600 / 60
+        )
+    }
+
+    pub fn duration(&self) -> String {
+        if self.duration_ms < 1_000 {
+            format!("{} ms", self.duration_ms)
+        } else {
+            format!("{:.1} s", self.duration_ms as f64 / 1_000.0)
+        }
+    }
+}
+
+/// Añade `attempt` al historial de una pregunta respetando [`MAX_ATTEMPTS`].
+pub fn push(history: &mut Vec<Attempt>, attempt: Attempt) {
+    history.push(attempt);
+    if history.len() > MAX_ATTEMPTS {
+        history.drain(..history.len() - MAX_ATTEMPTS);
+    }
+}
+
+/// Milisegundos desde el 1970-01-01; sirve tanto de marca de tiempo como de
+/// cronómetro (`Instant` no existe en wasm).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs_f64() * 1_000.0)
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_latest_attempts() {
+        let mut history = Vec::new();
+        for i in 0..MAX_ATTEMPTS as u64 + 5 {
+            push(
+                &mut history,
+                Attempt {
+                    timestamp: 1_792_332_180 + i,
+                    code: format!("intento {i}"),
+                    verdict: Verdict::WrongAnswer,
+                    duration_ms: 1_240,
+                },
+            );
+        }
+        assert_eq!(history.len(), MAX_ATTEMPTS);
+        assert_eq!(history[0].code, "intento 5");
+        assert_eq!(history[0].when(), "2026-10-18 14:03");
+        assert_eq!(history[0].duration(), "1.2 s");
+    }
+}
@@ -0,0 +1,225 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Configuración de autenticación del judge server.
+///
+/// Acepta claves estáticas (`JUDGE_API_KEYS` / `JUDGE_API_KEYS_FILE`) y/o
+/// tokens firmados con HMAC-SHA256 (`JUDGE_TOKEN_SECRET` / `JUDGE_TOKEN_SECRET_FILE`).
+/// Si no hay nada configurado, la autenticación queda desactivada.
+#[derive(Debug, Clone, Default)]
+pub struct JudgeAuth {
+    api_keys: Vec<String>,
+    token_secret: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    MissingCredentials,
+    InvalidCredentials,
+    MalformedToken,
+    ExpiredToken,
+}
+
+impl AuthError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            AuthError::MissingCredentials => "Falta la cabecera Authorization: Bearer <token>.",
+            AuthError::InvalidCredentials => "Credenciales del judge no válidas.",
+            AuthError::MalformedToken => "Token del judge mal formado.",
+            AuthError::ExpiredToken => "El token del judge ha caducado.",
+        }
+    }
+}
+
+impl JudgeAuth {
+    pub fn new(api_keys: Vec<String>, token_secret: Option<Vec<u8>>) -> Self {
+        Self {
+            api_keys: api_keys
+                .into_iter()
+                .map(|k| k.trim().to_string())
+                .filter(|k| !k.is_empty())
+                .collect(),
+            token_secret: token_secret.filter(|s| !s.is_empty()),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        let mut api_keys = Vec::new();
+        if let Ok(value) = std::env::var("JUDGE_API_KEYS") {
+            api_keys.extend(value.split(',').map(str::to_string));
+        }
+        if let Some(content) = read_env_file("JUDGE_API_KEYS_FILE")? {
+            api_keys.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+
+        Ok(Self::new(api_keys, token_secret_from_env()?))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.api_keys.is_empty() || self.token_secret.is_some()
+    }
+
+    /// Valida el valor de la cabecera `Authorization` (con o sin prefijo `Bearer`).
+    pub fn check_header(&self, header: Option<&str>) -> Result<(), AuthError> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let raw = header.map(str::trim).unwrap_or_default();
+        let credential = raw
+            .strip_prefix("Bearer ")
+            .or_else(|| raw.strip_prefix("bearer "))
+            .unwrap_or(raw)
+            .trim();
+
+        if credential.is_empty() {
+            return Err(AuthError::MissingCredentials);
+        }
+
+        self.check_credential(credential, unix_now())
+    }
+
+    pub fn check_credential(&self, credential: &str, now: u64) -> Result<(), AuthError> {
+        if self
+            .api_keys
+            .iter()
+            .any(|key| constant_time_eq(key.as_bytes(), credential.as_bytes()))
+        {
+            return Ok(());
+        }
+
+        match &self.token_secret {
+            Some(secret) => verify_token(secret, credential, now),
+            None => Err(AuthError::InvalidCredentials),
+        }
+    }
+}
+
+/// Lee el secreto HMAC de `JUDGE_TOKEN_SECRET` o, si no existe, de `JUDGE_TOKEN_SECRET_FILE`.
+pub fn token_secret_from_env() -> Result<Option<Vec<u8>>, String> {
+    match std::env::var("JUDGE_TOKEN_SECRET") {
+        Ok(value) if !value.trim().is_empty() => Ok(Some(value.trim().as_bytes().to_vec())),
+        _ => Ok(read_env_file("JUDGE_TOKEN_SECRET_FILE")?.map(|s| s.trim().as_bytes().to_vec())),
+    }
+}
+
+/// Genera un token `<sujeto>.<expira_unix>.<firma_hex>` válido durante `ttl_secs`.
+pub fn mint_token(secret: &[u8], subject: &str, ttl_secs: u64) -> Result<String, String> {
+    if subject.contains('.') {
+        return Err("El sujeto del token no puede contener '.'".into());
+    }
+    let expires_at = unix_now().saturating_add(ttl_secs);
+    let payload = format!("{subject}.{expires_at}");
+    let signature = sign(secret, &payload);
+    Ok(format!("{payload}.{}", to_hex(&signature)))
+}
+
+fn verify_token(secret: &[u8], token: &str, now: u64) -> Result<(), AuthError> {
+    let mut parts = token.rsplitn(3, '.');
+    let (signature_hex, expires_at, subject) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(sig), Some(exp), Some(sub)) => (sig, exp, sub),
+        _ => return Err(AuthError::InvalidCredentials),
+    };
+
+    let expires_at: u64 = expires_at.parse().map_err(|_| AuthError::MalformedToken)?;
+    let signature = from_hex(signature_hex).ok_or(AuthError::MalformedToken)?;
+
+    let mut mac = HmacSha256::new_from_slice(secret).map_err(|_| AuthError::InvalidCredentials)?;
+    mac.update(format!("{subject}.{expires_at}").as_bytes());
+    mac.verify_slice(&signature)
+        .map_err(|_| AuthError::InvalidCredentials)?;
+
+    if expires_at < now {
+        return Err(AuthError::ExpiredToken);
+    }
+
+    Ok(())
+}
+
+fn sign(secret: &[u8], payload: &str) -> Vec<u8> {
+    let mut mac =
+        HmacSha256::new_from_slice(secret).expect("HMAC acepta claves de cualquier tamaño");
+    mac.update(payload.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn read_env_file(var: &str) -> Result<Option<String>, String> {
+    match std::env::var(var) {
+        Ok(path) if !path.trim().is_empty() => std::fs::read_to_string(path.trim())
+            .map(Some)
+            .map_err(|e| format!("No se pudo leer {var} ({path}): {e}")),
+        _ => Ok(None),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minted_token_is_accepted_until_it_expires() {
+        let auth = JudgeAuth::new(vec![], Some(b"secreto".to_vec()));
+        let token = mint_token(b"secreto", "aula1", 60).expect("token");
+        assert_eq!(auth.check_credential(&token, unix_now()), Ok(()));
+        assert_eq!(
+            auth.check_credential(&token, unix_now() + 120),
+            Err(AuthError::ExpiredToken)
+        );
+
+        let other = JudgeAuth::new(vec![], Some(b"otro".to_vec()));
+        assert_eq!(
+            other.check_credential(&token, unix_now()),
+            Err(AuthError::InvalidCredentials)
+        );
+    }
+
+    #[test]
+    fn api_keys_and_disabled_auth() {
+        let auth = JudgeAuth::new(vec![" clave-1 ".into(), String::new()], None);
+        assert!(auth.check_header(Some("Bearer clave-1")).is_ok());
+        assert_eq!(
+            auth.check_header(Some("Bearer clave-2")),
+            Err(AuthError::InvalidCredentials)
+        );
+        assert_eq!(auth.check_header(None), Err(AuthError::MissingCredentials));
+
+        assert!(JudgeAuth::default().check_header(None).is_ok());
+    }
+}
@@ -3,7 +3,10 @@ use crate::model::Question;
 #[derive(Debug, Clone)]
 #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
 pub enum JudgeResult {
-    Accepted,
+    /// `warnings`: avisos del compilador o del linter; no impiden aceptar.
+    Accepted {
+        warnings: String,
+    },
     CompileError {
         stderr: String,
     },
@@ -28,13 +31,22 @@ pub enum JudgeResult {
     InfrastructureError {
         message: String,
     },
+    /// No cumple `requires`, `forbids` o `max_lines`; se comprueba en el cliente,
+    /// sin llegar a ejecutar (no viaja en el protocolo).
+    RequirementViolation {
+        message: String,
+    },
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 mod native {
     use super::JudgeResult;
-    use crate::judge::judge_utils::{line_diff, matches_expected_output, normalize_newlines};
-    use crate::model::{JudgeTestCase, Question};
+    use crate::judge::judge_utils::{
+        TEST_TIMEOUT_MS, cached_warnings, command_version, compiler_warnings, line_diff,
+        matches_expected_output, normalize_newlines, store_warnings, timed_compile, timed_run,
+        user_diagnostics, with_warnings,
+    };
+    use crate::model::{JudgeTestCase, Language, Question};
     use std::collections::hash_map::DefaultHasher;
     use std::env;
     use std::fs;
@@ -45,7 +57,7 @@ mod native {
     use std::thread;
     use std::time::{Duration, Instant};
 
-    const TIMEOUT_MS: u64 = 2_000;
+    const TIMEOUT_MS: u64 = TEST_TIMEOUT_MS;
     const POLL_MS: u64 = 10;
 
     #[derive(Clone)]
@@ -81,16 +93,33 @@ mod native {
 
         for (source, source_kind) in candidates {
             let binary_path = build_cached_binary(&cache_dir, &compiler, &source, &source_kind);
-            if !binary_path.exists() {
-                if let Err(stderr) = compile_source(&compiler, &source, &binary_path) {
-                    if first_compile_error.is_none() {
-                        first_compile_error = Some(stderr);
+            let relocate = |text: &str| {
+                user_diagnostics(
+                    text,
+                    Language::C,
+                    &binary_path.with_extension("c"),
+                    &source,
+                    user_code,
+                )
+            };
+            let warnings = if binary_path.exists() {
+                cached_warnings(&binary_path.with_extension("warnings"))
+            } else {
+                match timed_compile(|| compile_source(&compiler, &source, &binary_path)) {
+                    Ok(warnings) => warnings,
+                    Err(stderr) => {
+                        if first_compile_error.is_none() {
+                            first_compile_error = Some(relocate(&stderr));
+                        }
+                        continue;
                     }
-                    continue;
                 }
-            }
+            };
 
-            return run_tests(&binary_path, &question.tests);
+            return with_warnings(
+                timed_run(|| run_tests(&binary_path, &question.tests)),
+                relocate(&warnings),
+            );
         }
 
         JudgeResult::CompileError {
@@ -144,11 +173,13 @@ mod native {
         for (idx, test) in tests.iter().enumerate() {
             let exec = execute_test(binary_path, test, idx + 1, TIMEOUT_MS);
             match exec {
-                JudgeResult::Accepted => continue,
+                JudgeResult::Accepted { .. } => continue,
                 other => return other,
             }
         }
-        JudgeResult::Accepted
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
     }
 
     fn execute_test(
@@ -249,7 +280,9 @@ mod native {
             };
         }
 
-        JudgeResult::Accepted
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
     }
 
     fn apply_harness(user_code: &str, harness: Option<&str>) -> String {
@@ -262,7 +295,12 @@ mod native {
         user_code.to_string()
     }
 
-    fn compile_source(compiler: &Path, source: &str, output_binary: &Path) -> Result<(), String> {
+    /// Compila y devuelve los avisos (`-Wall -Wextra`), que no impiden ejecutar.
+    fn compile_source(
+        compiler: &Path,
+        source: &str,
+        output_binary: &Path,
+    ) -> Result<String, String> {
         let source_path = output_binary.with_extension("c");
         fs::write(&source_path, source)
             .map_err(|e| format!("No se pudo guardar código temporal: {e}"))?;
@@ -271,18 +309,28 @@ mod native {
             .arg(&source_path)
             .arg("-std=c11")
             .arg("-O2")
+            .arg("-Wall")
+            .arg("-Wextra")
             .arg("-o")
             .arg(output_binary)
             .output()
             .map_err(|e| format!("No se pudo invocar al compilador: {e}"))?;
 
         if output.status.success() {
-            Ok(())
+            let warnings = compiler_warnings(&output.stderr, &source_path);
+            store_warnings(&output_binary.with_extension("warnings"), &warnings)?;
+            Ok(warnings)
         } else {
             Err(String::from_utf8_lossy(&output.stderr).to_string())
         }
     }
 
+    pub fn toolchain_version() -> Result<String, String> {
+        let compiler = detect_compiler()?;
+        Ok(command_version(&compiler, "--version")
+            .unwrap_or_else(|| compiler.display().to_string()))
+    }
+
     fn detect_compiler() -> Result<PathBuf, String> {
         for candidate in ["clang", "gcc"] {
             if let Ok(status) = Command::new(candidate)
@@ -329,7 +377,7 @@ Windows: instala MSYS2/MinGW y agrega gcc/clang a PATH."
     ) -> PathBuf {
         let mut hasher = DefaultHasher::new();
         compiler.to_string_lossy().hash(&mut hasher);
-        "-std=c11 -O2".hash(&mut hasher);
+        "-std=c11 -O2 -Wall -Wextra".hash(&mut hasher);
         source.hash(&mut hasher);
         std::mem::discriminant(source_kind).hash(&mut hasher);
         let key = format!("{:016x}", hasher.finish());
@@ -347,6 +395,11 @@ pub fn grade_c_question(question: &Question, user_code: &str) -> JudgeResult {
     native::grade_c_question(question, user_code)
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn toolchain_version() -> Result<String, String> {
+    native::toolchain_version()
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn grade_c_question(_question: &Question, _user_code: &str) -> JudgeResult {
     JudgeResult::InfrastructureError {
@@ -356,7 +409,13 @@ pub fn grade_c_question(_question: &Question, _user_code: &str) -> JudgeResult {
 
 pub fn format_judge_message(result: &JudgeResult) -> String {
     match result {
-        JudgeResult::Accepted => "✅ ¡Correcto!".into(),
+        JudgeResult::Accepted { warnings } if warnings.trim().is_empty() => "✅ ¡Correcto!".into(),
+        JudgeResult::Accepted { warnings } => {
+            format!(
+                "✅ ¡Correcto!\n\n⚠ Avisos del compilador:\n{}",
+                warnings.trim()
+            )
+        }
         JudgeResult::CompileError { stderr } => {
             format!("❌ Error de compilación.\n\n{}", stderr.trim())
         }
@@ -387,6 +446,9 @@ pub fn format_judge_message(result: &JudgeResult) -> String {
             stderr.trim()
         ),
         JudgeResult::InfrastructureError { message } => format!("⚠ {message}"),
+        JudgeResult::RequirementViolation { message } => {
+            format!("❌ No cumple los requisitos del ejercicio.\n\n{message}")
+        }
     }
 }
 
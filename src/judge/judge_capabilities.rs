@@ -0,0 +1,150 @@
+use crate::judge::protocol::PROTOCOL_VERSION;
+use crate::model::Language;
+use serde::{Deserialize, Serialize};
+
+/// Tamaño máximo del cuerpo de una petición al judge server.
+pub const MAX_REQUEST_BYTES: usize = 2 * 1024 * 1024;
+
+/// Envíos como máximo en una petición a `/api/judge/batch`.
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+/// Tamaño máximo del cuerpo de una petición a `/api/judge/batch`.
+pub const MAX_BATCH_REQUEST_BYTES: usize = 32 * 1024 * 1024;
+
+/// Respuesta de `GET /capabilities`: lenguajes soportados, toolchains detectados y límites.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JudgeCapabilities {
+    pub protocol_version: u32,
+    #[serde(default)]
+    pub server_version: String,
+    pub languages: Vec<LanguageCapability>,
+    #[serde(default)]
+    pub limits: JudgeLimits,
+    #[serde(default)]
+    pub auth_required: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LanguageCapability {
+    pub language: String,
+    pub available: bool,
+    #[serde(default)]
+    pub toolchain: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JudgeLimits {
+    pub timeout_ms: u64,
+    pub max_request_bytes: usize,
+    #[serde(default)]
+    pub max_batch_size: usize,
+    /// Evaluaciones simultáneas que admite el servidor (0 = sin informar).
+    #[serde(default)]
+    pub workers: usize,
+}
+
+impl Default for JudgeLimits {
+    fn default() -> Self {
+        Self {
+            timeout_ms: crate::judge::judge_utils::TEST_TIMEOUT_MS,
+            max_request_bytes: MAX_REQUEST_BYTES,
+            max_batch_size: MAX_BATCH_SIZE,
+            workers: 0,
+        }
+    }
+}
+
+/// Nombre del toolchain del que depende cada lenguaje del quiz.
+/// El pseudocódigo se transpila a C, así que depende del compilador de C.
+pub fn capability_key(language: Language) -> Option<&'static str> {
+    match language {
+        Language::C | Language::Pseudocode => Some("c"),
+        Language::Kotlin => Some("kotlin"),
+        Language::Java => Some("java"),
+        Language::Rust => Some("rust"),
+        Language::Python => Some("python"),
+        Language::GitGithub => None,
+    }
+}
+
+impl JudgeCapabilities {
+    pub fn language(&self, key: &str) -> Option<&LanguageCapability> {
+        self.languages.iter().find(|l| l.language == key)
+    }
+
+    pub fn all_available(&self) -> bool {
+        self.languages.iter().all(|l| l.available)
+    }
+
+    pub fn any_available(&self) -> bool {
+        self.languages.iter().any(|l| l.available)
+    }
+
+    /// Motivo por el que no se puede evaluar `language`, o `None` si está disponible
+    /// (o si el judge no informa de ese lenguaje).
+    pub fn unavailable_reason(&self, language: Language) -> Option<String> {
+        let capability = self.language(capability_key(language)?)?;
+        if capability.available {
+            return None;
+        }
+        Some(
+            capability
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("El judge no soporta {}.", capability.language)),
+        )
+    }
+}
+
+/// Detecta los toolchains instalados en esta máquina reutilizando los `detect_*` de cada juez.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn detect_local_capabilities() -> JudgeCapabilities {
+    use crate::judge::{judge_c, judge_java, judge_kt, judge_python, judge_rust};
+
+    type Probe = fn() -> Result<String, String>;
+    let probes: [(&str, Probe); 5] = [
+        ("c", judge_c::toolchain_version),
+        ("kotlin", judge_kt::toolchain_version),
+        ("java", judge_java::toolchain_version),
+        ("rust", judge_rust::toolchain_version),
+        ("python", judge_python::toolchain_version),
+    ];
+
+    let handles: Vec<_> = probes
+        .into_iter()
+        .map(|(language, probe)| (language, std::thread::spawn(probe)))
+        .collect();
+
+    let languages = handles
+        .into_iter()
+        .map(|(language, handle)| {
+            let result = handle
+                .join()
+                .unwrap_or_else(|_| Err("La detección del toolchain falló.".into()));
+            match result {
+                Ok(version) => LanguageCapability {
+                    language: language.to_string(),
+                    available: true,
+                    toolchain: Some(version),
+                    message: None,
+                },
+                Err(message) => LanguageCapability {
+                    language: language.to_string(),
+                    available: false,
+                    toolchain: None,
+                    message: Some(message),
+                },
+            }
+        })
+        .collect();
+
+    JudgeCapabilities {
+        protocol_version: PROTOCOL_VERSION,
+        server_version: env!("CARGO_PKG_VERSION").to_string(),
+        languages,
+        limits: JudgeLimits::default(),
+        auth_required: false,
+    }
+}
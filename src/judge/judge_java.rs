@@ -3,8 +3,12 @@ use crate::judge::judge_c::JudgeResult;
 #[cfg(not(target_arch = "wasm32"))]
 mod native_java {
     use crate::judge::judge_c::JudgeResult;
-    use crate::judge::judge_utils::{line_diff, matches_expected_output, normalize_newlines};
-    use crate::model::{JudgeTestCase, Question};
+    use crate::judge::judge_utils::{
+        TEST_TIMEOUT_MS, cached_warnings, command_version, compiler_warnings, line_diff,
+        matches_expected_output, normalize_newlines, store_warnings, timed_compile, timed_run,
+        user_diagnostics, with_warnings,
+    };
+    use crate::model::{JudgeTestCase, Language, Question};
     use std::collections::hash_map::DefaultHasher;
     use std::env;
     use std::fs;
@@ -15,7 +19,7 @@ mod native_java {
     use std::thread;
     use std::time::{Duration, Instant};
 
-    const TIMEOUT_MS: u64 = 2_000;
+    const TIMEOUT_MS: u64 = TEST_TIMEOUT_MS;
     const POLL_MS: u64 = 10;
 
     #[derive(Clone)]
@@ -54,16 +58,33 @@ mod native_java {
 
         for (source, source_kind) in candidates {
             let class_dir = build_cached_class_dir(&cache_dir, &javac, &source, &source_kind);
-            if !class_dir.join("Main.class").exists() {
-                if let Err(stderr) = compile_source(&javac, &source, &class_dir) {
-                    if first_compile_error.is_none() {
-                        first_compile_error = Some(stderr);
+            let relocate = |text: &str| {
+                user_diagnostics(
+                    text,
+                    Language::Java,
+                    &class_dir.join("Main.java"),
+                    &source,
+                    user_code,
+                )
+            };
+            let warnings = if class_dir.join("Main.class").exists() {
+                cached_warnings(&class_dir.join("warnings.txt"))
+            } else {
+                match timed_compile(|| compile_source(&javac, &source, &class_dir)) {
+                    Ok(warnings) => warnings,
+                    Err(stderr) => {
+                        if first_compile_error.is_none() {
+                            first_compile_error = Some(relocate(&stderr));
+                        }
+                        continue;
                     }
-                    continue;
                 }
-            }
+            };
 
-            return run_tests(&class_dir, &question.tests);
+            return with_warnings(
+                timed_run(|| run_tests(&class_dir, &question.tests)),
+                relocate(&warnings),
+            );
         }
 
         JudgeResult::CompileError {
@@ -72,6 +93,12 @@ mod native_java {
         }
     }
 
+    pub fn toolchain_version() -> Result<String, String> {
+        let javac = detect_javac()?;
+        detect_java_runtime()?;
+        Ok(command_version(&javac, "-version").unwrap_or_else(|| "javac".into()))
+    }
+
     fn detect_javac() -> Result<PathBuf, String> {
         if let Ok(status) = Command::new("javac")
             .arg("-version")
@@ -162,7 +189,8 @@ mod native_java {
         cache_dir.join(key)
     }
 
-    fn compile_source(javac: &Path, source: &str, out_dir: &Path) -> Result<(), String> {
+    /// Compila con `-Xlint:all` y devuelve los avisos de javac.
+    fn compile_source(javac: &Path, source: &str, out_dir: &Path) -> Result<String, String> {
         fs::create_dir_all(out_dir)
             .map_err(|err| format!("No se pudo crear directorio temporal Java: {err}"))?;
 
@@ -172,13 +200,16 @@ mod native_java {
 
         let output = Command::new(javac)
             .arg(&src_path)
+            .arg("-Xlint:all")
             .arg("-d")
             .arg(out_dir)
             .output()
             .map_err(|err| format!("No se pudo invocar javac: {err}"))?;
 
         if output.status.success() {
-            Ok(())
+            let warnings = compiler_warnings(&output.stderr, &src_path);
+            store_warnings(&out_dir.join("warnings.txt"), &warnings)?;
+            Ok(warnings)
         } else {
             Err(String::from_utf8_lossy(&output.stderr).to_string())
         }
@@ -188,11 +219,13 @@ mod native_java {
         for (idx, test) in tests.iter().enumerate() {
             let exec = execute_test(class_dir, test, idx + 1, TIMEOUT_MS);
             match exec {
-                JudgeResult::Accepted => continue,
+                JudgeResult::Accepted { .. } => continue,
                 other => return other,
             }
         }
-        JudgeResult::Accepted
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
     }
 
     fn execute_test(
@@ -263,7 +296,9 @@ mod native_java {
                         };
                     }
 
-                    return JudgeResult::Accepted;
+                    return JudgeResult::Accepted {
+                        warnings: String::new(),
+                    };
                 }
                 Ok(None) => {
                     if start.elapsed() > Duration::from_millis(timeout_ms) {
@@ -288,7 +323,7 @@ mod native_java {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use native_java::grade_java_question;
+pub use native_java::{grade_java_question, toolchain_version};
 
 #[cfg(target_arch = "wasm32")]
 pub fn grade_java_question(_question: &crate::model::Question, _user_code: &str) -> JudgeResult {
@@ -3,8 +3,12 @@ use crate::judge::judge_c::JudgeResult;
 #[cfg(not(target_arch = "wasm32"))]
 mod native_kotlin {
     use crate::judge::judge_c::JudgeResult;
-    use crate::judge::judge_utils::{line_diff, matches_expected_output};
-    use crate::model::{JudgeTestCase, Question};
+    use crate::judge::judge_utils::{
+        TEST_TIMEOUT_MS, cached_warnings, command_version, compiler_warnings, line_diff,
+        matches_expected_output, store_warnings, timed_compile, timed_run, user_diagnostics,
+        with_warnings,
+    };
+    use crate::model::{JudgeTestCase, Language, Question};
     use std::collections::hash_map::DefaultHasher;
     use std::env;
     use std::fs;
@@ -15,7 +19,7 @@ mod native_kotlin {
     use std::thread;
     use std::time::{Duration, Instant};
 
-    const TIMEOUT_MS: u64 = 2_000;
+    const TIMEOUT_MS: u64 = TEST_TIMEOUT_MS;
     const POLL_MS: u64 = 10;
 
     pub fn grade_kotlin_question(question: &Question, user_code: &str) -> JudgeResult {
@@ -40,13 +44,37 @@ mod native_kotlin {
         };
 
         let jar_path = build_cached_jar(&cache_dir, &kotlinc, user_code);
-        if !jar_path.exists() {
-            if let Err(stderr) = compile_kotlin(&kotlinc, user_code, &jar_path) {
-                return JudgeResult::CompileError { stderr };
+        let relocate = |text: &str| {
+            user_diagnostics(
+                text,
+                Language::Kotlin,
+                &jar_path.with_extension("kt"),
+                user_code,
+                user_code,
+            )
+        };
+        let warnings = if jar_path.exists() {
+            cached_warnings(&jar_path.with_extension("warnings"))
+        } else {
+            match timed_compile(|| compile_kotlin(&kotlinc, user_code, &jar_path)) {
+                Ok(warnings) => warnings,
+                Err(stderr) => {
+                    return JudgeResult::CompileError {
+                        stderr: relocate(&stderr),
+                    };
+                }
             }
-        }
+        };
+
+        with_warnings(
+            timed_run(|| run_tests_jar(&jar_path, &question.tests)),
+            relocate(&warnings),
+        )
+    }
 
-        run_tests_jar(&jar_path, &question.tests)
+    pub fn toolchain_version() -> Result<String, String> {
+        let kotlinc = detect_kotlinc()?;
+        Ok(command_version(&kotlinc, "-version").unwrap_or_else(|| "kotlinc".into()))
     }
 
     fn detect_kotlinc() -> Result<PathBuf, String> {
@@ -95,7 +123,8 @@ mod native_kotlin {
         cache_dir.join(format!("{key}.jar"))
     }
 
-    fn compile_kotlin(kotlinc: &Path, source: &str, out_jar: &Path) -> Result<(), String> {
+    /// Compila y devuelve los avisos de kotlinc (activos por defecto).
+    fn compile_kotlin(kotlinc: &Path, source: &str, out_jar: &Path) -> Result<String, String> {
         // Asegura que exista main si tu UX lo requiere.
         // (Si quieres: detectas "fun main" y si no lo hay, envuelves.)
         let src_path = out_jar.with_extension("kt");
@@ -111,7 +140,9 @@ mod native_kotlin {
             .map_err(|e| format!("No se pudo invocar kotlinc: {e}"))?;
 
         if output.status.success() {
-            Ok(())
+            let warnings = compiler_warnings(&output.stderr, &src_path);
+            store_warnings(&out_jar.with_extension("warnings"), &warnings)?;
+            Ok(warnings)
         } else {
             Err(String::from_utf8_lossy(&output.stderr).to_string())
         }
@@ -121,11 +152,13 @@ mod native_kotlin {
         for (idx, test) in tests.iter().enumerate() {
             let exec = execute_test_jar(jar_path, test, idx + 1, TIMEOUT_MS);
             match exec {
-                JudgeResult::Accepted => continue,
+                JudgeResult::Accepted { .. } => continue,
                 other => return other,
             }
         }
-        JudgeResult::Accepted
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
     }
 
     fn execute_test_jar(
@@ -194,7 +227,9 @@ mod native_kotlin {
                         };
                     }
 
-                    return JudgeResult::Accepted;
+                    return JudgeResult::Accepted {
+                        warnings: String::new(),
+                    };
                 }
                 Ok(None) => {
                     if start.elapsed() > Duration::from_millis(timeout_ms) {
@@ -219,7 +254,7 @@ mod native_kotlin {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use native_kotlin::grade_kotlin_question;
+pub use native_kotlin::{grade_kotlin_question, toolchain_version};
 
 #[cfg(target_arch = "wasm32")]
 pub fn grade_kotlin_question(_question: &crate::model::Question, _user_code: &str) -> JudgeResult {
@@ -0,0 +1,235 @@
+use crate::judge::judge_utils::JudgeTimings;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Límites (en segundos) de los histogramas de latencia.
+const LATENCY_BUCKETS: [f64; 10] = [0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: Duration) {
+        let secs = value.as_secs_f64();
+        for (bucket, limit) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if secs <= limit {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += secs;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        for (bucket, limit) in self.buckets.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(out, "{name}_bucket{{{labels}{sep}le=\"{limit}\"}} {bucket}");
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{{labels}{sep}le=\"+Inf\"}} {}",
+            self.count
+        );
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    /// (lenguaje, veredicto) -> nº de evaluaciones
+    verdicts: BTreeMap<(String, String), u64>,
+    /// código HTTP -> nº de peticiones
+    http_status: BTreeMap<u16, u64>,
+    compile_latency: BTreeMap<String, Histogram>,
+    run_latency: BTreeMap<String, Histogram>,
+}
+
+/// Métricas del judge server en formato de exposición de Prometheus.
+#[derive(Debug, Default)]
+pub struct JudgeMetrics {
+    counters: Mutex<Counters>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    in_flight: AtomicI64,
+}
+
+/// Guarda que mantiene el gauge de trabajos en curso mientras vive.
+pub struct InFlightGuard<'a> {
+    metrics: &'a JudgeMetrics,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.metrics.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl JudgeMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_job(&self) -> InFlightGuard<'_> {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard { metrics: self }
+    }
+
+    pub fn in_flight(&self) -> i64 {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Registra una evaluación terminada. Si hubo ejecución pero no compilación,
+    /// el artefacto vino de la cache.
+    pub fn record_judgement(&self, language: &str, verdict: &str, timings: JudgeTimings) {
+        if timings.run.is_some() {
+            if timings.compile.is_some() {
+                self.cache_misses.fetch_add(1, Ordering::Relaxed);
+            } else {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        *counters
+            .verdicts
+            .entry((language.to_string(), verdict.to_string()))
+            .or_default() += 1;
+        if let Some(compile) = timings.compile {
+            counters
+                .compile_latency
+                .entry(language.to_string())
+                .or_default()
+                .observe(compile);
+        }
+        if let Some(run) = timings.run {
+            counters
+                .run_latency
+                .entry(language.to_string())
+                .or_default()
+                .observe(run);
+        }
+    }
+
+    pub fn record_http_status(&self, status: u16) {
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        *counters.http_status.entry(status).or_default() += 1;
+    }
+
+    pub fn render(&self) -> String {
+        let counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        out.push_str(
+            "# HELP summer_quiz_judge_requests_total Evaluaciones por lenguaje y veredicto.\n",
+        );
+        out.push_str("# TYPE summer_quiz_judge_requests_total counter\n");
+        for ((language, verdict), count) in &counters.verdicts {
+            let _ = writeln!(
+                out,
+                "summer_quiz_judge_requests_total{{language=\"{}\",verdict=\"{}\"}} {count}",
+                escape_label(language),
+                escape_label(verdict)
+            );
+        }
+
+        out.push_str("# HELP summer_quiz_http_responses_total Respuestas HTTP por código.\n");
+        out.push_str("# TYPE summer_quiz_http_responses_total counter\n");
+        for (status, count) in &counters.http_status {
+            let _ = writeln!(
+                out,
+                "summer_quiz_http_responses_total{{status=\"{status}\"}} {count}"
+            );
+        }
+
+        out.push_str("# HELP summer_quiz_judge_compile_seconds Tiempo de compilación.\n");
+        out.push_str("# TYPE summer_quiz_judge_compile_seconds histogram\n");
+        for (language, histogram) in &counters.compile_latency {
+            let labels = format!("language=\"{}\"", escape_label(language));
+            histogram.render(&mut out, "summer_quiz_judge_compile_seconds", &labels);
+        }
+
+        out.push_str("# HELP summer_quiz_judge_run_seconds Tiempo de ejecución de los tests.\n");
+        out.push_str("# TYPE summer_quiz_judge_run_seconds histogram\n");
+        for (language, histogram) in &counters.run_latency {
+            let labels = format!("language=\"{}\"", escape_label(language));
+            histogram.render(&mut out, "summer_quiz_judge_run_seconds", &labels);
+        }
+
+        let hits = self.cache_hits.load(Ordering::Relaxed);
+        let misses = self.cache_misses.load(Ordering::Relaxed);
+        out.push_str(
+            "# HELP summer_quiz_judge_cache_hits_total Artefactos reutilizados de la cache.\n",
+        );
+        out.push_str("# TYPE summer_quiz_judge_cache_hits_total counter\n");
+        let _ = writeln!(out, "summer_quiz_judge_cache_hits_total {hits}");
+        out.push_str(
+            "# HELP summer_quiz_judge_cache_misses_total Artefactos compilados de nuevo.\n",
+        );
+        out.push_str("# TYPE summer_quiz_judge_cache_misses_total counter\n");
+        let _ = writeln!(out, "summer_quiz_judge_cache_misses_total {misses}");
+
+        out.push_str("# HELP summer_quiz_judge_in_flight Evaluaciones en curso.\n");
+        out.push_str("# TYPE summer_quiz_judge_in_flight gauge\n");
+        let _ = writeln!(out, "summer_quiz_judge_in_flight {}", self.in_flight());
+
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_includes_verdicts_histograms_and_cache() {
+        let metrics = JudgeMetrics::new();
+        let guard = metrics.start_job();
+        metrics.record_judgement(
+            "c",
+            "accepted",
+            JudgeTimings {
+                compile: Some(Duration::from_millis(300)),
+                run: Some(Duration::from_millis(20)),
+            },
+        );
+        metrics.record_judgement(
+            "c",
+            "wrong_answer",
+            JudgeTimings {
+                compile: None,
+                run: Some(Duration::from_millis(20)),
+            },
+        );
+
+        let text = metrics.render();
+        assert!(
+            text.contains(
+                "summer_quiz_judge_requests_total{language=\"c\",verdict=\"accepted\"} 1"
+            )
+        );
+        assert!(
+            text.contains("summer_quiz_judge_compile_seconds_bucket{language=\"c\",le=\"0.5\"} 1")
+        );
+        assert!(text.contains("summer_quiz_judge_run_seconds_count{language=\"c\"} 2"));
+        assert!(text.contains("summer_quiz_judge_cache_hits_total 1"));
+        assert!(text.contains("summer_quiz_judge_in_flight 1"));
+
+        drop(guard);
+        assert_eq!(metrics.in_flight(), 0);
+    }
+}
@@ -69,22 +69,10 @@ pub fn run_pseudo_tests(
 
     let question = Question {
         language: Language::C,
-        module: 0,
-        prompt: String::new(),
         answer: c_code,
-        hint: None,
-        number: 0,
-        input_prefill: None,
         mode: Some(GradingMode::JudgeC),
         tests: tests.to_vec(),
-        judge_harness: None,
-        judge_endpoint: None,
-        is_done: false,
-        saw_solution: false,
-        attempts: 0,
-        fails: 0,
-        skips: 0,
-        id: None,
+        ..Default::default()
     };
 
     c_judge.grade(&question, &question.answer)
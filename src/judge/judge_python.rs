@@ -3,8 +3,12 @@ use crate::judge::judge_c::JudgeResult;
 #[cfg(not(target_arch = "wasm32"))]
 mod native_python {
     use crate::judge::judge_c::JudgeResult;
-    use crate::judge::judge_utils::{line_diff, matches_expected_output, normalize_newlines};
-    use crate::model::{JudgeTestCase, Question};
+    use crate::judge::judge_utils::{
+        TEST_TIMEOUT_MS, cached_warnings, command_version, compiler_warnings, line_diff,
+        matches_expected_output, normalize_newlines, store_warnings, timed_compile, timed_run,
+        user_diagnostics, with_warnings,
+    };
+    use crate::model::{JudgeTestCase, Language, Question};
     use std::collections::hash_map::DefaultHasher;
     use std::env;
     use std::fs;
@@ -15,7 +19,7 @@ mod native_python {
     use std::thread;
     use std::time::{Duration, Instant};
 
-    const TIMEOUT_MS: u64 = 2_000;
+    const TIMEOUT_MS: u64 = TEST_TIMEOUT_MS;
     const POLL_MS: u64 = 10;
 
     pub fn grade_python_question(question: &Question, user_code: &str) -> JudgeResult {
@@ -40,19 +44,37 @@ mod native_python {
         };
 
         let script_path = build_cached_script_path(&cache_dir, &python, user_code);
-        if !script_path.exists() {
+        let relocate = |text: &str| {
+            user_diagnostics(text, Language::Python, &script_path, user_code, user_code)
+        };
+        let warnings = if script_path.exists() {
+            cached_warnings(&script_path.with_extension("warnings"))
+        } else {
             if let Err(err) = fs::write(&script_path, user_code) {
                 return JudgeResult::InfrastructureError {
                     message: format!("No se pudo guardar el script Python temporal: {err}"),
                 };
             }
 
-            if let Err(stderr) = compile_python(&python, &script_path) {
-                return JudgeResult::CompileError { stderr };
+            match timed_compile(|| compile_python(&python, &script_path)) {
+                Ok(warnings) => warnings,
+                Err(stderr) => {
+                    return JudgeResult::CompileError {
+                        stderr: relocate(&stderr),
+                    };
+                }
             }
-        }
+        };
+
+        with_warnings(
+            timed_run(|| run_tests(&python, &script_path, &question.tests)),
+            relocate(&warnings),
+        )
+    }
 
-        run_tests(&python, &script_path, &question.tests)
+    pub fn toolchain_version() -> Result<String, String> {
+        let python = detect_python()?;
+        Ok(command_version(&python, "--version").unwrap_or_else(|| python.display().to_string()))
     }
 
     fn detect_python() -> Result<PathBuf, String> {
@@ -98,7 +120,9 @@ mod native_python {
         cache_dir.join(format!("{key}.py"))
     }
 
-    fn compile_python(python: &Path, script_path: &Path) -> Result<(), String> {
+    /// Comprueba la sintaxis y devuelve los avisos: los `SyntaxWarning` de
+    /// `py_compile` y, si está instalado, lo que encuentre `pyflakes`.
+    fn compile_python(python: &Path, script_path: &Path) -> Result<String, String> {
         let output = Command::new(python)
             .arg("-m")
             .arg("py_compile")
@@ -107,21 +131,44 @@ mod native_python {
             .map_err(|err| format!("No se pudo invocar Python para compilar: {err}"))?;
 
         if output.status.success() {
-            Ok(())
+            let mut warnings = compiler_warnings(&output.stderr, script_path);
+            let lint = pyflakes_warnings(python, script_path);
+            if !lint.is_empty() {
+                if !warnings.is_empty() {
+                    warnings.push('\n');
+                }
+                warnings.push_str(&lint);
+            }
+            store_warnings(&script_path.with_extension("warnings"), &warnings)?;
+            Ok(warnings)
         } else {
             Err(String::from_utf8_lossy(&output.stderr).to_string())
         }
     }
 
+    /// `pyflakes` sale con código 1 cuando hay avisos y los escribe en stdout; si no
+    /// está instalado solo deja un error en stderr, que se ignora.
+    fn pyflakes_warnings(python: &Path, script_path: &Path) -> String {
+        Command::new(python)
+            .arg("-m")
+            .arg("pyflakes")
+            .arg(script_path)
+            .output()
+            .map(|output| compiler_warnings(&output.stdout, script_path))
+            .unwrap_or_default()
+    }
+
     fn run_tests(python: &Path, script_path: &Path, tests: &[JudgeTestCase]) -> JudgeResult {
         for (idx, test) in tests.iter().enumerate() {
             let exec = execute_test(python, script_path, test, idx + 1, TIMEOUT_MS);
             match exec {
-                JudgeResult::Accepted => continue,
+                JudgeResult::Accepted { .. } => continue,
                 other => return other,
             }
         }
-        JudgeResult::Accepted
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
     }
 
     fn execute_test(
@@ -191,7 +238,9 @@ mod native_python {
                         };
                     }
 
-                    return JudgeResult::Accepted;
+                    return JudgeResult::Accepted {
+                        warnings: String::new(),
+                    };
                 }
                 Ok(None) => {
                     if start.elapsed() > Duration::from_millis(timeout_ms) {
@@ -216,7 +265,7 @@ mod native_python {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use native_python::grade_python_question;
+pub use native_python::{grade_python_question, toolchain_version};
 
 #[cfg(target_arch = "wasm32")]
 pub fn grade_python_question(_question: &crate::model::Question, _user_code: &str) -> JudgeResult {
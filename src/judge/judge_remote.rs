@@ -1,53 +1,14 @@
 use crate::judge::judge_c::JudgeResult;
-use crate::model::{JudgeTestCase, Language, Question};
-use serde::{Deserialize, Serialize};
+#[cfg(target_arch = "wasm32")]
+use crate::judge::judge_capabilities::JudgeCapabilities;
+use crate::judge::protocol::{JudgeRequest, JudgeResponse};
+use crate::model::{Language, Question};
 
 #[cfg(target_arch = "wasm32")]
 const DEFAULT_ENDPOINT: &str = "/api/judge/sync";
 #[cfg(not(target_arch = "wasm32"))]
 const DEFAULT_NATIVE_ENDPOINT: &str = "http://127.0.0.1:8787/api/judge/sync";
 
-#[derive(Debug, Serialize)]
-struct JudgeRequest {
-    language: String,
-    source: String,
-    tests: Vec<JudgeTestCase>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    harness: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    question_id: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(tag = "status", rename_all = "snake_case")]
-enum JudgeResponse {
-    Accepted,
-    CompileError {
-        stderr: String,
-    },
-    WrongAnswer {
-        test_index: usize,
-        input: String,
-        expected: String,
-        received: String,
-        diff: String,
-    },
-    Timeout {
-        test_index: usize,
-        input: String,
-        timeout_ms: u64,
-    },
-    RuntimeError {
-        test_index: usize,
-        input: String,
-        stderr: String,
-        exit_code: Option<i32>,
-    },
-    InfrastructureError {
-        message: String,
-    },
-}
-
 fn endpoint_for(question: &Question) -> String {
     question
         .judge_endpoint
@@ -151,10 +112,33 @@ fn endpoint_candidates(primary: &str) -> Vec<String> {
     candidates
 }
 
+/// URL de `/capabilities` en el mismo servidor que el endpoint de evaluación.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn capabilities_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    let path_start = endpoint.find("://").map(|i| i + 3).unwrap_or(0);
+    match endpoint[path_start..].find("/judge") {
+        Some(i) => format!("{}/capabilities", &endpoint[..path_start + i]),
+        None => format!("{endpoint}/api/capabilities"),
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use super::endpoint_candidates;
+    use super::{capabilities_url, endpoint_candidates};
+
+    #[test]
+    fn capabilities_url_sits_next_to_judge_route() {
+        assert_eq!(capabilities_url("/api/judge/sync"), "/api/capabilities");
+        assert_eq!(
+            capabilities_url("http://127.0.0.1:8787/judge/"),
+            "http://127.0.0.1:8787/capabilities"
+        );
+        assert_eq!(
+            capabilities_url("https://judge.example.com"),
+            "https://judge.example.com/api/capabilities"
+        );
+    }
 
     #[test]
     fn endpoint_candidates_include_common_paths_for_origin() {
@@ -183,15 +167,27 @@ mod tests {
 
 #[cfg(target_arch = "wasm32")]
 fn default_endpoint() -> String {
-    endpoint_from_build_env()
-        .or_else(endpoint_from_querystring)
-        .or_else(endpoint_from_meta)
-        .or_else(endpoint_from_local_storage)
+    option_env!("SUMMER_QUIZ_JUDGE_ENDPOINT")
+        .and_then(normalize_value)
+        .or_else(|| value_from_querystring("judge_endpoint"))
+        .or_else(|| value_from_meta("summer-quiz-judge-endpoint"))
+        .or_else(|| value_from_local_storage("summer_quiz_judge_endpoint"))
         .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string())
 }
 
+/// Token (API key o token firmado) que se envía como `Authorization: Bearer`.
+/// Se busca en los mismos sitios que el endpoint.
+#[cfg(target_arch = "wasm32")]
+fn default_token() -> Option<String> {
+    option_env!("SUMMER_QUIZ_JUDGE_TOKEN")
+        .and_then(normalize_value)
+        .or_else(|| value_from_querystring("judge_token"))
+        .or_else(|| value_from_meta("summer-quiz-judge-token"))
+        .or_else(|| value_from_local_storage("summer_quiz_judge_token"))
+}
+
 #[cfg(target_arch = "wasm32")]
-fn normalize_endpoint(value: &str) -> Option<String> {
+fn normalize_value(value: &str) -> Option<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         None
@@ -201,12 +197,7 @@ fn normalize_endpoint(value: &str) -> Option<String> {
 }
 
 #[cfg(target_arch = "wasm32")]
-fn endpoint_from_build_env() -> Option<String> {
-    option_env!("SUMMER_QUIZ_JUDGE_ENDPOINT").and_then(normalize_endpoint)
-}
-
-#[cfg(target_arch = "wasm32")]
-fn endpoint_from_querystring() -> Option<String> {
+pub(crate) fn value_from_querystring(param: &str) -> Option<String> {
     let window = web_sys::window()?;
     let search = window.location().search().ok()?;
     let query = search.strip_prefix('?').unwrap_or(search.as_str());
@@ -217,10 +208,10 @@ fn endpoint_from_querystring() -> Option<String> {
             None => (pair, ""),
         };
 
-        if key == "judge_endpoint" {
+        if key == param {
             let decoded = js_sys::decode_uri_component(value).ok()?;
             let decoded = decoded.as_string()?;
-            return normalize_endpoint(&decoded);
+            return normalize_value(&decoded);
         }
     }
 
@@ -228,27 +219,27 @@ fn endpoint_from_querystring() -> Option<String> {
 }
 
 #[cfg(target_arch = "wasm32")]
-fn endpoint_from_meta() -> Option<String> {
+pub(crate) fn value_from_meta(name: &str) -> Option<String> {
     let window = web_sys::window()?;
     let document = window.document()?;
     let meta = document
-        .query_selector("meta[name='summer-quiz-judge-endpoint']")
+        .query_selector(&format!("meta[name='{name}']"))
         .ok()??;
 
     meta.get_attribute("content")
         .as_deref()
-        .and_then(normalize_endpoint)
+        .and_then(normalize_value)
 }
 
 #[cfg(target_arch = "wasm32")]
-fn endpoint_from_local_storage() -> Option<String> {
+pub(crate) fn value_from_local_storage(key: &str) -> Option<String> {
     let window = web_sys::window()?;
     let storage = window.local_storage().ok()??;
     storage
-        .get_item("summer_quiz_judge_endpoint")
+        .get_item(key)
         .ok()?
         .as_deref()
-        .and_then(normalize_endpoint)
+        .and_then(normalize_value)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -259,6 +250,14 @@ fn default_endpoint() -> String {
         .unwrap_or_else(|| DEFAULT_NATIVE_ENDPOINT.to_string())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn default_token() -> Option<String> {
+    std::env::var("SUMMER_QUIZ_JUDGE_TOKEN")
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
 fn to_remote_language(lang: Language) -> &'static str {
     match lang {
         Language::C => "c",
@@ -271,57 +270,14 @@ fn to_remote_language(lang: Language) -> &'static str {
     }
 }
 
-fn map_response(resp: JudgeResponse) -> JudgeResult {
-    match resp {
-        JudgeResponse::Accepted => JudgeResult::Accepted,
-        JudgeResponse::CompileError { stderr } => JudgeResult::CompileError { stderr },
-        JudgeResponse::WrongAnswer {
-            test_index,
-            input,
-            expected,
-            received,
-            diff,
-        } => JudgeResult::WrongAnswer {
-            test_index,
-            input,
-            expected,
-            received,
-            diff,
-        },
-        JudgeResponse::Timeout {
-            test_index,
-            input,
-            timeout_ms,
-        } => JudgeResult::Timeout {
-            test_index,
-            input,
-            timeout_ms,
-        },
-        JudgeResponse::RuntimeError {
-            test_index,
-            input,
-            stderr,
-            exit_code,
-        } => JudgeResult::RuntimeError {
-            test_index,
-            input,
-            stderr,
-            exit_code,
-        },
-        JudgeResponse::InfrastructureError { message } => {
-            JudgeResult::InfrastructureError { message }
-        }
-    }
-}
-
 fn build_request(question: &Question, source: &str) -> JudgeRequest {
-    JudgeRequest {
-        language: to_remote_language(question.language).to_string(),
-        source: source.to_string(),
-        tests: question.tests.clone(),
-        harness: question.judge_harness.clone(),
-        question_id: question.id.clone(),
-    }
+    JudgeRequest::new(
+        to_remote_language(question.language),
+        source,
+        question.tests.clone(),
+        question.judge_harness.clone(),
+        Some(question.id.clone()),
+    )
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -330,11 +286,18 @@ pub fn grade_remote_question(question: &Question, user_code: &str) -> JudgeResul
     let payload = build_request(question, user_code);
     let client = reqwest::blocking::Client::new();
 
+    let token = default_token();
+
     let endpoints = endpoint_candidates(&endpoint);
     let mut last_http_error = None;
 
     for candidate in endpoints {
-        let response = match client.post(&candidate).json(&payload).send() {
+        let mut request = client.post(&candidate).json(&payload);
+        if let Some(token) = &token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = match request.send() {
             Ok(response) => response,
             Err(err) => {
                 return JudgeResult::InfrastructureError {
@@ -368,7 +331,7 @@ pub fn grade_remote_question(question: &Question, user_code: &str) -> JudgeResul
         }
 
         return match response.json::<JudgeResponse>() {
-            Ok(body) => map_response(body),
+            Ok(body) => body.outcome.into(),
             Err(err) => JudgeResult::InfrastructureError {
                 message: format!("Respuesta JSON inválida del judge remoto: {err}"),
             },
@@ -381,7 +344,6 @@ pub fn grade_remote_question(question: &Question, user_code: &str) -> JudgeResul
     }
 }
 
-
 #[cfg(target_arch = "wasm32")]
 const MAX_RETRIES: u32 = 3;
 #[cfg(target_arch = "wasm32")]
@@ -402,6 +364,7 @@ async fn fetch_once(
     window: &web_sys::Window,
     endpoint: &str,
     payload_json: &str,
+    token: Option<&str>,
 ) -> Result<JudgeResult, String> {
     use wasm_bindgen::JsCast;
     use wasm_bindgen::JsValue;
@@ -421,6 +384,13 @@ async fn fetch_once(
         .set("Content-Type", "application/json")
         .map_err(|e| format!("No se pudo asignar Content-Type: {e:?}"))?;
 
+    if let Some(token) = token {
+        request
+            .headers()
+            .set("Authorization", &format!("Bearer {token}"))
+            .map_err(|e| format!("No se pudo asignar Authorization: {e:?}"))?;
+    }
+
     let resp_value = JsFuture::from(window.fetch_with_request(&request))
         .await
         .map_err(|e| format!("NetworkError: {e:?}"))?;
@@ -449,7 +419,7 @@ async fn fetch_once(
     }
 
     match serde_json::from_str::<JudgeResponse>(&text) {
-        Ok(body) => Ok(map_response(body)),
+        Ok(body) => Ok(body.outcome.into()),
         Err(err) => Err(format!("JSON inválido: {err}. Body: {}", text.trim())),
     }
 }
@@ -476,6 +446,7 @@ pub async fn grade_remote_question(question: &Question, user_code: &str) -> Judg
         }
     };
 
+    let token = default_token();
     let endpoints = endpoint_candidates(&endpoint);
     let target = endpoints.first().cloned().unwrap_or(endpoint);
     let mut last_error = String::new();
@@ -485,7 +456,7 @@ pub async fn grade_remote_question(question: &Question, user_code: &str) -> Judg
             sleep_ms(RETRY_DELAY_MS * attempt as i32).await;
         }
 
-        match fetch_once(&window, &target, &payload_json).await {
+        match fetch_once(&window, &target, &payload_json, token.as_deref()).await {
             Ok(result) => return result,
             Err(err) => last_error = err,
         }
@@ -497,3 +468,42 @@ pub async fn grade_remote_question(question: &Question, user_code: &str) -> Judg
         ),
     }
 }
+
+/// Pregunta al judge remoto qué lenguajes puede evaluar.
+#[cfg(target_arch = "wasm32")]
+pub async fn fetch_capabilities() -> Result<JudgeCapabilities, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Request, RequestInit, RequestMode, Response};
+
+    let window = web_sys::window().ok_or("No existe window en entorno WASM.")?;
+    let url = capabilities_url(&default_endpoint());
+
+    let opts = RequestInit::new();
+    opts.set_method("GET");
+    opts.set_mode(RequestMode::Cors);
+    let request = Request::new_with_str_and_init(&url, &opts)
+        .map_err(|e| format!("No se pudo crear request: {e:?}"))?;
+
+    let response: Response = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("NetworkError: {e:?}"))?
+        .dyn_into()
+        .map_err(|_| "Respuesta no es un Response válido.".to_string())?;
+
+    if !response.ok() {
+        return Err(format!("HTTP {} en {url}", response.status()));
+    }
+
+    let text = JsFuture::from(
+        response
+            .text()
+            .map_err(|e| format!("No se pudo obtener body: {e:?}"))?,
+    )
+    .await
+    .map_err(|e| format!("No se pudo leer body: {e:?}"))?
+    .as_string()
+    .unwrap_or_default();
+
+    serde_json::from_str(&text).map_err(|e| format!("JSON inválido: {e}"))
+}
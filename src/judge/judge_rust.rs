@@ -3,8 +3,12 @@ use crate::judge::judge_c::JudgeResult;
 #[cfg(not(target_arch = "wasm32"))]
 mod native_rust {
     use crate::judge::judge_c::JudgeResult;
-    use crate::judge::judge_utils::{line_diff, matches_expected_output, normalize_newlines};
-    use crate::model::{JudgeTestCase, Question};
+    use crate::judge::judge_utils::{
+        TEST_TIMEOUT_MS, cached_warnings, command_version, compiler_warnings, line_diff,
+        matches_expected_output, normalize_newlines, store_warnings, timed_compile, timed_run,
+        user_diagnostics, with_warnings,
+    };
+    use crate::model::{JudgeTestCase, Language, Question};
     use std::collections::hash_map::DefaultHasher;
     use std::env;
     use std::fs;
@@ -15,7 +19,7 @@ mod native_rust {
     use std::thread;
     use std::time::{Duration, Instant};
 
-    const TIMEOUT_MS: u64 = 2_000;
+    const TIMEOUT_MS: u64 = TEST_TIMEOUT_MS;
     const POLL_MS: u64 = 10;
 
     #[derive(Clone)]
@@ -50,16 +54,33 @@ mod native_rust {
 
         for (source, source_kind) in candidates {
             let binary_path = build_cached_binary(&cache_dir, &rustc, &source, &source_kind);
-            if !binary_path.exists() {
-                if let Err(stderr) = compile_source(&rustc, &source, &binary_path) {
-                    if first_compile_error.is_none() {
-                        first_compile_error = Some(stderr);
+            let relocate = |text: &str| {
+                user_diagnostics(
+                    text,
+                    Language::Rust,
+                    &binary_path.with_extension("rs"),
+                    &source,
+                    user_code,
+                )
+            };
+            let warnings = if binary_path.exists() {
+                cached_warnings(&binary_path.with_extension("warnings"))
+            } else {
+                match timed_compile(|| compile_source(&rustc, &source, &binary_path)) {
+                    Ok(warnings) => warnings,
+                    Err(stderr) => {
+                        if first_compile_error.is_none() {
+                            first_compile_error = Some(relocate(&stderr));
+                        }
+                        continue;
                     }
-                    continue;
                 }
-            }
+            };
 
-            return run_tests(&binary_path, &question.tests);
+            return with_warnings(
+                timed_run(|| run_tests(&binary_path, &question.tests)),
+                relocate(&warnings),
+            );
         }
 
         JudgeResult::CompileError {
@@ -68,6 +89,11 @@ mod native_rust {
         }
     }
 
+    pub fn toolchain_version() -> Result<String, String> {
+        let rustc = detect_rustc()?;
+        Ok(command_version(&rustc, "--version").unwrap_or_else(|| "rustc".into()))
+    }
+
     fn detect_rustc() -> Result<PathBuf, String> {
         if let Ok(status) = Command::new("rustc")
             .arg("--version")
@@ -144,7 +170,8 @@ mod native_rust {
         }
     }
 
-    fn compile_source(rustc: &Path, source: &str, out_bin: &Path) -> Result<(), String> {
+    /// Compila y devuelve los avisos de rustc (activos por defecto).
+    fn compile_source(rustc: &Path, source: &str, out_bin: &Path) -> Result<String, String> {
         let src_path = out_bin.with_extension("rs");
         fs::write(&src_path, source)
             .map_err(|err| format!("No se pudo guardar Rust temporal: {err}"))?;
@@ -158,7 +185,9 @@ mod native_rust {
             .map_err(|err| format!("No se pudo invocar rustc: {err}"))?;
 
         if output.status.success() {
-            Ok(())
+            let warnings = compiler_warnings(&output.stderr, &src_path);
+            store_warnings(&out_bin.with_extension("warnings"), &warnings)?;
+            Ok(warnings)
         } else {
             Err(String::from_utf8_lossy(&output.stderr).to_string())
         }
@@ -168,11 +197,13 @@ mod native_rust {
         for (idx, test) in tests.iter().enumerate() {
             let exec = execute_test(binary_path, test, idx + 1, TIMEOUT_MS);
             match exec {
-                JudgeResult::Accepted => continue,
+                JudgeResult::Accepted { .. } => continue,
                 other => return other,
             }
         }
-        JudgeResult::Accepted
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
     }
 
     fn execute_test(
@@ -240,7 +271,9 @@ mod native_rust {
                         };
                     }
 
-                    return JudgeResult::Accepted;
+                    return JudgeResult::Accepted {
+                        warnings: String::new(),
+                    };
                 }
                 Ok(None) => {
                     if start.elapsed() > Duration::from_millis(timeout_ms) {
@@ -265,7 +298,7 @@ mod native_rust {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use native_rust::grade_rust_question;
+pub use native_rust::{grade_rust_question, toolchain_version};
 
 #[cfg(target_arch = "wasm32")]
 pub fn grade_rust_question(_question: &crate::model::Question, _user_code: &str) -> JudgeResult {
@@ -1,5 +1,10 @@
 #![allow(dead_code)]
 
+use crate::judge::judge_c::JudgeResult;
+
+/// Tiempo máximo por caso de prueba en los jueces nativos.
+pub const TEST_TIMEOUT_MS: u64 = 2_000;
+
 pub fn normalize_newlines(value: &str) -> String {
     value.replace("\r\n", "\n")
 }
@@ -77,3 +82,116 @@ pub fn line_diff(expected: &str, received: &str) -> String {
 
     "Diferencia no localizada (posible carácter invisible).".into()
 }
+
+/// Primera línea no vacía de `<programa> <arg>` (stdout o stderr), p. ej. `gcc --version`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn command_version(program: &std::path::Path, arg: &str) -> Option<String> {
+    let output = std::process::Command::new(program).arg(arg).output().ok()?;
+    [output.stdout, output.stderr].iter().find_map(|bytes| {
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Tiempos de la última evaluación realizada en este hilo (compilación y ejecución).
+/// `compile` queda a `None` si el artefacto salió de la cache.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JudgeTimings {
+    pub compile: Option<std::time::Duration>,
+    pub run: Option<std::time::Duration>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    static LAST_TIMINGS: std::cell::Cell<JudgeTimings> = const {
+        std::cell::Cell::new(JudgeTimings { compile: None, run: None })
+    };
+}
+
+/// Devuelve y limpia los tiempos acumulados en el hilo actual.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn take_judge_timings() -> JudgeTimings {
+    LAST_TIMINGS.with(|cell| cell.take())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn timed_compile<T>(compile: impl FnOnce() -> T) -> T {
+    let started = std::time::Instant::now();
+    let result = compile();
+    let elapsed = started.elapsed();
+    LAST_TIMINGS.with(|cell| {
+        let mut timings = cell.get();
+        timings.compile = Some(timings.compile.unwrap_or_default() + elapsed);
+        cell.set(timings);
+    });
+    result
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn timed_run<T>(run: impl FnOnce() -> T) -> T {
+    let started = std::time::Instant::now();
+    let result = run();
+    let elapsed = started.elapsed();
+    LAST_TIMINGS.with(|cell| {
+        let mut timings = cell.get();
+        timings.run = Some(timings.run.unwrap_or_default() + elapsed);
+        cell.set(timings);
+    });
+    result
+}
+
+/// Avisos que dejó una compilación correcta, sin la ruta temporal del fichero
+/// para que se lean como `main.c:3:9: warning: ...`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn compiler_warnings(stderr: &[u8], source_path: &std::path::Path) -> String {
+    let text = String::from_utf8_lossy(stderr);
+    let text = match source_path.parent() {
+        Some(dir) => text.replace(
+            &format!("{}{}", dir.display(), std::path::MAIN_SEPARATOR),
+            "",
+        ),
+        None => text.into_owned(),
+    };
+    text.trim().to_string()
+}
+
+/// Los avisos se guardan junto al artefacto cacheado para poder mostrarlos
+/// también cuando no hace falta recompilar.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn store_warnings(path: &std::path::Path, warnings: &str) -> Result<(), String> {
+    std::fs::write(path, warnings)
+        .map_err(|err| format!("No se pudieron guardar los avisos: {err}"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn cached_warnings(path: &std::path::Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_default()
+}
+
+/// Adjunta `warnings` al veredicto si es `Accepted`; el resto no los muestra.
+pub fn with_warnings(result: JudgeResult, warnings: String) -> JudgeResult {
+    match result {
+        JudgeResult::Accepted { .. } => JudgeResult::Accepted { warnings },
+        other => other,
+    }
+}
+
+/// Salida del compilador con las ubicaciones de `compiled` reescritas a líneas del
+/// código del alumno (ver [`crate::diagnostics::relocate`]).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn user_diagnostics(
+    text: &str,
+    language: crate::model::Language,
+    compiled: &std::path::Path,
+    source: &str,
+    user_code: &str,
+) -> String {
+    let file = compiled
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    crate::diagnostics::relocate(text, language, &file, source, user_code)
+}
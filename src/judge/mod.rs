@@ -1,6 +1,11 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod judge_auth;
 pub mod judge_c;
+pub mod judge_capabilities;
 pub mod judge_java;
 pub mod judge_kt;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod judge_metrics;
 
 pub mod judge_pseudo;
 pub mod judge_remote;
@@ -10,3 +15,5 @@ pub mod judge_python;
 pub mod judge_rust;
 
 pub mod judge_utils;
+
+pub mod protocol;
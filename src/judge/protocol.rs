@@ -0,0 +1,400 @@
+//! Tipos del protocolo HTTP entre el cliente (escritorio/web) y `summer_quiz_judge_server`.
+//!
+//! Reglas para evolucionarlo sin romper binarios antiguos:
+//! - los campos nuevos llevan `#[serde(default)]` y son opcionales;
+//! - nunca se renombra ni se elimina un campo o un `status` existente;
+//! - un `status` desconocido se deserializa como [`JudgeOutcome::Unknown`];
+//! - cualquier cambio incompatible sube [`PROTOCOL_VERSION`].
+
+use crate::judge::judge_c::JudgeResult;
+use crate::model::JudgeTestCase;
+use serde::{Deserialize, Serialize};
+
+/// Versión actual del protocolo. Las peticiones sin `protocol_version`
+/// (clientes anteriores a que existiera) se tratan como versión [`LEGACY_PROTOCOL_VERSION`].
+pub const PROTOCOL_VERSION: u32 = 1;
+pub const LEGACY_PROTOCOL_VERSION: u32 = 0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JudgeRequest {
+    #[serde(default)]
+    pub protocol_version: u32,
+    pub language: String,
+    pub source: String,
+    pub tests: Vec<JudgeTestCase>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub harness: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JudgeResponse {
+    #[serde(default)]
+    pub protocol_version: u32,
+    #[serde(flatten)]
+    pub outcome: JudgeOutcome,
+}
+
+/// Veredicto tal y como viaja por la red (`{"status": "...", ...}`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JudgeOutcome {
+    Accepted {
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        warnings: String,
+    },
+    CompileError {
+        stderr: String,
+    },
+    WrongAnswer {
+        test_index: usize,
+        input: String,
+        expected: String,
+        received: String,
+        diff: String,
+    },
+    Timeout {
+        test_index: usize,
+        input: String,
+        timeout_ms: u64,
+    },
+    RuntimeError {
+        test_index: usize,
+        input: String,
+        stderr: String,
+        exit_code: Option<i32>,
+    },
+    InfrastructureError {
+        message: String,
+    },
+    /// `status` añadido por un servidor más nuevo que este cliente.
+    #[serde(other)]
+    Unknown,
+}
+
+/// `POST /api/judge/batch`: varias evaluaciones en una sola petición.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JudgeBatchRequest {
+    #[serde(default)]
+    pub protocol_version: u32,
+    pub submissions: Vec<JudgeRequest>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JudgeBatchResponse {
+    #[serde(default)]
+    pub protocol_version: u32,
+    /// En el mismo orden que `submissions`.
+    pub results: Vec<JudgeBatchItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JudgeBatchItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question_id: Option<String>,
+    #[serde(flatten)]
+    pub outcome: JudgeOutcome,
+}
+
+impl JudgeRequest {
+    pub fn new(
+        language: &str,
+        source: &str,
+        tests: Vec<JudgeTestCase>,
+        harness: Option<String>,
+        question_id: Option<String>,
+    ) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            language: language.to_string(),
+            source: source.to_string(),
+            tests,
+            harness,
+            question_id,
+        }
+    }
+}
+
+impl JudgeResponse {
+    pub fn new(outcome: JudgeOutcome) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            outcome,
+        }
+    }
+}
+
+impl JudgeBatchRequest {
+    pub fn new(submissions: Vec<JudgeRequest>) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            submissions,
+        }
+    }
+}
+
+impl JudgeOutcome {
+    pub fn status(&self) -> &'static str {
+        match self {
+            JudgeOutcome::Accepted { .. } => "accepted",
+            JudgeOutcome::CompileError { .. } => "compile_error",
+            JudgeOutcome::WrongAnswer { .. } => "wrong_answer",
+            JudgeOutcome::Timeout { .. } => "timeout",
+            JudgeOutcome::RuntimeError { .. } => "runtime_error",
+            JudgeOutcome::InfrastructureError { .. } => "infrastructure_error",
+            JudgeOutcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<JudgeResult> for JudgeOutcome {
+    fn from(result: JudgeResult) -> Self {
+        match result {
+            JudgeResult::Accepted { warnings } => JudgeOutcome::Accepted { warnings },
+            JudgeResult::CompileError { stderr } => JudgeOutcome::CompileError { stderr },
+            JudgeResult::WrongAnswer {
+                test_index,
+                input,
+                expected,
+                received,
+                diff,
+            } => JudgeOutcome::WrongAnswer {
+                test_index,
+                input,
+                expected,
+                received,
+                diff,
+            },
+            JudgeResult::Timeout {
+                test_index,
+                input,
+                timeout_ms,
+            } => JudgeOutcome::Timeout {
+                test_index,
+                input,
+                timeout_ms,
+            },
+            JudgeResult::RuntimeError {
+                test_index,
+                input,
+                stderr,
+                exit_code,
+            } => JudgeOutcome::RuntimeError {
+                test_index,
+                input,
+                stderr,
+                exit_code,
+            },
+            // Los requisitos se comprueban en el cliente antes de enviar; el servidor
+            // nunca produce `RequirementViolation`.
+            JudgeResult::InfrastructureError { message }
+            | JudgeResult::RequirementViolation { message } => {
+                JudgeOutcome::InfrastructureError { message }
+            }
+        }
+    }
+}
+
+impl From<JudgeOutcome> for JudgeResult {
+    fn from(outcome: JudgeOutcome) -> Self {
+        match outcome {
+            JudgeOutcome::Accepted { warnings } => JudgeResult::Accepted { warnings },
+            JudgeOutcome::CompileError { stderr } => JudgeResult::CompileError { stderr },
+            JudgeOutcome::WrongAnswer {
+                test_index,
+                input,
+                expected,
+                received,
+                diff,
+            } => JudgeResult::WrongAnswer {
+                test_index,
+                input,
+                expected,
+                received,
+                diff,
+            },
+            JudgeOutcome::Timeout {
+                test_index,
+                input,
+                timeout_ms,
+            } => JudgeResult::Timeout {
+                test_index,
+                input,
+                timeout_ms,
+            },
+            JudgeOutcome::RuntimeError {
+                test_index,
+                input,
+                stderr,
+                exit_code,
+            } => JudgeResult::RuntimeError {
+                test_index,
+                input,
+                stderr,
+                exit_code,
+            },
+            JudgeOutcome::InfrastructureError { message } => {
+                JudgeResult::InfrastructureError { message }
+            }
+            JudgeOutcome::Unknown => JudgeResult::InfrastructureError {
+                message: "El judge devolvió un veredicto que esta versión no reconoce. \
+                          Actualiza summer_quiz."
+                    .into(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_outcomes() -> Vec<JudgeOutcome> {
+        vec![
+            JudgeOutcome::Accepted {
+                warnings: String::new(),
+            },
+            JudgeOutcome::Accepted {
+                warnings: "warning: unused variable 'x'".into(),
+            },
+            JudgeOutcome::CompileError {
+                stderr: "error: expected ';'".into(),
+            },
+            JudgeOutcome::WrongAnswer {
+                test_index: 2,
+                input: "3\n".into(),
+                expected: "6\n".into(),
+                received: "5\n".into(),
+                diff: "- 6\n+ 5".into(),
+            },
+            JudgeOutcome::Timeout {
+                test_index: 1,
+                input: String::new(),
+                timeout_ms: 2000,
+            },
+            JudgeOutcome::RuntimeError {
+                test_index: 1,
+                input: "0\n".into(),
+                stderr: "Floating point exception".into(),
+                exit_code: None,
+            },
+            JudgeOutcome::InfrastructureError {
+                message: "sin compilador".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn request_and_response_round_trip() {
+        let request = JudgeRequest::new(
+            "c",
+            "int main(void){return 0;}",
+            vec![JudgeTestCase {
+                input: "1\n".into(),
+                output: "1\n".into(),
+            }],
+            None,
+            Some("c-001".into()),
+        );
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(!json.contains("harness"));
+        assert_eq!(
+            serde_json::from_str::<JudgeRequest>(&json).unwrap(),
+            request
+        );
+
+        for outcome in sample_outcomes() {
+            let response = JudgeResponse::new(outcome);
+            let json = serde_json::to_string(&response).unwrap();
+            assert_eq!(
+                serde_json::from_str::<JudgeResponse>(&json).unwrap(),
+                response
+            );
+        }
+    }
+
+    #[test]
+    fn batch_round_trip_keeps_order_and_ids() {
+        let batch = JudgeBatchRequest::new(vec![
+            JudgeRequest::new("c", "a", vec![], None, Some("q1".into())),
+            JudgeRequest::new("python", "b", vec![], None, None),
+        ]);
+        let json = serde_json::to_string(&batch).unwrap();
+        assert_eq!(
+            serde_json::from_str::<JudgeBatchRequest>(&json).unwrap(),
+            batch
+        );
+
+        let response = JudgeBatchResponse {
+            protocol_version: PROTOCOL_VERSION,
+            results: sample_outcomes()
+                .into_iter()
+                .enumerate()
+                .map(|(i, outcome)| JudgeBatchItem {
+                    question_id: Some(format!("q{i}")),
+                    outcome,
+                })
+                .collect(),
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            serde_json::from_str::<JudgeBatchResponse>(&json).unwrap(),
+            response
+        );
+    }
+
+    #[test]
+    fn accepts_legacy_payloads_without_version() {
+        let request: JudgeRequest = serde_json::from_str(
+            r#"{"language":"c","source":"x","tests":[{"input":"","output":""}]}"#,
+        )
+        .unwrap();
+        assert_eq!(request.protocol_version, LEGACY_PROTOCOL_VERSION);
+        assert_eq!(request.harness, None);
+
+        let response: JudgeResponse =
+            serde_json::from_str(r#"{"status":"compile_error","stderr":"boom"}"#).unwrap();
+        assert_eq!(response.protocol_version, LEGACY_PROTOCOL_VERSION);
+        assert_eq!(
+            response.outcome,
+            JudgeOutcome::CompileError {
+                stderr: "boom".into()
+            }
+        );
+    }
+
+    #[test]
+    fn tolerates_fields_and_statuses_from_newer_servers() {
+        let response: JudgeResponse =
+            serde_json::from_str(r#"{"protocol_version":7,"status":"accepted","elapsed_ms":12}"#)
+                .unwrap();
+        assert_eq!(
+            response.outcome,
+            JudgeOutcome::Accepted {
+                warnings: String::new()
+            }
+        );
+
+        let response: JudgeResponse =
+            serde_json::from_str(r#"{"protocol_version":7,"status":"memory_limit","limit":64}"#)
+                .unwrap();
+        assert_eq!(response.outcome, JudgeOutcome::Unknown);
+        assert!(matches!(
+            JudgeResult::from(response.outcome),
+            JudgeResult::InfrastructureError { .. }
+        ));
+    }
+
+    #[test]
+    fn new_responses_stay_readable_by_old_clients() {
+        // Los clientes anteriores deserializan directamente el enum etiquetado por `status`.
+        for outcome in sample_outcomes() {
+            let json = serde_json::to_string(&JudgeResponse::new(outcome.clone())).unwrap();
+            assert_eq!(
+                serde_json::from_str::<JudgeOutcome>(&json).unwrap(),
+                outcome
+            );
+        }
+    }
+}
@@ -0,0 +1,474 @@
+//! Lexer sencillo por lenguaje para comparar código sin depender de espacios ni
+//! comentarios: los literales (cadenas y caracteres) se conservan tal cual.
+//!
+//! No valida el programa; solo lo parte en tokens de forma estable, así que dos
+//! códigos con los mismos tokens se consideran iguales.
+
+use crate::model::Language;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Ident,
+    Number,
+    /// Cadena o carácter, con sus comillas (y prefijos como `r#` en Rust).
+    Literal,
+    Punct,
+    /// Fin de línea lógica (solo Python).
+    Newline,
+    /// Cambios de sangría (solo Python).
+    Indent,
+    Dedent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+}
+
+impl Token {
+    fn new(kind: TokenKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// Operadores de varios caracteres, de más largo a más corto (se toma el más largo).
+const C_LIKE_OPERATORS: &[&str] = &[
+    ">>>=", "<<=", ">>=", ">>>", "===", "!==", "..=", "...", "->", "=>", "::", "++", "--", "+=",
+    "-=", "*=", "/=", "%=", "&=", "|=", "^=", "==", "!=", "<=", ">=", "&&", "||", "<<", ">>", "?.",
+    "?:", "!!", "..",
+];
+const PYTHON_OPERATORS: &[&str] = &[
+    "**=", "//=", ">>=", "<<=", "...", "->", ":=", "**", "//", "+=", "-=", "*=", "/=", "%=", "&=",
+    "|=", "^=", "@=", "==", "!=", "<=", ">=", "<<", ">>",
+];
+const PSEUDO_OPERATORS: &[&str] = &[":=", "<=", ">=", "<>", "==", "!=", "&&", "||", ".."];
+
+pub fn tokenize(language: Language, code: &str) -> Vec<Token> {
+    Lexer::new(language, code).run()
+}
+
+struct Lexer {
+    language: Language,
+    chars: Vec<char>,
+    pos: usize,
+    tokens: Vec<Token>,
+    /// Profundidad de paréntesis/corchetes/llaves (en Python, dentro no cuentan los saltos).
+    depth: usize,
+    indents: Vec<usize>,
+    at_line_start: bool,
+}
+
+impl Lexer {
+    fn new(language: Language, code: &str) -> Self {
+        Self {
+            language,
+            chars: code.replace("\r\n", "\n").chars().collect(),
+            pos: 0,
+            tokens: Vec::new(),
+            depth: 0,
+            indents: vec![0],
+            at_line_start: true,
+        }
+    }
+
+    fn is_python(&self) -> bool {
+        self.language == Language::Python
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek(i) == Some(c))
+    }
+
+    fn slice(&self, start: usize) -> String {
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn run(mut self) -> Vec<Token> {
+        while self.pos < self.chars.len() {
+            if self.is_python() && self.at_line_start && self.depth == 0 {
+                self.indentation();
+                continue;
+            }
+            let c = self.chars[self.pos];
+            if c == '\n' {
+                self.pos += 1;
+                if self.is_python() && self.depth == 0 {
+                    self.newline();
+                    self.at_line_start = true;
+                }
+            } else if c.is_whitespace() {
+                self.pos += 1;
+            } else if self.is_python() && c == '\\' && self.peek(1) == Some('\n') {
+                self.pos += 2;
+            } else if self.comment() {
+            } else if let Some(literal) = self.literal() {
+                self.tokens.push(Token::new(TokenKind::Literal, literal));
+            } else if c.is_ascii_digit()
+                || (c == '.' && self.peek(1).is_some_and(|n| n.is_ascii_digit()))
+            {
+                let number = self.number();
+                self.tokens.push(Token::new(TokenKind::Number, number));
+            } else if c.is_alphabetic() || c == '_' {
+                let start = self.pos;
+                while self
+                    .peek(0)
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_')
+                {
+                    self.pos += 1;
+                }
+                let ident = self.slice(start);
+                self.tokens.push(Token::new(TokenKind::Ident, ident));
+            } else {
+                let op = self.operator();
+                match op.as_str() {
+                    "(" | "[" | "{" => self.depth += 1,
+                    ")" | "]" | "}" => self.depth = self.depth.saturating_sub(1),
+                    _ => {}
+                }
+                self.tokens.push(Token::new(TokenKind::Punct, op));
+            }
+        }
+
+        if self.is_python() {
+            self.newline();
+            while self.indents.len() > 1 {
+                self.indents.pop();
+                self.tokens.push(Token::new(TokenKind::Dedent, ""));
+            }
+        }
+        self.tokens
+    }
+
+    /// Python: mide la sangría de una línea con contenido y emite `Indent`/`Dedent`.
+    fn indentation(&mut self) {
+        let mut width = 0;
+        let mut i = self.pos;
+        while let Some(&c) = self.chars.get(i) {
+            match c {
+                ' ' => width += 1,
+                '\t' => width = (width / 8 + 1) * 8,
+                _ => break,
+            }
+            i += 1;
+        }
+        // Las líneas vacías o solo con comentario no cambian la sangría.
+        if matches!(self.chars.get(i), None | Some('\n') | Some('#')) {
+            while self.chars.get(i).is_some_and(|&c| c != '\n') {
+                i += 1;
+            }
+            self.pos = (i + 1).min(self.chars.len());
+            return;
+        }
+
+        self.pos = i;
+        self.at_line_start = false;
+        let current = *self.indents.last().unwrap_or(&0);
+        if width > current {
+            self.indents.push(width);
+            self.tokens.push(Token::new(TokenKind::Indent, ""));
+        } else {
+            while width < *self.indents.last().unwrap_or(&0) {
+                self.indents.pop();
+                self.tokens.push(Token::new(TokenKind::Dedent, ""));
+            }
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.tokens.last().is_some_and(|t| {
+            !matches!(
+                t.kind,
+                TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent
+            )
+        }) {
+            self.tokens.push(Token::new(TokenKind::Newline, ""));
+        }
+    }
+
+    /// Salta un comentario si empieza aquí.
+    fn comment(&mut self) -> bool {
+        if self.is_python() {
+            if self.peek(0) == Some('#') {
+                while self.peek(0).is_some_and(|c| c != '\n') {
+                    self.pos += 1;
+                }
+                return true;
+            }
+            return false;
+        }
+
+        if self.starts_with("//") {
+            while self.peek(0).is_some_and(|c| c != '\n') {
+                self.pos += 1;
+            }
+            return true;
+        }
+        if self.starts_with("/*") {
+            let nested = matches!(self.language, Language::Kotlin | Language::Rust);
+            let mut level = 0;
+            while self.pos < self.chars.len() {
+                if self.starts_with("/*") && (nested || level == 0) {
+                    level += 1;
+                    self.pos += 2;
+                } else if self.starts_with("*/") {
+                    level -= 1;
+                    self.pos += 2;
+                    if level == 0 {
+                        break;
+                    }
+                } else {
+                    self.pos += 1;
+                }
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Lee una cadena o carácter si empieza aquí (incluidos prefijos de Python y Rust).
+    fn literal(&mut self) -> Option<String> {
+        let start = self.pos;
+        let c = self.peek(0)?;
+
+        if self.language == Language::Rust {
+            if let Some(raw) = self.rust_raw_string() {
+                return Some(raw);
+            }
+            // `'a` sin cierre es un lifetime, no un carácter.
+            if c == '\''
+                && self.peek(1).is_some_and(|n| n.is_alphabetic() || n == '_')
+                && self.peek(2) != Some('\'')
+            {
+                return None;
+            }
+            if c == 'b' && matches!(self.peek(1), Some('"' | '\'')) {
+                self.pos += 1;
+                self.quoted(self.peek(0)?);
+                return Some(self.slice(start));
+            }
+        }
+
+        if self.is_python() {
+            let prefix_len = (0..3)
+                .take_while(|&i| self.peek(i).is_some_and(|p| "rRbBfFuU".contains(p)))
+                .count();
+            if prefix_len > 0 && matches!(self.peek(prefix_len), Some('"' | '\'')) {
+                self.pos += prefix_len;
+            }
+        }
+
+        let quote = self.peek(0)?;
+        let triple_quotes = match self.language {
+            Language::Python => quote == '"' || quote == '\'',
+            Language::Kotlin | Language::Java => quote == '"',
+            _ => false,
+        };
+        if triple_quotes && self.peek(1) == Some(quote) && self.peek(2) == Some(quote) {
+            let delimiter: String = [quote; 3].iter().collect();
+            self.pos += 3;
+            while self.pos < self.chars.len() && !self.starts_with(&delimiter) {
+                self.pos += if self.peek(0) == Some('\\') { 2 } else { 1 };
+            }
+            self.pos = (self.pos + 3).min(self.chars.len());
+            return Some(self.slice(start));
+        }
+
+        if quote == '"' || quote == '\'' {
+            self.quoted(quote);
+            return Some(self.slice(start));
+        }
+        self.pos = start;
+        None
+    }
+
+    /// Avanza sobre `"..."`/`'...'` respetando los escapes; un salto de línea sin cerrar termina el literal.
+    fn quoted(&mut self, quote: char) {
+        self.pos += 1;
+        while let Some(c) = self.peek(0) {
+            self.pos += 1;
+            match c {
+                '\\' => self.pos = (self.pos + 1).min(self.chars.len()),
+                '\n' => break,
+                c if c == quote => break,
+                _ => {}
+            }
+        }
+    }
+
+    /// `r"..."`, `r#"..."#`, `br"..."`.
+    fn rust_raw_string(&mut self) -> Option<String> {
+        let start = self.pos;
+        let mut i = 0;
+        if self.peek(i) == Some('b') {
+            i += 1;
+        }
+        if self.peek(i) != Some('r') {
+            return None;
+        }
+        i += 1;
+        let hashes = (i..).take_while(|&j| self.peek(j) == Some('#')).count();
+        if self.peek(i + hashes) != Some('"') {
+            return None;
+        }
+        self.pos += i + hashes + 1;
+        let closing: String = std::iter::once('"')
+            .chain(std::iter::repeat_n('#', hashes))
+            .collect();
+        while self.pos < self.chars.len() && !self.starts_with(&closing) {
+            self.pos += 1;
+        }
+        self.pos = (self.pos + closing.len()).min(self.chars.len());
+        Some(self.slice(start))
+    }
+
+    fn number(&mut self) -> String {
+        let start = self.pos;
+        while let Some(c) = self.peek(0) {
+            let exponent_sign = matches!(c, '+' | '-')
+                && self.pos > start
+                && matches!(self.chars[self.pos - 1], 'e' | 'E')
+                && !self.slice(start).starts_with("0x");
+            // `1..5` es un rango, no un número con punto.
+            let decimal_point = c == '.' && self.peek(1) != Some('.');
+            if c.is_alphanumeric() || c == '_' || decimal_point || exponent_sign {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        self.slice(start)
+    }
+
+    fn operator(&mut self) -> String {
+        let operators = match self.language {
+            Language::Python => PYTHON_OPERATORS,
+            Language::Pseudocode => PSEUDO_OPERATORS,
+            _ => C_LIKE_OPERATORS,
+        };
+        let op = operators
+            .iter()
+            .find(|op| self.starts_with(op))
+            .map(|op| op.to_string())
+            .unwrap_or_else(|| self.chars[self.pos].to_string());
+        self.pos += op.chars().count();
+        op
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(language: Language, code: &str) -> Vec<String> {
+        tokenize(language, code)
+            .into_iter()
+            .map(|t| match t.kind {
+                TokenKind::Newline => "⏎".into(),
+                TokenKind::Indent => "→".into(),
+                TokenKind::Dedent => "←".into(),
+                _ => t.text,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_literals_and_drops_comments() {
+        assert_eq!(
+            texts(
+                Language::C,
+                "printf(\"http://x /* y */\"); // fin\n/* a */ x+=1;"
+            ),
+            [
+                "printf",
+                "(",
+                "\"http://x /* y */\"",
+                ")",
+                ";",
+                "x",
+                "+=",
+                "1",
+                ";"
+            ]
+        );
+        assert_ne!(
+            tokenize(Language::C, "printf(\"a b\");"),
+            tokenize(Language::C, "printf(\"ab\");")
+        );
+        assert_eq!(
+            tokenize(Language::C, "int  x=1 ;"),
+            tokenize(Language::C, "int x = 1;")
+        );
+        assert_ne!(
+            tokenize(Language::C, "int x;"),
+            tokenize(Language::C, "intx;")
+        );
+        assert_eq!(
+            texts(
+                Language::Kotlin,
+                "/* a /* b */ c */ val s = \"\"\"x // y\"\"\""
+            ),
+            ["val", "s", "=", "\"\"\"x // y\"\"\""]
+        );
+        assert_eq!(
+            texts(Language::Rust, "fn f<'a>(s: &'a str) { r#\"a\"b\"#; 'c'; }"),
+            [
+                "fn",
+                "f",
+                "<",
+                "'",
+                "a",
+                ">",
+                "(",
+                "s",
+                ":",
+                "&",
+                "'",
+                "a",
+                "str",
+                ")",
+                "{",
+                "r#\"a\"b\"#",
+                ";",
+                "'c'",
+                ";",
+                "}"
+            ]
+        );
+        assert_eq!(
+            texts(Language::Pseudocode, "x := \"a // b\"; // c"),
+            ["x", ":=", "\"a // b\"", ";"]
+        );
+    }
+
+    #[test]
+    fn python_comments_and_indentation() {
+        assert_eq!(
+            texts(
+                Language::Python,
+                "if x:  # c\n\n    y = 7 // 2\nprint(f'#{y}')"
+            ),
+            [
+                "if", "x", ":", "⏎", "→", "y", "=", "7", "//", "2", "⏎", "←", "print", "(",
+                "f'#{y}'", ")", "⏎"
+            ]
+        );
+        assert_ne!(
+            tokenize(Language::Python, "if a:\n  b\nc"),
+            tokenize(Language::Python, "if a:\n  b\n  c")
+        );
+        assert_eq!(
+            tokenize(Language::Python, "f(1,\n      2)"),
+            tokenize(Language::Python, "f(1, 2)")
+        );
+    }
+}
@@ -1,8 +1,23 @@
 pub mod app;
+pub mod bank_layout;
+pub mod blanks;
+pub mod c_ast;
 pub mod code_utils;
 pub mod data;
+pub mod diagnostics;
+pub mod equivalence;
+pub mod exam;
+pub mod history;
 pub mod judge;
+pub mod lexer;
 pub mod model;
+pub mod parsons;
+pub mod predict;
+pub mod requirements;
+pub mod review;
+pub mod shuffle;
+pub mod structural;
+pub mod token_diff;
 pub mod ui;
 pub mod update;
 pub mod view_models;
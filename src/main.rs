@@ -1,9 +1,8 @@
 use summer_quiz::app::QuizApp;
+use summer_quiz::model::AppState;
 
 #[cfg(not(target_arch = "wasm32"))]
 use egui::Visuals;
-#[cfg(not(target_arch = "wasm32"))]
-use summer_quiz::model::AppState;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
@@ -22,6 +21,9 @@ fn main() -> eframe::Result<()> {
         "summer_quiz - Telegram: @sugarRayL",
         options,
         Box::new(|cc| {
+            // Imágenes de los enunciados y la teoría (markdown)
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+
             // Detectar tema preferido del sistema
             let prefers_dark = cc.egui_ctx.style().visuals.dark_mode;
             if prefers_dark {
@@ -88,11 +90,19 @@ fn main() {
                 canvas,
                 web_options,
                 Box::new(|cc| {
-                    let app: QuizApp = if let Some(storage) = cc.storage {
-                        eframe::get_value::<QuizApp>(storage, eframe::APP_KEY)
-                            .unwrap_or_else(QuizApp::new)
-                    } else {
-                        QuizApp::new()
+                    egui_extras::install_image_loaders(&cc.egui_ctx);
+                    // El banco no se guarda: como en escritorio, se vuelve a elegir
+                    // lenguaje, que lo carga (cuando terminen de descargarse los externos).
+                    let app: QuizApp = match cc
+                        .storage
+                        .and_then(|storage| eframe::get_value::<QuizApp>(storage, eframe::APP_KEY))
+                    {
+                        Some(mut state) => {
+                            state.state = AppState::LanguageSelect;
+                            state.has_saved_progress = true;
+                            state
+                        }
+                        None => QuizApp::new(),
                     };
                     Ok(Box::new(app))
                 }),
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Ord, PartialOrd,
+)]
 pub enum Language {
+    #[default]
     C,
     Pseudocode,
     Kotlin,
@@ -31,12 +35,102 @@ pub struct JudgeTestCase {
     pub output: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Tipo de pregunta. Las de opciones se corrigen en el cliente, sin `mode` ni tests.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionKind {
+    /// Código libre, corregido según `mode`.
+    #[default]
+    Code,
+    /// Exactamente una opción correcta.
+    SingleChoice,
+    /// Hay que marcar todas las correctas y ninguna incorrecta.
+    MultipleChoice,
+    /// `answer: verdadero|falso`, o dos `options` si se quiere feedback por opción.
+    TrueFalse,
+    /// `template` de solo lectura con huecos `{{nombre}}` que se corrigen uno a uno (`blanks`).
+    FillBlanks,
+    /// Se escribe lo que imprime `snippet`: `expected_output` o, con `compute_output`,
+    /// lo que da el juez del lenguaje.
+    PredictOutput,
+    /// Ordenar `lines` barajadas (con `distractors` que sobran); ver [`crate::parsons`].
+    Parsons,
+}
+
+/// Cómo se compara un hueco con su `answer`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BlankCheck {
+    /// Mismos tokens que `answer` ([`same_code`](crate::code_utils::same_code)): da igual
+    /// el espaciado y los comentarios, no el contenido de las cadenas.
+    #[default]
+    Normalize,
+    /// Igual salvo espacios al principio y al final.
+    Exact,
+    /// `answer` es una expresión regular que debe cubrir todo el hueco.
+    Regex,
+}
+
+/// Regla de equivalencia de las preguntas `normalize`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Equivalence {
+    /// `a == b` equivale a `b == a` (también `+`, `*`, `!=`, `&&`, `||`...) con operandos simples.
+    Commutative,
+    /// Las llaves de un `if`/`else`/`for`/`while` de una sola sentencia son opcionales.
+    OptionalBraces,
+    /// `i++`, `++i`, `i += 1` e `i = i + 1` son la misma sentencia (igual con `-`).
+    Increment,
+    /// Solo en C: las variables locales y los parámetros pueden llamarse de otra forma
+    /// (ver [`crate::structural`]).
+    Renaming,
+}
+
+/// Entrada de `forbids`: prohibida siempre o a partir de `max` usos.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Forbidden {
+    Always(String),
+    Limited { name: String, max: usize },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Blank {
+    pub name: String,
+    pub answer: String,
+    #[serde(default)]
+    pub check: BlankCheck,
+    /// Lo que se enseña como solución si no es `answer` (p. ej. con `regex`).
+    #[serde(default)]
+    pub solution: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceOption {
+    pub text: String,
+    #[serde(default)]
+    pub correct: bool,
+    /// Se muestra al enviar con esta opción marcada.
+    #[serde(default)]
+    pub feedback: Option<String>,
+}
+
+/// Resultado de corregir una pregunta de opciones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceGrade {
+    pub correct: bool,
+    /// Feedback de las opciones marcadas, en orden.
+    pub feedback: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Question {
     pub language: Language,
     #[serde(alias = "week")]
     pub module: usize,
     pub prompt: String,
+    /// Solución de referencia (vacía en las preguntas de opciones, salvo verdadero/falso).
+    #[serde(default)]
     pub answer: String,
     pub hint: Option<String>,
     #[serde(skip)]
@@ -61,14 +155,58 @@ pub struct Question {
     pub fails: u32,
     #[serde(default)]
     pub skips: u32,
+    /// Identificador estable: el progreso del alumno se guarda por `id`.
+    pub id: String,
+    /// `id`s anteriores de esta pregunta (renombrada, o una de las resultantes
+    /// de dividir otra). Quien completó cualquiera de ellos la tiene completada.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub kind: QuestionKind,
+    /// Opciones de las preguntas `single_choice`, `multiple_choice` y `true_false`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<ChoiceOption>,
+    /// Código de las preguntas `fill_blanks`, con huecos `{{nombre}}`.
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blanks: Vec<Blank>,
+    /// Código de solo lectura de las preguntas `predict_output`.
+    #[serde(default)]
+    pub snippet: Option<String>,
+    #[serde(default)]
+    pub expected_output: Option<String>,
+    /// Calcula la salida ejecutando `snippet` con el juez en lugar de `expected_output`.
+    #[serde(default)]
+    pub compute_output: bool,
+    /// Solución de los problemas `parsons`, línea a línea y en orden.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lines: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub distractors: Vec<String>,
+    /// En `parsons`, el alumno también elige la sangría de cada línea.
+    #[serde(default)]
+    pub indent: bool,
+    /// Otras soluciones válidas en modo `normalize`, además de `answer`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub answers: Vec<String>,
+    /// Diferencias que no cuentan al comparar en modo `normalize`; ver [`crate::equivalence`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equivalences: Vec<Equivalence>,
+    /// Construcciones o llamadas que el código debe usar (`for`, `scanf`...); ver [`crate::requirements`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbids: Vec<Forbidden>,
+    /// Máximo de líneas no vacías de la respuesta.
     #[serde(default)]
-    pub id: Option<String>,
+    pub max_lines: Option<usize>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Level {
     pub number: usize,
-    pub explanation: std::collections::HashMap<Language, String>,
+    pub explanation: HashMap<Language, String>,
     pub questions: Vec<Question>,
 }
 
@@ -79,10 +217,13 @@ pub struct Module {
     pub levels: Vec<Level>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Quiz {
     #[serde(alias = "weeks")]
     pub modules: Vec<Module>,
+    /// Preguntas retiradas del banco -> `id`s que heredan su progreso.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub replaced_by: HashMap<String, Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -96,6 +237,10 @@ pub enum AppState {
     Quiz,
     LevelSummary,
     Summary,
+    BankError,
+    ExamSetup,
+    Exam,
+    ExamReport,
 }
 
 impl Default for AppState {
@@ -104,6 +249,56 @@ impl Default for AppState {
     }
 }
 
+impl Module {
+    /// Clave estable del módulo para el progreso (no depende de su posición en el banco).
+    pub fn key(&self) -> String {
+        module_key(self.number)
+    }
+
+    /// Clave estable de un nivel para el progreso (no depende de su posición en el banco).
+    pub fn level_key(&self, level: &Level) -> String {
+        level_key(self.number, level.number)
+    }
+}
+
+pub fn module_key(module_number: usize) -> String {
+    format!("m{module_number}")
+}
+
+pub fn level_key(module_number: usize, level_number: usize) -> String {
+    format!("m{module_number}-l{level_number}")
+}
+
+impl Quiz {
+    /// Añade a `completed` las preguntas que heredan el progreso de `id`s
+    /// renombrados (`aliases`) o retirados (`replaced_by`). No borra ningún `id`:
+    /// si una pregunta vuelve al banco, su progreso sigue ahí.
+    pub fn migrate_completed(&self, completed: &mut HashSet<String>) {
+        loop {
+            let mut inherited = Vec::new();
+            for (old, new_ids) in &self.replaced_by {
+                if completed.contains(old) {
+                    inherited.extend(new_ids.iter().filter(|id| !completed.contains(*id)));
+                }
+            }
+            for q in self
+                .modules
+                .iter()
+                .flat_map(|m| &m.levels)
+                .flat_map(|l| &l.questions)
+            {
+                if !completed.contains(&q.id) && q.aliases.iter().any(|a| completed.contains(a)) {
+                    inherited.push(&q.id);
+                }
+            }
+            if inherited.is_empty() {
+                return;
+            }
+            completed.extend(inherited.into_iter().cloned());
+        }
+    }
+}
+
 impl Question {
     pub fn uses_judge_c(&self) -> bool {
         matches!(self.mode, Some(GradingMode::JudgeC))
@@ -118,6 +313,82 @@ impl Question {
         matches!(self.mode, Some(GradingMode::JudgeRemote)) && !self.tests.is_empty()
     }
 
+    pub fn is_choice(&self) -> bool {
+        matches!(
+            self.kind,
+            QuestionKind::SingleChoice | QuestionKind::MultipleChoice | QuestionKind::TrueFalse
+        )
+    }
+
+    /// Opciones a mostrar; un verdadero/falso sin `options` se construye desde `answer`.
+    pub fn choice_options(&self) -> Vec<ChoiceOption> {
+        if self.kind != QuestionKind::TrueFalse || !self.options.is_empty() {
+            return self.options.clone();
+        }
+        let is_true = matches!(
+            self.answer.trim().to_lowercase().as_str(),
+            "verdadero" | "true" | "v"
+        );
+        ["Verdadero", "Falso"]
+            .into_iter()
+            .zip([is_true, !is_true])
+            .map(|(text, correct)| ChoiceOption {
+                text: text.to_string(),
+                correct,
+                feedback: None,
+            })
+            .collect()
+    }
+
+    /// Correcta si las opciones marcadas son justo las correctas.
+    pub fn grade_choice(&self, selected: &[usize]) -> ChoiceGrade {
+        let options = self.choice_options();
+        let correct: HashSet<usize> = (0..options.len()).filter(|&i| options[i].correct).collect();
+        let marked: HashSet<usize> = selected.iter().copied().collect();
+        let mut marked_in_order: Vec<usize> = marked.iter().copied().collect();
+        marked_in_order.sort_unstable();
+        ChoiceGrade {
+            correct: !correct.is_empty() && correct == marked,
+            feedback: marked_in_order
+                .into_iter()
+                .filter_map(|i| options.get(i)?.feedback.clone())
+                .collect(),
+        }
+    }
+
+    /// Texto de la solución de una pregunta de opciones.
+    pub fn choice_solution(&self) -> String {
+        self.choice_options()
+            .iter()
+            .filter(|o| o.correct)
+            .map(|o| format!("✔ {}", o.text))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Problemas de definición de una pregunta de opciones (para `summer_quiz_bank`).
+    pub fn choice_problem(&self) -> Option<String> {
+        let options = self.choice_options();
+        let correct = options.iter().filter(|o| o.correct).count();
+        match self.kind {
+            QuestionKind::Code
+            | QuestionKind::FillBlanks
+            | QuestionKind::PredictOutput
+            | QuestionKind::Parsons => None,
+            _ if options.len() < 2 => Some("necesita al menos dos opciones".into()),
+            QuestionKind::SingleChoice | QuestionKind::TrueFalse if correct != 1 => {
+                Some(format!("debe tener una opción correcta y tiene {correct}"))
+            }
+            QuestionKind::MultipleChoice if correct == 0 => {
+                Some("no tiene ninguna opción correcta".into())
+            }
+            QuestionKind::TrueFalse if options.len() != 2 => {
+                Some("un verdadero/falso tiene exactamente dos opciones".into())
+            }
+            _ => None,
+        }
+    }
+
     pub fn reset_stats(&mut self) {
         self.is_done = false;
         self.attempts = 0;
@@ -126,7 +397,7 @@ impl Question {
         self.saw_solution = false;
     }
 
-    pub fn mark_done_test(&mut self) -> Option<String> {
+    pub fn mark_done_test(&mut self) -> String {
         self.is_done = true;
         self.saw_solution = false;
         self.attempts = 1;
@@ -135,3 +406,79 @@ impl Question {
         self.id.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completion_carries_over_aliases_and_replacements() {
+        let quiz: Quiz = serde_yaml::from_str(
+            r#"
+replaced_by:
+  c-old: [c-split-a, c-split-b]
+modules:
+- number: 1
+  explanation: M1
+  levels:
+  - number: 1
+    explanation: {}
+    questions:
+    - { id: c-renamed, aliases: [c-orig], language: C, module: 1, prompt: p, answer: a }
+    - { id: c-split-a, language: C, module: 1, prompt: p, answer: a }
+    - { id: c-split-b, language: C, module: 1, prompt: p, answer: a }
+    - { id: c-next, aliases: [c-split-b], language: C, module: 1, prompt: p, answer: a }
+"#,
+        )
+        .unwrap();
+
+        let mut completed: HashSet<String> = ["c-orig", "c-old", "c-gone"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        quiz.migrate_completed(&mut completed);
+
+        for id in ["c-renamed", "c-split-a", "c-split-b", "c-next", "c-gone"] {
+            assert!(completed.contains(id), "{id}");
+        }
+    }
+
+    #[test]
+    fn id_is_mandatory() {
+        let err =
+            serde_yaml::from_str::<Question>("{ language: C, module: 1, prompt: p, answer: a }")
+                .unwrap_err();
+        assert!(err.to_string().contains("id"), "{err}");
+    }
+
+    #[test]
+    fn grades_choice_questions() {
+        let multiple: Question = serde_yaml::from_str(
+            r#"
+id: c-ptr-mc
+language: C
+module: 1
+kind: multiple_choice
+prompt: ¿Qué declaraciones son punteros?
+options:
+- { text: "int *p;", correct: true }
+- { text: "int p;", feedback: "Es un entero." }
+- { text: "char *s;", correct: true }
+"#,
+        )
+        .unwrap();
+        assert!(multiple.grade_choice(&[2, 0]).correct);
+        let partial = multiple.grade_choice(&[0, 1]);
+        assert!(!partial.correct);
+        assert_eq!(partial.feedback, ["Es un entero."]);
+        assert_eq!(multiple.choice_problem(), None);
+
+        let true_false: Question = serde_yaml::from_str(
+            "{ id: c-tf, language: C, module: 1, kind: true_false, prompt: p, answer: falso }",
+        )
+        .unwrap();
+        assert!(true_false.grade_choice(&[1]).correct);
+        assert!(!true_false.grade_choice(&[0]).correct);
+        assert_eq!(true_false.choice_solution(), "✔ Falso");
+    }
+}
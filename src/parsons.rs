@@ -0,0 +1,162 @@
+//! Problemas de Parsons: ordenar las líneas barajadas de una solución.
+//!
+//! El banco da `lines` en el orden correcto y, opcionalmente, `distractors` que
+//! sobran. Con `indent: true` las líneas se muestran sin sangría y el alumno
+//! también tiene que elegirla. Si la pregunta tiene juez y tests, el programa
+//! montado se evalúa con él; si no, se compara el orden.
+
+use crate::model::{GradingMode, Question};
+use crate::shuffle::{seed_from, shuffle};
+use serde::{Deserialize, Serialize};
+
+/// Un nivel de sangría al montar el programa.
+pub const INDENT: &str = "    ";
+
+/// Línea colocada por el alumno: índice en [`blocks`] y nivel de sangría.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placed {
+    pub block: usize,
+    pub indent: usize,
+}
+
+/// Todas las líneas que se pueden colocar: primero las de la solución, luego los distractores.
+pub fn blocks(question: &Question) -> Vec<&str> {
+    question
+        .lines
+        .iter()
+        .chain(&question.distractors)
+        .map(String::as_str)
+        .collect()
+}
+
+/// Texto con el que se muestra un bloque (sin sangría si la elige el alumno).
+pub fn block_text(question: &Question, block: usize) -> &str {
+    let text = blocks(question).get(block).copied().unwrap_or_default();
+    if question.indent {
+        text.trim()
+    } else {
+        text.trim_end()
+    }
+}
+
+/// Nivel de sangría de una línea de la solución (un tabulador o cuatro espacios por nivel).
+pub fn indent_level(line: &str) -> usize {
+    let mut spaces = 0;
+    for c in line.chars() {
+        match c {
+            ' ' => spaces += 1,
+            '\t' => spaces += INDENT.len(),
+            _ => break,
+        }
+    }
+    spaces / INDENT.len()
+}
+
+/// Orden inicial de los bloques, barajado siempre igual para la misma pregunta.
+pub fn initial_pool(question: &Question) -> Vec<usize> {
+    let mut pool: Vec<usize> = (0..blocks(question).len()).collect();
+    shuffle(&mut pool, seed_from(&question.id));
+    pool
+}
+
+/// Programa formado por las líneas colocadas.
+pub fn assemble(question: &Question, answer: &[Placed]) -> String {
+    answer
+        .iter()
+        .map(|placed| {
+            let text = block_text(question, placed.block);
+            if question.indent {
+                format!("{}{text}", INDENT.repeat(placed.indent))
+            } else {
+                text.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn solution(question: &Question) -> String {
+    question.lines.join("\n")
+}
+
+/// Correcto si se colocaron exactamente las líneas de la solución, en orden
+/// (y con su sangría, si la elige el alumno). Las líneas repetidas son intercambiables.
+pub fn is_correct_order(question: &Question, answer: &[Placed]) -> bool {
+    answer.len() == question.lines.len()
+        && answer.iter().zip(&question.lines).all(|(placed, line)| {
+            block_text(question, placed.block).trim() == line.trim()
+                && (!question.indent || placed.indent == indent_level(line))
+        })
+}
+
+/// Se corrige ejecutando el programa montado en lugar de comparar el orden.
+pub fn uses_judge(question: &Question) -> bool {
+    !question.tests.is_empty()
+        && (question.uses_judge_c()
+            || question
+                .mode
+                .as_ref()
+                .is_some_and(|mode| *mode != GradingMode::Normalize))
+}
+
+/// Problemas de definición de un problema de Parsons (para `summer_quiz_bank`).
+pub fn parsons_problems(question: &Question) -> Vec<String> {
+    let mut problems = Vec::new();
+    if question.lines.len() < 2 {
+        problems.push("necesita al menos dos `lines`".into());
+    }
+    for distractor in &question.distractors {
+        if question
+            .lines
+            .iter()
+            .any(|line| line.trim() == distractor.trim())
+        {
+            problems.push(format!(
+                "el distractor `{}` es una línea de la solución",
+                distractor.trim()
+            ));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grades_order_and_indentation() {
+        let question: Question = serde_yaml::from_str(
+            r#"
+id: c-parsons
+language: C
+module: 1
+kind: parsons
+prompt: Ordena el bucle.
+indent: true
+lines:
+- "for (int i = 0; i < 3; i++) {"
+- "    printf(\"%d\", i);"
+- "}"
+distractors:
+- "for (int i = 0; i <= 3; i++) {"
+"#,
+        )
+        .unwrap();
+        assert!(parsons_problems(&question).is_empty());
+
+        let mut pool = initial_pool(&question);
+        assert_eq!(pool, initial_pool(&question));
+        pool.sort_unstable();
+        assert_eq!(pool, [0, 1, 2, 3]);
+
+        let placed = |block, indent| Placed { block, indent };
+        let flat = [placed(0, 0), placed(1, 0), placed(2, 0)];
+        assert!(!is_correct_order(&question, &flat));
+        let right = [placed(0, 0), placed(1, 1), placed(2, 0)];
+        assert!(is_correct_order(&question, &right));
+        assert_eq!(assemble(&question, &right), solution(&question));
+        let with_distractor = [placed(3, 0), placed(1, 1), placed(2, 0)];
+        assert!(!is_correct_order(&question, &with_distractor));
+    }
+}
@@ -0,0 +1,169 @@
+//! Preguntas `predict_output`: el alumno lee `snippet` y escribe lo que imprime.
+//!
+//! La salida correcta es `expected_output` o, con `compute_output: true`, la que
+//! da el juez del lenguaje al ejecutar el snippet. Para obtenerla se evalúa el
+//! snippet contra una salida imposible: el `WrongAnswer` resultante trae la salida real.
+
+use crate::judge::judge_c::JudgeResult;
+use crate::judge::judge_utils::normalize_newlines;
+use crate::model::{GradingMode, JudgeTestCase, Language, Question, QuestionKind};
+
+/// Salida esperada que ningún programa produce (ver el comentario del módulo).
+const NEVER_PRINTED: &str = "\u{1}";
+
+/// Juez con el que se ejecuta el snippet: el `mode` de la pregunta o el del lenguaje.
+pub fn judge_mode(question: &Question) -> GradingMode {
+    match &question.mode {
+        Some(mode) if *mode != GradingMode::Normalize => mode.clone(),
+        _ => match question.language {
+            Language::C | Language::GitGithub => GradingMode::JudgeC,
+            Language::Pseudocode => GradingMode::JudgePseudo,
+            Language::Kotlin => GradingMode::JudgeKotlin,
+            Language::Java => GradingMode::JudgeJava,
+            Language::Rust => GradingMode::JudgeRust,
+            Language::Python => GradingMode::JudgePython,
+        },
+    }
+}
+
+/// Pregunta de código equivalente para ejecutar el snippet con el juez, o `None`
+/// si la salida esperada es fija. Se envía `snippet` como código del alumno.
+pub fn judged_question(question: &Question) -> Option<Question> {
+    if question.kind != QuestionKind::PredictOutput || !question.compute_output {
+        return None;
+    }
+    let snippet = question.snippet.clone().unwrap_or_default();
+    Some(Question {
+        kind: QuestionKind::Code,
+        mode: Some(judge_mode(question)),
+        tests: vec![JudgeTestCase {
+            input: String::new(),
+            output: NEVER_PRINTED.into(),
+        }],
+        answer: snippet,
+        ..question.clone()
+    })
+}
+
+/// Compara la predicción con la salida real. A diferencia de los jueces no se
+/// acepta que la salida solo aparezca al final: la predicción debe ser exacta,
+/// salvo los espacios al final de cada línea y las líneas vacías del final.
+pub fn grade_prediction(prediction: &str, output: &str) -> JudgeResult {
+    if comparable(prediction) == comparable(output) {
+        JudgeResult::Accepted {
+            warnings: String::new(),
+        }
+    } else {
+        JudgeResult::WrongAnswer {
+            test_index: 0,
+            input: String::new(),
+            expected: String::new(),
+            received: String::new(),
+            diff: String::new(),
+        }
+    }
+}
+
+fn comparable(text: &str) -> String {
+    normalize_newlines(text)
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Salida real del snippet a partir del resultado del juez de [`judged_question`].
+/// Cualquier otro resultado (no compila, timeout...) es un fallo del banco y se devuelve tal cual.
+pub fn computed_output(result: JudgeResult) -> Result<String, JudgeResult> {
+    match result {
+        JudgeResult::WrongAnswer { received, .. } => Ok(received),
+        JudgeResult::Accepted { .. } => Err(JudgeResult::InfrastructureError {
+            message: "No se pudo obtener la salida del código.".into(),
+        }),
+        other => Err(other),
+    }
+}
+
+/// Problemas de definición de una pregunta `predict_output` (para `summer_quiz_bank`).
+pub fn prediction_problems(question: &Question) -> Vec<String> {
+    let mut problems = Vec::new();
+    if question
+        .snippet
+        .as_deref()
+        .is_none_or(|s| s.trim().is_empty())
+    {
+        problems.push("falta `snippet`".into());
+    }
+    match (&question.expected_output, question.compute_output) {
+        (Some(_), true) => {
+            problems.push("`expected_output` y `compute_output` son excluyentes".into())
+        }
+        (None, false) => problems.push("falta `expected_output` o `compute_output: true`".into()),
+        _ => {}
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(yaml: &str) -> Question {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn computes_output_with_the_language_judge() {
+        let q = question(
+            "{ id: c-p, language: C, module: 1, kind: predict_output, prompt: p, \
+             snippet: 'printf(\"%d\", 2 + 3);', compute_output: true }",
+        );
+        assert!(prediction_problems(&q).is_empty());
+        let judged = judged_question(&q).unwrap();
+        assert_eq!(judged.mode, Some(GradingMode::JudgeC));
+        assert_eq!(judged.answer, "printf(\"%d\", 2 + 3);");
+
+        let result = JudgeResult::WrongAnswer {
+            test_index: 0,
+            input: String::new(),
+            expected: NEVER_PRINTED.into(),
+            received: "5".into(),
+            diff: String::new(),
+        };
+        let output = computed_output(result).unwrap();
+        assert!(matches!(
+            grade_prediction("5\n", &output),
+            JudgeResult::Accepted { .. }
+        ));
+        assert!(matches!(
+            grade_prediction("6", &output),
+            JudgeResult::WrongAnswer { .. }
+        ));
+    }
+
+    #[test]
+    fn prediction_must_be_exact() {
+        let accepted = |prediction, output| {
+            matches!(
+                grade_prediction(prediction, output),
+                JudgeResult::Accepted { .. }
+            )
+        };
+        assert!(accepted("1 \r\n2\n\n", "1\n2\n"));
+        assert!(accepted("", ""));
+        assert!(!accepted("x = 5", "5"));
+        assert!(!accepted("1\n2\n3", "3"));
+    }
+
+    #[test]
+    fn fixed_output_needs_no_judge() {
+        let q = question(
+            "{ id: c-f, language: C, module: 1, kind: predict_output, prompt: p, \
+             snippet: x, expected_output: 'a' }",
+        );
+        assert!(judged_question(&q).is_none());
+        assert!(prediction_problems(&q).is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! Requisitos estáticos de una pregunta (`requires`, `forbids`, `max_lines`): se
+//! comprueban buscando tokens del lenguaje en el código, antes de ejecutar los tests.
+
+use crate::lexer::{Token, TokenKind, tokenize};
+use crate::model::{Forbidden, Language, Question};
+
+/// Tokens de `text` sin saltos de línea ni sangrías (en Python).
+fn code_tokens(language: Language, text: &str) -> Vec<Token> {
+    tokenize(language, text)
+        .into_iter()
+        .filter(|t| {
+            !matches!(
+                t.kind,
+                TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent
+            )
+        })
+        .collect()
+}
+
+/// Veces que aparece la secuencia de tokens de `pattern` (sin solaparse).
+fn occurrences(language: Language, code: &[Token], pattern: &str) -> usize {
+    let pattern = code_tokens(language, pattern);
+    if pattern.is_empty() {
+        return 0;
+    }
+    let mut count = 0;
+    let mut i = 0;
+    while i + pattern.len() <= code.len() {
+        if code[i..i + pattern.len()] == pattern[..] {
+            count += 1;
+            i += pattern.len();
+        } else {
+            i += 1;
+        }
+    }
+    count
+}
+
+fn times(n: usize) -> String {
+    if n == 1 {
+        "1 vez".into()
+    } else {
+        format!("{n} veces")
+    }
+}
+
+/// Requisitos que incumple `code`, uno por línea, o `None` si los cumple todos.
+pub fn violation(question: &Question, code: &str) -> Option<String> {
+    let tokens = code_tokens(question.language, code);
+    let mut broken = Vec::new();
+    for required in &question.requires {
+        if occurrences(question.language, &tokens, required) == 0 {
+            broken.push(format!("Debes usar `{}`.", required.trim()));
+        }
+    }
+    for forbidden in &question.forbids {
+        match forbidden {
+            Forbidden::Always(name) => {
+                if occurrences(question.language, &tokens, name) > 0 {
+                    broken.push(format!("No puedes usar `{}`.", name.trim()));
+                }
+            }
+            Forbidden::Limited { name, max } => {
+                let used = occurrences(question.language, &tokens, name);
+                if used > *max {
+                    broken.push(format!(
+                        "`{}` se puede usar como mucho {} (lo usas {}).",
+                        name.trim(),
+                        times(*max),
+                        times(used)
+                    ));
+                }
+            }
+        }
+    }
+    if let Some(max) = question.max_lines {
+        let lines = code.lines().filter(|line| !line.trim().is_empty()).count();
+        if lines > max {
+            broken.push(format!(
+                "La solución debe tener como mucho {max} líneas (tiene {lines})."
+            ));
+        }
+    }
+    (!broken.is_empty()).then(|| broken.join("\n"))
+}
+
+/// Problemas de definición de los requisitos (para `summer_quiz_bank`), incluido
+/// que la respuesta de referencia no los cumpla.
+pub fn requirement_problems(question: &Question) -> Vec<String> {
+    let mut problems = Vec::new();
+    let names = question
+        .requires
+        .iter()
+        .chain(question.forbids.iter().map(|forbidden| match forbidden {
+            Forbidden::Always(name) | Forbidden::Limited { name, .. } => name,
+        }));
+    for name in names {
+        if code_tokens(question.language, name).is_empty() {
+            problems.push(format!("requisito vacío: `{name}`"));
+        }
+    }
+    if question.max_lines == Some(0) {
+        problems.push("`max_lines` debe ser mayor que 0".into());
+    }
+    if !question.answer.trim().is_empty()
+        && let Some(broken) = violation(question, &question.answer)
+    {
+        problems.push(format!(
+            "la respuesta de referencia no los cumple: {}",
+            broken.replace('\n', " ")
+        ));
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_required_and_forbidden_tokens() {
+        let question: Question = serde_yaml::from_str(
+            r##"
+id: c-req
+language: C
+module: 1
+prompt: p
+answer: "for (int i = 0; i < 3; i++) printf(\"%d\", i);"
+requires: [for]
+forbids: [while, "#include <math.h>", { name: printf, max: 1 }]
+max_lines: 3
+"##,
+        )
+        .unwrap();
+        assert!(requirement_problems(&question).is_empty());
+        assert_eq!(
+            violation(
+                &question,
+                "#include <math.h>\nint i = 0;\n// for\nwhile (i < 3) { printf(\"%d\", i); printf(\"for\"); i++; }"
+            )
+            .unwrap(),
+            "Debes usar `for`.\nNo puedes usar `while`.\nNo puedes usar `#include <math.h>`.\n\
+             `printf` se puede usar como mucho 1 vez (lo usas 2 veces).\n\
+             La solución debe tener como mucho 3 líneas (tiene 4)."
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Repaso espaciado (SM-2) de las preguntas ya completadas: cada pregunta tiene
+//! una tarjeta con su facilidad, su intervalo y el día en que toca repasarla.
+
+use serde::{Deserialize, Serialize};
+
+/// Preguntas que se sirven como mucho en una sesión de repaso.
+pub const MAX_SESSION: usize = 20;
+
+const MIN_EASE: f32 = 1.3;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReviewCard {
+    pub ease: f32,
+    /// Días entre el último repaso y el siguiente.
+    pub interval: u32,
+    /// Repasos correctos seguidos.
+    pub repetitions: u32,
+    /// Día (desde el 1970-01-01) en que toca repasarla.
+    pub due: u64,
+}
+
+impl ReviewCard {
+    /// Tarjeta de una pregunta recién completada: se repasa al día siguiente.
+    pub fn new(today: u64) -> Self {
+        Self {
+            ease: 2.5,
+            interval: 1,
+            repetitions: 0,
+            due: today + 1,
+        }
+    }
+
+    pub fn is_due(&self, today: u64) -> bool {
+        self.due <= today
+    }
+
+    /// Actualiza la tarjeta según SM-2; `quality` va de 0 (no la recordaba) a 5.
+    pub fn review(&mut self, quality: u8, today: u64) {
+        let quality = quality.min(5);
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval = 1;
+        } else {
+            self.repetitions += 1;
+            self.interval = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval as f32 * self.ease).round() as u32,
+            };
+        }
+        let miss = f32::from(5 - quality);
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.due = today + u64::from(self.interval);
+    }
+}
+
+/// Calidad SM-2 de un repaso resuelto tras `fails` intentos fallidos; ver la
+/// solución cuenta como no recordarla.
+pub fn quality(fails: u32, saw_solution: bool) -> u8 {
+    match (saw_solution, fails) {
+        (true, _) => 1,
+        (false, 0) => 5,
+        (false, 1) => 3,
+        (false, _) => 2,
+    }
+}
+
+/// Día actual (UTC) contado desde el 1970-01-01.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn today() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn today() -> u64 {
+    (js_sys::Date::now() / 86_400_000.0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_grow_and_reset_on_lapse() {
+        let mut card = ReviewCard::new(100);
+        assert!(!card.is_due(100) && card.is_due(101));
+
+        card.review(quality(0, false), 101);
+        assert_eq!((card.interval, card.due), (1, 102));
+        card.review(quality(0, false), 102);
+        assert_eq!((card.interval, card.due), (6, 108));
+        card.review(quality(1, false), 108);
+        assert_eq!(card.interval, 16);
+        assert!(card.ease < 2.7);
+
+        card.review(quality(0, true), 125);
+        assert_eq!((card.repetitions, card.interval, card.due), (0, 1, 126));
+        assert!(card.ease >= MIN_EASE);
+    }
+}
@@ -0,0 +1,704 @@
+//! Corrección por estructura de las preguntas `normalize` de C: se comparan los
+//! árboles de [`crate::c_ast`] en lugar de los tokens, y se explica qué difiere.
+//!
+//! Las declaraciones seguidas (y los campos de un `struct`, las directivas, tipos
+//! y funciones) pueden ir en cualquier orden, `int a, b;` equivale a `int a; int b;`
+//! y las llaves de un bloque de una sola sentencia no cuentan. Con la regla
+//! `renaming`, las variables locales y los parámetros pueden llamarse de otra forma.
+
+use crate::c_ast::{Declaration, Expr, Function, Init, Item, Stmt, parse, show};
+use crate::equivalence::reference_answers;
+use crate::lexer::{Token, TokenKind};
+use crate::model::{Equivalence, Language, Question};
+use std::collections::HashMap;
+
+/// Diferencias de `code` con la respuesta de referencia más parecida (vacío si
+/// coincide con alguna), o `None` si la pregunta o `code` no se pueden analizar.
+/// Sin `reveal` los mensajes dicen qué difiere pero no el valor o tipo esperado.
+pub fn closest_differences(question: &Question, code: &str, reveal: bool) -> Option<Vec<String>> {
+    if question.language != Language::C {
+        return None;
+    }
+    let rules = &question.equivalences;
+    let references: Vec<Vec<Item>> = reference_answers(question)
+        .filter_map(|answer| parse(answer, rules).ok())
+        .collect();
+    if references.is_empty() {
+        return None;
+    }
+    let submitted = parse(code, rules).ok()?;
+    let renaming = rules.contains(&Equivalence::Renaming);
+    references
+        .iter()
+        .map(|reference| differences(reference, &submitted, renaming, reveal))
+        .min_by_key(Vec::len)
+}
+
+/// Qué le falta, le sobra o tiene distinto `submitted` respecto a `reference`.
+pub fn differences(
+    reference: &[Item],
+    submitted: &[Item],
+    renaming: bool,
+    reveal: bool,
+) -> Vec<String> {
+    let mut comparison = Comparison {
+        renaming,
+        reveal,
+        names: HashMap::new(),
+        out: Vec::new(),
+    };
+    comparison.items(reference, submitted);
+    comparison.out
+}
+
+struct Comparison {
+    renaming: bool,
+    /// ¿Se pueden enseñar los valores, tipos y nombres de la referencia?
+    reveal: bool,
+    /// Nombre en el código del alumno -> nombre en la referencia (con `renaming`).
+    names: HashMap<String, String>,
+    out: Vec<String>,
+}
+
+fn item_key(item: &Item) -> Option<String> {
+    Some(match item {
+        Item::Include(header) => format!("#include <{header}>"),
+        Item::Define { name, .. } => format!("#define {name}"),
+        Item::Directive(text) => text.clone(),
+        Item::Enum { name, .. } | Item::Struct { name, .. } => format!("tipo {name}"),
+        Item::Typedef(declaration) => format!("tipo {}", declaration.name),
+        Item::Function(function) if function.body.is_some() => format!("fn {}", function.name),
+        Item::Function(function) => format!("prototipo {}", function.name),
+        // Las variables globales van en orden con las sentencias sueltas.
+        Item::Decl(_) | Item::Stmt(_) => return None,
+    })
+}
+
+/// "falta ..." / "sobra ..." de un elemento de primer nivel.
+fn describe_item(item: &Item) -> String {
+    match item {
+        Item::Include(header) => format!("`#include <{header}>`"),
+        Item::Define { name, .. } => format!("`#define {name}`"),
+        Item::Directive(text) => format!("`{text}`"),
+        Item::Enum { name, .. } | Item::Struct { name, .. } => format!("el tipo `{name}`"),
+        Item::Typedef(declaration) => format!("el tipo `{}`", declaration.name),
+        Item::Function(function) if function.body.is_some() => {
+            format!("la función `{}`", function.name)
+        }
+        Item::Function(function) => format!("el prototipo de `{}`", function.name),
+        Item::Decl(declaration) => format!("la variable global `{}`", declaration.name),
+        Item::Stmt(_) => "una sentencia".into(),
+    }
+}
+
+fn describe_stmt(stmt: &Stmt) -> &'static str {
+    match stmt {
+        Stmt::Decl(_) => "una declaración",
+        Stmt::Expr(_) => "una sentencia",
+        Stmt::Block(_) => "un bloque",
+        Stmt::If { .. } => "un `if`",
+        Stmt::While { .. } => "un `while`",
+        Stmt::DoWhile { .. } => "un `do`-`while`",
+        Stmt::For { .. } => "un `for`",
+        Stmt::Switch { .. } => "un `switch`",
+        Stmt::Case(Some(_)) => "un `case`",
+        Stmt::Case(None) => "un `default`",
+        Stmt::Return(_) => "un `return`",
+        Stmt::Break => "un `break`",
+        Stmt::Continue => "un `continue`",
+    }
+}
+
+fn type_name(ty: &str, pointers: usize) -> String {
+    format!("{ty}{}", "*".repeat(pointers))
+}
+
+fn dims_text(dims: &[Expr]) -> String {
+    dims.iter().map(|dim| format!("[{}]", show(dim))).collect()
+}
+
+/// Cuerpo de un `if`, bucle... como lista de sentencias (las llaves no cuentan).
+fn as_block(stmt: &Stmt) -> &[Stmt] {
+    match stmt {
+        Stmt::Block(body) => body,
+        other => std::slice::from_ref(other),
+    }
+}
+
+/// Tramo de un bloque: declaraciones seguidas (en cualquier orden) u otra sentencia.
+enum Segment<'s> {
+    Decls(Vec<&'s Declaration>),
+    Stmt(&'s Stmt),
+}
+
+fn segments(block: &[Stmt]) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    for stmt in block {
+        match (stmt, segments.last_mut()) {
+            (Stmt::Decl(declaration), Some(Segment::Decls(run))) => run.push(declaration),
+            (Stmt::Decl(declaration), _) => segments.push(Segment::Decls(vec![declaration])),
+            (other, _) => segments.push(Segment::Stmt(other)),
+        }
+    }
+    segments
+}
+
+impl Comparison {
+    fn push(&mut self, context: &str, message: String) {
+        self.out.push(
+            if context.is_empty() {
+                let mut chars = message.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            } else {
+                format!("{context}: {message}")
+            } + ".",
+        );
+    }
+
+    /// Tokens del alumno con los nombres de la referencia (sin tocar campos: `a.x`, `p->x`).
+    fn renamed(&self, expr: &[Token]) -> Expr {
+        let mut out = expr.to_vec();
+        for i in 0..out.len() {
+            let field = i > 0 && matches!(out[i - 1].text.as_str(), "." | "->");
+            if out[i].kind == TokenKind::Ident
+                && !field
+                && let Some(name) = self.names.get(&out[i].text)
+            {
+                out[i].text = name.clone();
+            }
+        }
+        out
+    }
+
+    fn same_expr(&self, reference: &[Token], submitted: &[Token]) -> bool {
+        reference == self.renamed(submitted).as_slice()
+    }
+
+    fn same_init(&self, reference: &Init, submitted: &Init) -> bool {
+        match (reference, submitted) {
+            (Init::Expr(r), Init::Expr(s)) => self.same_expr(r, s),
+            (Init::List(r), Init::List(s)) => {
+                r.len() == s.len() && r.iter().zip(s).all(|(r, s)| self.same_init(r, s))
+            }
+            _ => false,
+        }
+    }
+
+    fn same_shape(&self, reference: &Declaration, submitted: &Declaration) -> bool {
+        reference.ty == submitted.ty
+            && reference.pointers == submitted.pointers
+            && reference.dims.len() == submitted.dims.len()
+            && reference
+                .dims
+                .iter()
+                .zip(&submitted.dims)
+                .all(|(r, s)| self.same_expr(r, s))
+            && match (&reference.init, &submitted.init) {
+                (Some(r), Some(s)) => self.same_init(r, s),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn items(&mut self, reference: &[Item], submitted: &[Item]) {
+        let keyed = |items: &[Item]| -> Vec<(String, usize)> {
+            items
+                .iter()
+                .enumerate()
+                .filter_map(|(i, item)| item_key(item).map(|key| (key, i)))
+                .collect()
+        };
+        let submitted_keys: HashMap<String, usize> = keyed(submitted).into_iter().collect();
+        let reference_keys: HashMap<String, usize> = keyed(reference).into_iter().collect();
+
+        for (key, i) in keyed(reference) {
+            match submitted_keys.get(&key) {
+                Some(&j) => self.item(&reference[i], &submitted[j]),
+                None => self.push("", format!("falta {}", describe_item(&reference[i]))),
+            }
+        }
+        for (key, j) in keyed(submitted) {
+            if !reference_keys.contains_key(&key) {
+                self.push("", format!("sobra {}", describe_item(&submitted[j])));
+            }
+        }
+
+        let loose = |items: &[Item]| -> Vec<Stmt> {
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    Item::Decl(declaration) => Some(Stmt::Decl(declaration.clone())),
+                    Item::Stmt(stmt) => Some(stmt.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        self.block(
+            "Fuera de las funciones",
+            &loose(reference),
+            &loose(submitted),
+        );
+    }
+
+    fn item(&mut self, reference: &Item, submitted: &Item) {
+        match (reference, submitted) {
+            (Item::Define { name, value }, Item::Define { value: other, .. }) if value != other => {
+                let message = if self.reveal {
+                    format!("`{name}` debería valer `{value}`")
+                } else {
+                    format!("el valor de `{name}` no es el esperado")
+                };
+                self.push("", message);
+            }
+            (Item::Enum { name, values }, Item::Enum { values: other, .. }) if values != other => {
+                self.push("", format!("los valores de `{name}` no son los esperados"));
+            }
+            (Item::Define { .. }, Item::Define { .. }) | (Item::Enum { .. }, Item::Enum { .. }) => {
+            }
+            (Item::Struct { name, fields }, Item::Struct { fields: other, .. }) => {
+                let context = format!("En `{name}`");
+                let reference: Vec<&Declaration> = fields.iter().collect();
+                let submitted: Vec<&Declaration> = other.iter().collect();
+                let renaming = std::mem::replace(&mut self.renaming, false);
+                self.run(
+                    &context,
+                    &reference,
+                    &submitted,
+                    ("falta el campo", "sobra el campo"),
+                );
+                self.renaming = renaming;
+            }
+            (Item::Typedef(r), Item::Typedef(s)) => self.declaration("", r, s),
+            (Item::Function(r), Item::Function(s)) => self.function(r, s),
+            (Item::Enum { name, .. }, _) => {
+                self.push("", format!("`{name}` debería ser un `enum`"))
+            }
+            (Item::Struct { name, .. }, _) => {
+                self.push("", format!("`{name}` debería ser un `struct`"))
+            }
+            (Item::Typedef(declaration), _) => {
+                let message = if self.reveal {
+                    format!(
+                        "`{}` debería ser otro nombre de `{}`",
+                        declaration.name,
+                        type_name(&declaration.ty, declaration.pointers)
+                    )
+                } else {
+                    format!("`{}` debería ser un `typedef`", declaration.name)
+                };
+                self.push("", message)
+            }
+            _ => {}
+        }
+    }
+
+    fn function(&mut self, reference: &Function, submitted: &Function) {
+        let name = &reference.name;
+        if (&reference.ret, reference.ret_pointers) != (&submitted.ret, submitted.ret_pointers) {
+            let message = if self.reveal {
+                let ret = type_name(&reference.ret, reference.ret_pointers);
+                format!("`{name}` debería devolver `{ret}`")
+            } else {
+                format!("el tipo que devuelve `{name}` no es el esperado")
+            };
+            self.push("", message);
+        }
+        if reference.params.len() != submitted.params.len() {
+            let count = reference.params.len();
+            let plural = if count == 1 { "" } else { "s" };
+            let message = if self.reveal {
+                format!("`{name}` debería tener {count} parámetro{plural}")
+            } else {
+                format!("el número de parámetros de `{name}` no es el esperado")
+            };
+            self.push("", message);
+        }
+
+        let outer = self.names.clone();
+        let definition = reference.body.is_some();
+        for (n, (r, s)) in reference.params.iter().zip(&submitted.params).enumerate() {
+            if (&r.ty, r.pointers, r.dims.len()) != (&s.ty, s.pointers, s.dims.len()) {
+                let n = n + 1;
+                let message = if self.reveal {
+                    let ty = type_name(&r.ty, r.pointers) + &"[]".repeat(r.dims.len());
+                    format!("el parámetro {n} de `{name}` debería ser de tipo `{ty}`")
+                } else {
+                    format!("el tipo del parámetro {n} de `{name}` no es el esperado")
+                };
+                self.push("", message);
+            }
+            // En los prototipos el nombre de los parámetros no importa.
+            if definition && r.name != s.name {
+                if self.renaming {
+                    self.names.insert(s.name.clone(), r.name.clone());
+                } else {
+                    let n = n + 1;
+                    let param = &r.name;
+                    let message = if self.reveal {
+                        format!("el parámetro {n} de `{name}` debería llamarse `{param}`")
+                    } else {
+                        format!("el nombre del parámetro {n} de `{name}` no es el esperado")
+                    };
+                    self.push("", message);
+                }
+            }
+        }
+        if let (Some(r), Some(s)) = (&reference.body, &submitted.body) {
+            self.block(&format!("En `{name}`"), r, s);
+        }
+        self.names = outer;
+    }
+
+    fn declaration(&mut self, context: &str, reference: &Declaration, submitted: &Declaration) {
+        let name = &reference.name;
+        if (&reference.ty, reference.pointers) != (&submitted.ty, submitted.pointers) {
+            let message = if self.reveal {
+                let ty = type_name(&reference.ty, reference.pointers);
+                format!("`{name}` debería ser de tipo `{ty}`")
+            } else {
+                format!("el tipo de `{name}` no es el esperado")
+            };
+            self.push(context, message);
+        }
+        let same_dims = reference.dims.len() == submitted.dims.len()
+            && reference
+                .dims
+                .iter()
+                .zip(&submitted.dims)
+                .all(|(r, s)| self.same_expr(r, s));
+        if !same_dims {
+            let message = if reference.dims.is_empty() {
+                format!("`{name}` no debería ser un array")
+            } else if !self.reveal {
+                format!("el tamaño de `{name}` no es el esperado")
+            } else {
+                format!(
+                    "`{name}` debería tener tamaño `{}`",
+                    dims_text(&reference.dims)
+                )
+            };
+            self.push(context, message);
+        }
+        match (&reference.init, &submitted.init) {
+            (Some(_), None) => self.push(context, format!("falta inicializar `{name}`")),
+            (None, Some(_)) => self.push(context, format!("`{name}` no debería inicializarse")),
+            (Some(r), Some(s)) if !self.same_init(r, s) => self.push(
+                context,
+                format!("el valor inicial de `{name}` no es el esperado"),
+            ),
+            _ => {}
+        }
+    }
+
+    /// Declaraciones seguidas: se emparejan por nombre y, con `renaming`, por forma.
+    /// `missing` y `extra` empiezan los mensajes ("falta declarar", "sobra el campo"...).
+    fn run(
+        &mut self,
+        context: &str,
+        reference: &[&Declaration],
+        submitted: &[&Declaration],
+        (missing, extra): (&str, &str),
+    ) {
+        let mut pending: Vec<&Declaration> = submitted.to_vec();
+        let mut unmatched = Vec::new();
+        for r in reference {
+            match pending.iter().position(|s| s.name == r.name) {
+                Some(j) => {
+                    let s = pending.remove(j);
+                    self.declaration(context, r, s);
+                }
+                None => unmatched.push(*r),
+            }
+        }
+        for r in unmatched {
+            match pending
+                .iter()
+                .position(|s| self.renaming && self.same_shape(r, s))
+            {
+                Some(j) => {
+                    let s = pending.remove(j);
+                    self.names.insert(s.name.clone(), r.name.clone());
+                }
+                None => self.push(context, format!("{missing} `{}`", r.name)),
+            }
+        }
+        for s in pending {
+            self.push(context, format!("{extra} `{}`", s.name));
+        }
+    }
+
+    /// Compara dos bloques en orden; se detiene en la primera sentencia distinta.
+    fn block(&mut self, context: &str, reference: &[Stmt], submitted: &[Stmt]) -> bool {
+        const DECLARE: (&str, &str) = ("falta declarar", "sobra la declaración de");
+        let before = self.out.len();
+        let reference = segments(reference);
+        let submitted = segments(submitted);
+        let (mut i, mut j) = (0, 0);
+        while i < reference.len() && j < submitted.len() {
+            match (&reference[i], &submitted[j]) {
+                (Segment::Decls(r), Segment::Decls(s)) => self.run(context, r, s, DECLARE),
+                (Segment::Stmt(r), Segment::Stmt(s)) => {
+                    if !self.stmt(context, r, s) {
+                        return false;
+                    }
+                }
+                // Declaraciones de más o de menos: se señalan y se sigue comparando.
+                (Segment::Decls(r), Segment::Stmt(_)) => {
+                    self.run(context, r, &[], DECLARE);
+                    i += 1;
+                    continue;
+                }
+                (Segment::Stmt(_), Segment::Decls(s)) => {
+                    self.run(context, &[], s, DECLARE);
+                    j += 1;
+                    continue;
+                }
+            }
+            i += 1;
+            j += 1;
+        }
+        for segment in &reference[i..] {
+            match segment {
+                Segment::Decls(r) => self.run(context, r, &[], DECLARE),
+                Segment::Stmt(stmt) => {
+                    self.push(context, format!("falta {}", describe_stmt(stmt)));
+                    break;
+                }
+            }
+        }
+        for segment in &submitted[j..] {
+            match segment {
+                Segment::Decls(s) => self.run(context, &[], s, DECLARE),
+                Segment::Stmt(stmt) => {
+                    self.push(context, format!("sobra {}", describe_stmt(stmt)));
+                    break;
+                }
+            }
+        }
+        self.out.len() == before
+    }
+
+    fn stmt(&mut self, context: &str, reference: &Stmt, submitted: &Stmt) -> bool {
+        let before = self.out.len();
+        match (reference, submitted) {
+            (Stmt::Expr(r), Stmt::Expr(s)) => {
+                if !self.same_expr(r, s) {
+                    self.push(context, format!("`{};` no es lo esperado", show(s)));
+                }
+            }
+            (Stmt::Block(r), Stmt::Block(s)) => {
+                self.block(context, r, s);
+            }
+            (
+                Stmt::If {
+                    cond,
+                    then,
+                    otherwise,
+                },
+                Stmt::If {
+                    cond: s_cond,
+                    then: s_then,
+                    otherwise: s_otherwise,
+                },
+            ) => {
+                self.condition(context, "del `if`", cond, s_cond);
+                let inner = format!("{context}, dentro del `if`");
+                if self.block(&inner, as_block(then), as_block(s_then)) {
+                    match (otherwise, s_otherwise) {
+                        (Some(r), Some(s)) => {
+                            let inner = format!("{context}, dentro del `else`");
+                            self.block(&inner, as_block(r), as_block(s));
+                        }
+                        (Some(_), None) => self.push(context, "falta el `else`".into()),
+                        (None, Some(_)) => self.push(context, "sobra el `else`".into()),
+                        (None, None) => {}
+                    }
+                }
+            }
+            (Stmt::While { cond, body }, Stmt::While { cond: c, body: b })
+            | (Stmt::DoWhile { body, cond }, Stmt::DoWhile { body: b, cond: c }) => {
+                self.condition(context, "del `while`", cond, c);
+                let inner = format!("{context}, dentro del `while`");
+                self.block(&inner, as_block(body), as_block(b));
+            }
+            (
+                Stmt::For {
+                    init,
+                    cond,
+                    step,
+                    body,
+                },
+                Stmt::For {
+                    init: s_init,
+                    cond: s_cond,
+                    step: s_step,
+                    body: s_body,
+                },
+            ) => {
+                self.block(&format!("{context}, en el `for`"), init, s_init);
+                self.condition(context, "del `for`", cond, s_cond);
+                if !self.same_expr(step, s_step) {
+                    self.push(
+                        context,
+                        format!(
+                            "la actualización `{}` del `for` no es la esperada",
+                            show(s_step)
+                        ),
+                    );
+                }
+                let inner = format!("{context}, dentro del `for`");
+                self.block(&inner, as_block(body), as_block(s_body));
+            }
+            (Stmt::Switch { value, body }, Stmt::Switch { value: v, body: b }) => {
+                self.condition(context, "del `switch`", value, v);
+                let inner = format!("{context}, dentro del `switch`");
+                self.block(&inner, as_block(body), as_block(b));
+            }
+            (Stmt::Case(r), Stmt::Case(s)) => match (r, s) {
+                (Some(r), Some(s)) if !self.same_expr(r, s) => {
+                    self.push(context, format!("`case {}:` no es lo esperado", show(s)))
+                }
+                (Some(_), None) | (None, Some(_)) => {
+                    let (expected, found) = (describe_stmt(reference), describe_stmt(submitted));
+                    self.push(context, format!("se esperaba {expected} y hay {found}"))
+                }
+                _ => {}
+            },
+            (Stmt::Return(r), Stmt::Return(s)) => match (r, s) {
+                (Some(r), Some(s)) if !self.same_expr(r, s) => self.push(
+                    context,
+                    format!("`return {};` no devuelve lo esperado", show(s)),
+                ),
+                (Some(_), None) => self.push(context, "falta el valor del `return`".into()),
+                (None, Some(_)) => {
+                    self.push(context, "el `return` no debería devolver nada".into())
+                }
+                _ => {}
+            },
+            (Stmt::Break, Stmt::Break) | (Stmt::Continue, Stmt::Continue) => {}
+            (Stmt::Block(r), s) => {
+                self.block(context, r, std::slice::from_ref(s));
+            }
+            (r, Stmt::Block(s)) => {
+                self.block(context, std::slice::from_ref(r), s);
+            }
+            (r, s) => {
+                let (expected, found) = (describe_stmt(r), describe_stmt(s));
+                self.push(context, format!("se esperaba {expected} y hay {found}"));
+            }
+        }
+        self.out.len() == before
+    }
+
+    fn condition(&mut self, context: &str, of: &str, reference: &[Token], submitted: &[Token]) {
+        if !self.same_expr(reference, submitted) {
+            let shown = show(submitted);
+            self.push(
+                context,
+                format!("la condición `{shown}` {of} no es la esperada"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(reference: &str, submitted: &str, renaming: bool) -> Vec<String> {
+        let reference = parse(reference, &[]).unwrap();
+        let submitted = parse(submitted, &[]).unwrap();
+        differences(&reference, &submitted, renaming, true)
+    }
+
+    #[test]
+    fn explains_what_differs() {
+        let reference = "int main() {\n  int a = 1;\n  float b;\n  a = a + 2;\n  return 0;\n}";
+        assert!(
+            diff(
+                reference,
+                "int main(){ float b; int a = 1; a = a + 2; return 0; }",
+                false
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            diff(
+                reference,
+                "int main(){ int a = 2; double b; a = a + 2; return 0; }",
+                false
+            ),
+            [
+                "En `main`: el valor inicial de `a` no es el esperado.",
+                "En `main`: `b` debería ser de tipo `float`."
+            ]
+        );
+        assert_eq!(
+            diff(
+                reference,
+                "#include <stdio.h>\nint main(){ int a = 1; float b; return 0; }",
+                false
+            ),
+            [
+                "En `main`: se esperaba una sentencia y hay un `return`.",
+                "Sobra `#include <stdio.h>`."
+            ]
+        );
+
+        let renamed = "int main() {\n  int x = 1;\n  float y;\n  x = x + 2;\n  return 0;\n}";
+        assert_eq!(diff(reference, renamed, false).len(), 5);
+        assert!(diff(reference, renamed, true).is_empty());
+    }
+
+    #[test]
+    fn checks_the_order_of_top_level_statements() {
+        assert_eq!(
+            diff("int x;\nx = 5;", "x = 5;\nint x;", false),
+            [
+                "Fuera de las funciones: falta declarar `x`.",
+                "Fuera de las funciones: sobra la declaración de `x`."
+            ]
+        );
+        let reference =
+            "int suma = 0;\nfor (int i = 0; i < 3; i++) suma += i;\nprintf(\"%d\", suma);";
+        assert!(diff(reference, reference, false).is_empty());
+        assert!(
+            !diff(
+                reference,
+                "printf(\"%d\", suma);\nint suma = 0;\nfor (int i = 0; i < 3; i++) suma += i;",
+                false
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn hides_expected_values_until_revealed() {
+        let reference = parse(
+            "#define N 10\nint doble(int x) { float y; return x * 2; }",
+            &[],
+        )
+        .unwrap();
+        let submitted = parse(
+            "#define N 5\nlong doble(char x) { int y; return x * 2; }",
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            differences(&reference, &submitted, false, false),
+            [
+                "El valor de `N` no es el esperado.",
+                "El tipo que devuelve `doble` no es el esperado.",
+                "El tipo del parámetro 1 de `doble` no es el esperado.",
+                "En `doble`: el tipo de `y` no es el esperado."
+            ]
+        );
+        assert_eq!(
+            differences(&reference, &submitted, false, true)[0],
+            "`N` debería valer `10`."
+        );
+    }
+}
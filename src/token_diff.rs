@@ -0,0 +1,187 @@
+//! Diferencias token a token entre una respuesta `normalize` y la referencia más
+//! parecida, para explicar en qué falla sin enseñar la solución antes de tiempo.
+
+use crate::lexer::{Token, TokenKind};
+
+/// Fallos (contando el actual) a partir de los que se enseñan los tokens esperados,
+/// los mismos que hacen falta para ver la solución.
+pub const REVEAL_AFTER_FAILS: u32 = 2;
+
+/// Tokens que se enumeran como mucho en cada lista.
+const MAX_LISTED: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Same,
+    /// Token de la referencia que no está en la respuesta.
+    Missing(usize),
+    /// Token de la respuesta que no está en la referencia.
+    Extra(usize),
+}
+
+/// Secuencia mínima de ediciones (subsecuencia común más larga).
+fn edits(expected: &[Token], received: &[Token]) -> Vec<Edit> {
+    let (n, m) = (expected.len(), received.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == received[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == received[j] {
+            out.push(Edit::Same);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(Edit::Extra(j));
+            j += 1;
+        } else {
+            out.push(Edit::Missing(i));
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Número de tokens que faltan o sobran.
+pub fn distance(expected: &[Token], received: &[Token]) -> usize {
+    edits(expected, received)
+        .iter()
+        .filter(|edit| **edit != Edit::Same)
+        .count()
+}
+
+fn token_text(token: &Token) -> &str {
+    match token.kind {
+        TokenKind::Newline => "⏎",
+        TokenKind::Indent => "→",
+        TokenKind::Dedent => "←",
+        _ => &token.text,
+    }
+}
+
+fn listed(tokens: &[&Token]) -> String {
+    let mut out: Vec<String> = tokens
+        .iter()
+        .take(MAX_LISTED)
+        .map(|t| format!("`{}`", token_text(t)))
+        .collect();
+    if tokens.len() > MAX_LISTED {
+        out.push("…".into());
+    }
+    out.join(" ")
+}
+
+/// Explica en qué difiere `received` de `expected`. Sin `reveal` solo se citan
+/// tokens del alumno: dónde está la primera diferencia y qué le sobra.
+pub fn describe(expected: &[Token], received: &[Token], reveal: bool) -> Vec<String> {
+    let edits = edits(expected, received);
+    let Some(first) = edits.iter().position(|edit| *edit != Edit::Same) else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+
+    // Antes de la primera diferencia todo coincide: `first` tokens de cada lado.
+    let context: Vec<&str> = received[first.saturating_sub(4)..first]
+        .iter()
+        .map(token_text)
+        .collect();
+    let place = if context.is_empty() {
+        "al principio".to_string()
+    } else {
+        format!("después de `{}`", context.join(" "))
+    };
+    let changed = edits[first..]
+        .iter()
+        .take_while(|edit| **edit != Edit::Same);
+    let (mut missing_here, mut extra_here) = (None, None);
+    for edit in changed {
+        match *edit {
+            Edit::Missing(i) => missing_here = missing_here.or(Some(&expected[i])),
+            Edit::Extra(j) => extra_here = extra_here.or(Some(&received[j])),
+            Edit::Same => {}
+        }
+    }
+    let what = match (missing_here, extra_here) {
+        (Some(wanted), Some(found)) if reveal => format!(
+            "se esperaba `{}` en lugar de `{}`",
+            token_text(wanted),
+            token_text(found)
+        ),
+        (Some(_), Some(found)) => format!("`{}` no es lo esperado", token_text(found)),
+        (None, Some(found)) => format!("sobra `{}`", token_text(found)),
+        (Some(wanted), None) if reveal => format!("falta `{}`", token_text(wanted)),
+        _ => "falta algo".into(),
+    };
+    lines.push(format!("La primera diferencia está {place}: {what}."));
+
+    let missing: Vec<&Token> = edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Edit::Missing(i) => Some(&expected[*i]),
+            _ => None,
+        })
+        .collect();
+    let extra: Vec<&Token> = edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Edit::Extra(j) => Some(&received[*j]),
+            _ => None,
+        })
+        .collect();
+    if !missing.is_empty() {
+        lines.push(if reveal {
+            format!("Faltan: {}", listed(&missing))
+        } else {
+            let count = missing.len();
+            let (verb, noun) = if count == 1 {
+                ("Falta", "token")
+            } else {
+                ("Faltan", "tokens")
+            };
+            format!("{verb} {count} {noun}.")
+        });
+    }
+    if !extra.is_empty() {
+        lines.push(format!("Sobran: {}", listed(&extra)));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use crate::model::Language;
+
+    #[test]
+    fn hides_expected_tokens_until_revealed() {
+        let expected = tokenize(Language::C, "for (i = 0; i < n; i++) x += i;");
+        let received = tokenize(Language::C, "for (i = 1; i < n; i++) x += i");
+        assert_eq!(distance(&expected, &received), 3);
+        assert_eq!(
+            describe(&expected, &received, false),
+            [
+                "La primera diferencia está después de `for ( i =`: `1` no es lo esperado.",
+                "Faltan 2 tokens.",
+                "Sobran: `1`"
+            ]
+        );
+        assert_eq!(
+            describe(&expected, &received, true),
+            [
+                "La primera diferencia está después de `for ( i =`: se esperaba `0` en lugar de `1`.",
+                "Faltan: `0` `;`",
+                "Sobran: `1`"
+            ]
+        );
+        assert!(describe(&expected, &expected, false).is_empty());
+    }
+}
@@ -1,5 +1,8 @@
 use crate::QuizApp;
-use egui::{Button, CentralPanel, Context, Frame, ScrollArea, Ui, Visuals};
+use crate::diagnostics::{Diagnostic, Severity};
+use egui::text::{CCursor, CCursorRange};
+use egui::text_edit::TextEditOutput;
+use egui::{Align, Button, CentralPanel, Color32, Context, Frame, Rect, ScrollArea, Ui, Visuals};
 use egui_code_editor::{CodeEditor, ColorTheme, Syntax};
 
 pub fn top_panel(app: &mut QuizApp, ctx: &Context, borrar: bool) {
@@ -73,25 +76,34 @@ pub fn simple_panel(
     });
 }
 
-/// Muestra un editor de solo lectura (solución).
-/// Editor de entrada con ancho fijo
+/// Marcas del editor de entrada: las líneas de `diagnostics` (con el mensaje al
+/// pasar el ratón) y, si se pide `jump_to`, la línea a la que llevar el cursor.
+#[derive(Clone, Copy, Default)]
+pub struct EditorMarks<'a> {
+    pub diagnostics: &'a [Diagnostic],
+    pub jump_to: Option<usize>,
+}
+
+/// Editor de entrada con ancho fijo, letra del tamaño de una línea monoespaciada
+/// y las `marks` del último envío.
 pub fn code_editor_input(
     ui: &mut Ui,
-    id: &str,
     width: f32,
     rows: usize,
-    fontsize: f32,
     syntax: Syntax,
     text: &mut String,
     max_height: f32,
+    marks: EditorMarks,
 ) {
     ScrollArea::vertical()
         .max_height(max_height)
         .auto_shrink([false; 2])
         .show(ui, |ui| {
             ui.set_width(width);
-            CodeEditor::default()
-                .id_source(id)
+            let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+            let fontsize = ui.fonts(|f| f.row_height(&font_id));
+            let output = CodeEditor::default()
+                .id_source("user_input")
                 .with_rows(rows)
                 .with_fontsize(fontsize)
                 .with_theme(ColorTheme::GITHUB_DARK)
@@ -99,9 +111,76 @@ pub fn code_editor_input(
                 .with_numlines(true)
                 .vscroll(false)
                 .show(ui, text);
+            for diagnostic in marks.diagnostics {
+                mark_line(ui, &output, text, diagnostic);
+            }
+            if let Some(line) = marks.jump_to {
+                jump_to_line(ui, &output, text, line);
+            }
         });
 }
 
+/// Índice (en caracteres) del principio de la línea `line` (desde 1).
+fn line_start(text: &str, line: usize) -> Option<usize> {
+    if line == 0 || line > text.split('\n').count() {
+        return None;
+    }
+    Some(
+        text.split('\n')
+            .take(line - 1)
+            .map(|l| l.chars().count() + 1)
+            .sum(),
+    )
+}
+
+/// Rectángulo en pantalla de la línea `line` del editor.
+fn line_rect(output: &TextEditOutput, text: &str, line: usize) -> Option<Rect> {
+    let start = line_start(text, line)?;
+    let cursor = output
+        .galley
+        .pos_from_cursor(CCursor::new(start))
+        .translate(output.galley_pos.to_vec2());
+    let bounds = output.response.rect;
+    Some(Rect::from_x_y_ranges(bounds.x_range(), cursor.y_range()))
+}
+
+fn mark_line(ui: &Ui, output: &TextEditOutput, text: &str, diagnostic: &Diagnostic) {
+    let Some(rect) = line_rect(output, text, diagnostic.line) else {
+        return;
+    };
+    let color = match diagnostic.severity {
+        Severity::Error => Color32::from_rgba_unmultiplied(230, 60, 60, 45),
+        Severity::Warning => Color32::from_rgba_unmultiplied(230, 180, 40, 40),
+        Severity::Note => Color32::from_rgba_unmultiplied(80, 140, 230, 35),
+    };
+    ui.painter().rect_filled(rect, 0.0, color);
+    if ui
+        .ctx()
+        .pointer_hover_pos()
+        .is_some_and(|pos| rect.contains(pos))
+    {
+        output
+            .response
+            .clone()
+            .on_hover_text_at_pointer(&diagnostic.message);
+    }
+}
+
+fn jump_to_line(ui: &Ui, output: &TextEditOutput, text: &str, line: usize) {
+    let Some(start) = line_start(text, line) else {
+        return;
+    };
+    let mut state = output.state.clone();
+    state
+        .cursor
+        .set_char_range(Some(CCursorRange::one(CCursor::new(start))));
+    state.store(ui.ctx(), output.response.id);
+    output.response.request_focus();
+    if let Some(rect) = line_rect(output, text, line) {
+        ui.scroll_to_rect(rect, Some(Align::Center));
+    }
+}
+
 /// Editor de sólo lectura (solución) con ancho fijo
 pub fn code_editor_solution(
     ui: &mut Ui,
@@ -0,0 +1,120 @@
+//! Markdown de los bancos (enunciados y teoría): CommonMark con bloques de código
+//! resaltados e imágenes relativas a la carpeta de los bancos (`![](images/x.png)`).
+
+use egui::{Context, Id, Ui};
+use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+use std::collections::HashSet;
+
+/// Esquema con el que se registran las imágenes de los bancos en egui.
+const IMAGE_URI_PREFIX: &str = "bytes://bank/";
+
+pub fn bank_markdown(ui: &mut Ui, cache: &mut CommonMarkCache, text: &str) {
+    for path in image_refs(text) {
+        request_image(ui.ctx(), path);
+    }
+    CommonMarkViewer::new()
+        .default_implicit_uri_scheme(IMAGE_URI_PREFIX)
+        .show(ui, cache, text);
+}
+
+/// Los enunciados se escribieron como texto plano, donde cada salto de línea cuenta:
+/// fuera de los bloques de código se convierten en saltos forzados de markdown.
+pub fn prompt_markdown(prompt: &str) -> String {
+    let mut out = String::with_capacity(prompt.len() + 16);
+    let mut in_code = false;
+    let mut lines = prompt.lines().peekable();
+    while let Some(line) = lines.next() {
+        let fence = line.trim_start().starts_with("```");
+        if fence {
+            in_code = !in_code;
+        }
+        out.push_str(line);
+        let next_is_text = lines
+            .peek()
+            .is_some_and(|next| !next.trim().is_empty() && !next.trim_start().starts_with("```"));
+        if !in_code && !fence && !line.trim().is_empty() && next_is_text {
+            out.push_str("  ");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Rutas relativas de las imágenes (`![alt](ruta)`) del texto; las URL con esquema se ignoran.
+fn image_refs(text: &str) -> Vec<&str> {
+    let mut refs = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("![") {
+        rest = &rest[start + 2..];
+        let Some(close) = rest.find("](") else {
+            break;
+        };
+        rest = &rest[close + 2..];
+        let Some(end) = rest.find(')') else {
+            break;
+        };
+        // `![alt](ruta "título")`: el título no forma parte de la ruta.
+        let target = rest[..end].split_whitespace().next().unwrap_or_default();
+        if !target.is_empty() && !target.contains("://") && !target.starts_with("data:") {
+            refs.push(target.trim_start_matches("./"));
+        }
+        rest = &rest[end..];
+    }
+    refs
+}
+
+/// Registra los bytes de la imagen en egui la primera vez que se cita.
+fn request_image(ctx: &Context, path: &str) {
+    let requested_id = Id::new("bank_images_requested");
+    let first_time = ctx.data_mut(|data| {
+        data.get_temp_mut_or_default::<HashSet<String>>(requested_id)
+            .insert(path.to_string())
+    });
+    if !first_time {
+        return;
+    }
+    let uri = format!("{IMAGE_URI_PREFIX}{path}");
+
+    #[cfg(not(target_arch = "wasm32"))]
+    match crate::data::bank_image(path) {
+        Some(bytes) => ctx.include_bytes(uri, bytes),
+        None => log::warn!("Imagen no encontrada en los bancos: {path}"),
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let ctx = ctx.clone();
+        let path = path.to_string();
+        wasm_bindgen_futures::spawn_local(async move {
+            match crate::data::fetch_bank_image(&path).await {
+                Ok(Some(bytes)) => {
+                    ctx.include_bytes(uri, bytes);
+                    ctx.request_repaint();
+                }
+                Ok(None) => log::warn!("Imagen no encontrada en los bancos: {path}"),
+                Err(err) => log::warn!("No se pudo descargar la imagen {path}: {err}"),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_relative_images_only() {
+        let text = "Mira ![memoria](images/ptr.png \"punteros\") y ![](./images/a.jpg)\n\
+                    ![web](https://example.com/x.png) `![no](cerrada`";
+        assert_eq!(image_refs(text), ["images/ptr.png", "images/a.jpg"]);
+    }
+
+    #[test]
+    fn prompt_keeps_line_breaks_outside_code() {
+        let prompt = "Fila 1: a\nFila 2: b\n\n```c\nint x;\nint y;\n```\nFin";
+        assert_eq!(
+            prompt_markdown(prompt),
+            "Fila 1: a  \nFila 2: b\n\n```c\nint x;\nint y;\n```\nFin\n"
+        );
+    }
+}
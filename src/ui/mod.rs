@@ -1,5 +1,6 @@
 mod helpers;
 pub mod layout;
+pub mod markdown;
 pub mod views;
 
 use crate::app::QuizApp;
@@ -11,9 +12,18 @@ use layout::{bottom_panel, top_panel};
 impl App for QuizApp {
     fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
         self.poll_remote_judge_result();
-        if self.is_remote_judge_pending() {
+        self.poll_capabilities();
+        self.poll_banks_prefetch();
+        if self.is_remote_judge_pending()
+            || self.is_capabilities_check_pending()
+            || self.is_banks_prefetch_pending()
+        {
             ctx.request_repaint_after(std::time::Duration::from_millis(120));
         }
+        if self.exam.is_some() {
+            self.tick_exam(ctx.input(|i| i.time));
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
 
         // BOTÓN SUPERIOR DE REINICIAR y CAMBIAR LENGUAJE (solo visible durante el quiz y resumen)
         if matches!(self.state, AppState::Quiz | AppState::Summary) {
@@ -37,10 +47,13 @@ impl App for QuizApp {
             AppState::Welcome => views::welcome::ui_welcome(self, ctx),
             AppState::ModuleMenu => views::module_menu::ui_module_menu(self, ctx),
             AppState::LevelMenu => views::level_menu::ui_level_menu(self, ctx),
-            AppState::Quiz => views::quiz::ui_quiz(self, ctx),
+            AppState::Quiz | AppState::Exam => views::quiz::ui_quiz(self, ctx),
             AppState::Summary => views::summary::ui_summary_view(self, ctx),
             AppState::LevelSummary => views::level_summary::ui_level_summary(self, ctx),
             AppState::LevelTheory => views::level_theory::ui_level_theory(self, ctx),
+            AppState::BankError => views::bank_error::ui_bank_error(self, ctx),
+            AppState::ExamSetup => views::exam::ui_exam_setup(self, ctx),
+            AppState::ExamReport => views::exam::ui_exam_report(self, ctx),
         }
 
         if self.confirm_reset {
@@ -49,6 +62,9 @@ impl App for QuizApp {
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Durante un repaso o un examen se guarda la posición del recorrido normal.
+        self.swap_session_position();
         set_value(storage, APP_KEY, self);
+        self.swap_session_position();
     }
 }
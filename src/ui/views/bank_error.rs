@@ -0,0 +1,65 @@
+use crate::QuizApp;
+use crate::ui::layout::centered_panel;
+use egui::{Button, Context, RichText, ScrollArea};
+
+pub fn ui_bank_error(app: &mut QuizApp, ctx: &Context) {
+    centered_panel(ctx, 360.0, 640.0, |ui| {
+        ui.heading("⚠ No se pudo cargar el banco de preguntas");
+        ui.add_space(12.0);
+
+        match &app.bank_error {
+            Some(err) => {
+                ui.label(RichText::new(err.location()).strong());
+                if let Some(id) = &err.question_id {
+                    ui.label(format!("Pregunta: {id}"));
+                }
+                ui.add_space(8.0);
+                ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    ui.label(
+                        RichText::new(&err.message)
+                            .monospace()
+                            .color(ui.visuals().error_fg_color),
+                    );
+                });
+            }
+            None => {
+                ui.label("Error desconocido al cargar el banco.");
+            }
+        }
+
+        ui.add_space(16.0);
+        ui.label(
+            "Corrige el fichero y reintenta, o continúa con las preguntas integradas en la app.",
+        );
+        ui.add_space(12.0);
+
+        ui.vertical_centered(|ui| {
+            let btn_retry = ui.add_sized([260.0, 36.0], Button::new("🔄 Reintentar"));
+            ui.add_space(5.0);
+            let btn_skip = ui
+                .add_sized(
+                    [260.0, 36.0],
+                    Button::new("⏭ Cargar omitiendo preguntas rotas"),
+                )
+                .on_hover_text("Se cargan todas las preguntas válidas del fichero.");
+            ui.add_space(5.0);
+            let btn_embedded = ui.add_sized([260.0, 36.0], Button::new("📦 Usar banco integrado"));
+            ui.add_space(5.0);
+            let btn_back = ui.add_sized([260.0, 36.0], Button::new("🔙 Volver"));
+
+            if btn_retry.clicked() {
+                app.reintentar_banco();
+            }
+            if btn_skip.clicked() {
+                app.cargar_banco_omitiendo_rotas();
+            }
+            if btn_embedded.clicked() {
+                app.usar_banco_integrado();
+            }
+            if btn_back.clicked() {
+                app.bank_error = None;
+                app.salir_app();
+            }
+        });
+    });
+}
@@ -0,0 +1,232 @@
+use crate::QuizApp;
+use crate::exam::{clock, date};
+use crate::model::{AppState, Language};
+use crate::ui::layout::two_button_row;
+use egui::{CentralPanel, Context, DragValue, Grid, ScrollArea, TextEdit};
+
+/// Elección de niveles, número de preguntas, duración y semilla del examen.
+pub fn ui_exam_setup(app: &mut QuizApp, ctx: &Context) {
+    CentralPanel::default().show(ctx, |ui| {
+        let max_width = 600.0;
+        let panel_width = (ui.available_width() * 0.97).min(max_width);
+
+        ui.vertical_centered(|ui| {
+            egui::Frame::default()
+                .fill(ui.visuals().window_fill())
+                .inner_margin(egui::Margin::symmetric(16, 30))
+                .show(ui, |ui| {
+                    ui.set_width(panel_width);
+
+                    ui.heading("📝 Examen");
+                    ui.add_space(5.0);
+                    ui.label(
+                        "Sin pistas, soluciones ni teoría. Se entrega solo al acabarse el tiempo.",
+                    );
+                    ui.add_space(10.0);
+
+                    // Niveles del lenguaje actual que tienen preguntas
+                    let lang = app.selected_language.unwrap_or(Language::C);
+                    let levels: Vec<_> = app
+                        .quiz
+                        .modules
+                        .iter()
+                        .map(|module| {
+                            let levels = module
+                                .levels
+                                .iter()
+                                .map(|level| {
+                                    let count = level
+                                        .questions
+                                        .iter()
+                                        .filter(|q| q.language == lang)
+                                        .count();
+                                    (module.level_key(level), level.number, count)
+                                })
+                                .filter(|(_, _, count)| *count > 0)
+                                .collect::<Vec<_>>();
+                            (module.number, levels)
+                        })
+                        .collect();
+
+                    ScrollArea::vertical()
+                        .id_salt("exam_levels_scroll")
+                        .max_height(260.0)
+                        .show(ui, |ui| {
+                            for (module_number, module_levels) in &levels {
+                                if module_levels.is_empty() {
+                                    continue;
+                                }
+                                ui.label(format!("📅 Semana {module_number}"));
+                                ui.horizontal_wrapped(|ui| {
+                                    for (key, level_number, count) in module_levels {
+                                        let selected = &mut app.exam_config.levels;
+                                        let mut checked = selected.contains(key);
+                                        if ui
+                                            .checkbox(
+                                                &mut checked,
+                                                format!("Nivel {level_number} ({count})"),
+                                            )
+                                            .changed()
+                                        {
+                                            if checked {
+                                                selected.push(key.clone());
+                                            } else {
+                                                selected.retain(|k| k != key);
+                                            }
+                                        }
+                                    }
+                                });
+                            }
+                        });
+
+                    ui.add_space(10.0);
+                    let available = app.exam_pool().len();
+                    Grid::new("exam_config_grid")
+                        .spacing([8.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label("Preguntas:");
+                            ui.add(DragValue::new(&mut app.exam_config.questions).range(1..=100));
+                            ui.end_row();
+                            ui.label("Duración (min):");
+                            ui.add(DragValue::new(&mut app.exam_config.minutes).range(1..=240));
+                            ui.end_row();
+                            ui.label("Semilla:");
+                            ui.add(
+                                TextEdit::singleline(&mut app.exam_config.seed)
+                                    .desired_width(200.0),
+                            )
+                            .on_hover_text(
+                                "Con la misma semilla todos reciben las mismas preguntas.",
+                            );
+                            ui.end_row();
+                        });
+                    ui.add_space(5.0);
+                    ui.label(format!(
+                        "Se sortearán {} de {available} preguntas disponibles.",
+                        app.exam_config.questions.min(available)
+                    ));
+
+                    ui.add_space(10.0);
+                    let (empezar, volver) =
+                        two_button_row(ui, panel_width * 0.8, "▶ Empezar examen", "🔙 Volver");
+                    if empezar {
+                        app.empezar_examen(ctx.input(|i| i.time));
+                    }
+                    if volver {
+                        app.state = AppState::Welcome;
+                        app.message.clear();
+                    }
+
+                    if !app.message.is_empty() {
+                        ui.add_space(8.0);
+                        ui.label(&app.message);
+                    }
+
+                    // Exámenes anteriores en este lenguaje
+                    let previous: Vec<_> = app
+                        .exam_results
+                        .iter()
+                        .rev()
+                        .filter(|r| r.language == lang)
+                        .take(5)
+                        .collect();
+                    if !previous.is_empty() {
+                        ui.add_space(12.0);
+                        ui.separator();
+                        ui.label("Exámenes anteriores:");
+                        for result in previous {
+                            ui.label(format!(
+                                "{} · semilla \"{}\" · {} / 10 ({} de {})",
+                                date(result.day),
+                                result.seed,
+                                result.grade(),
+                                result.solved(),
+                                result.answers.len()
+                            ));
+                        }
+                    }
+                });
+        });
+    });
+}
+
+/// Informe del último examen entregado.
+pub fn ui_exam_report(app: &mut QuizApp, ctx: &Context) {
+    CentralPanel::default().show(ctx, |ui| {
+        let max_width = 600.0;
+        let panel_width = (ui.available_width() * 0.97).min(max_width);
+
+        ui.vertical_centered(|ui| {
+            egui::Frame::default()
+                .fill(ui.visuals().window_fill())
+                .inner_margin(egui::Margin::symmetric(16, 30))
+                .show(ui, |ui| {
+                    ui.set_width(panel_width / 1.5);
+
+                    ui.heading("Resultado del examen");
+                    ui.add_space(10.0);
+
+                    if let Some(result) = app.exam_results.last() {
+                        ui.heading(format!(
+                            "Nota: {} / 10 ({} de {} preguntas)",
+                            result.grade(),
+                            result.solved(),
+                            result.answers.len()
+                        ));
+                        ui.label(format!(
+                            "Tiempo: {} de {}{}",
+                            clock(result.used_secs),
+                            clock(result.time_limit_secs),
+                            if result.timed_out { " (agotado)" } else { "" }
+                        ));
+                        ui.add_space(10.0);
+
+                        ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                            Grid::new("exam_results_grid")
+                                .striped(true)
+                                .spacing([8.0, 0.0])
+                                .show(ui, |ui| {
+                                    ui.label("#");
+                                    ui.label("Semana");
+                                    ui.label("Nivel");
+                                    ui.label("Intentos");
+                                    ui.label("Estado");
+                                    ui.end_row();
+
+                                    for (i, answer) in result.answers.iter().enumerate() {
+                                        ui.label((i + 1).to_string());
+                                        ui.label(answer.module.to_string());
+                                        ui.label(answer.level.to_string());
+                                        ui.label(answer.attempts.to_string());
+                                        ui.label(if answer.solved {
+                                            "✅ Correcta"
+                                        } else if answer.attempts > 0 {
+                                            "❌ Fallida"
+                                        } else {
+                                            "❌ Sin responder"
+                                        });
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+
+                    if !app.message.is_empty() {
+                        ui.add_space(8.0);
+                        ui.label(&app.message);
+                    }
+
+                    ui.add_space(10.0);
+                    let (otro, inicio) =
+                        two_button_row(ui, panel_width / 1.5, "📝 Otro examen", "🏠 Inicio");
+                    if otro {
+                        app.abrir_examen();
+                    }
+                    if inicio {
+                        app.state = AppState::Welcome;
+                        app.message.clear();
+                    }
+                });
+        });
+    });
+}
@@ -6,6 +6,8 @@ use egui::{Align, Button, CentralPanel, Context, RichText};
 pub fn ui_language_select(app: &mut QuizApp, ctx: &Context) {
     // Limpiamos cualquier mensaje previo
     app.message.clear();
+    app.ensure_capabilities_check();
+    app.ensure_banks_prefetch();
 
     CentralPanel::default().show(ctx, |ui| {
         // 1) Vertical centering aproximado
@@ -33,53 +35,101 @@ pub fn ui_language_select(app: &mut QuizApp, ctx: &Context) {
 
                     let button_width = ((content_width - 40.0) / 2.0).clamp(120.0, 280.0);
 
-                    ui.vertical_centered(|ui| {
-                        // Botones de lenguaje
-                        let btn_c = ui.add_sized([button_width, 40.0], Button::new("Lenguaje C"));
-                        ui.add_space(5.0);
-
-                        let btn_pseudocode =
-                            ui.add_sized([button_width, 40.0], Button::new("Pseudocódigo"));
-                        ui.add_space(5.0);
-                        let btn_kotlin = ui.add_sized([button_width, 40.0], Button::new("Kotlin"));
-                        ui.add_space(5.0);
-                        let btn_java = ui.add_sized([button_width, 40.0], Button::new("Java"));
-                        ui.add_space(5.0);
-                        let btn_rust = ui.add_sized([button_width, 40.0], Button::new("Rust"));
-                        ui.add_space(5.0);
-                        let btn_python = ui.add_sized([button_width, 40.0], Button::new("Python"));
-                        ui.add_space(5.0);
-
-                        #[cfg(not(target_arch = "wasm32"))]
-                        let btn_exit = ui.add_sized([button_width, 40.0], Button::new("Salir"));
-
-                        // Al hacer click, actualizamos estado en QuizApp
-                        if btn_c.clicked() {
-                            app.seleccionar_lenguaje(Language::C);
-                        }
-                        if btn_pseudocode.clicked() {
-                            app.seleccionar_lenguaje(Language::Pseudocode);
-                        }
-                        if btn_kotlin.clicked() {
-                            app.seleccionar_lenguaje(Language::Kotlin);
-                        }
-                        if btn_java.clicked() {
-                            app.seleccionar_lenguaje(Language::Java);
-                        }
-                        if btn_rust.clicked() {
-                            app.seleccionar_lenguaje(Language::Rust);
-                        }
-                        if btn_python.clicked() {
-                            app.seleccionar_lenguaje(Language::Python);
-                        }
-                        #[cfg(not(target_arch = "wasm32"))]
-                        if btn_exit.clicked() {
-                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                        }
+                    // Mientras se descargan los bancos externos no se puede elegir lenguaje.
+                    let banks_loading = app.is_banks_prefetch_pending();
+                    if banks_loading {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Cargando bancos de preguntas…");
+                        });
+                        ui.add_space(8.0);
+                    }
+
+                    ui.add_enabled_ui(!banks_loading, |ui| {
+                        ui.vertical_centered(|ui| {
+                            // Botones de lenguaje (marcados con ⚠ si falta su toolchain)
+                            let btn_c =
+                                language_button(ui, app, button_width, "Lenguaje C", Language::C);
+                            ui.add_space(5.0);
+
+                            let btn_pseudocode = language_button(
+                                ui,
+                                app,
+                                button_width,
+                                "Pseudocódigo",
+                                Language::Pseudocode,
+                            );
+                            ui.add_space(5.0);
+                            let btn_kotlin =
+                                language_button(ui, app, button_width, "Kotlin", Language::Kotlin);
+                            ui.add_space(5.0);
+                            let btn_java =
+                                language_button(ui, app, button_width, "Java", Language::Java);
+                            ui.add_space(5.0);
+                            let btn_rust =
+                                language_button(ui, app, button_width, "Rust", Language::Rust);
+                            ui.add_space(5.0);
+                            let btn_python =
+                                language_button(ui, app, button_width, "Python", Language::Python);
+                            ui.add_space(5.0);
+
+                            #[cfg(not(target_arch = "wasm32"))]
+                            let btn_exit = ui.add_sized([button_width, 40.0], Button::new("Salir"));
+
+                            // Al hacer click, actualizamos estado en QuizApp
+                            if btn_c.clicked() {
+                                app.seleccionar_lenguaje(Language::C);
+                            }
+                            if btn_pseudocode.clicked() {
+                                app.seleccionar_lenguaje(Language::Pseudocode);
+                            }
+                            if btn_kotlin.clicked() {
+                                app.seleccionar_lenguaje(Language::Kotlin);
+                            }
+                            if btn_java.clicked() {
+                                app.seleccionar_lenguaje(Language::Java);
+                            }
+                            if btn_rust.clicked() {
+                                app.seleccionar_lenguaje(Language::Rust);
+                            }
+                            if btn_python.clicked() {
+                                app.seleccionar_lenguaje(Language::Python);
+                            }
+                            #[cfg(not(target_arch = "wasm32"))]
+                            if btn_exit.clicked() {
+                                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                            }
+                        })
                     });
 
                     ui.add_space(16.0);
 
+                    if app.is_capabilities_check_pending() {
+                        ui.label(RichText::new("Comprobando compiladores disponibles…").weak());
+                    }
+                    let unavailable: Vec<&str> = [
+                        ("C / Pseudocódigo", Language::C),
+                        ("Kotlin", Language::Kotlin),
+                        ("Java", Language::Java),
+                        ("Rust", Language::Rust),
+                        ("Python", Language::Python),
+                    ]
+                    .into_iter()
+                    .filter(|(_, lang)| app.language_unavailable_reason(*lang).is_some())
+                    .map(|(name, _)| name)
+                    .collect();
+                    if !unavailable.is_empty() {
+                        ui.label(
+                            RichText::new(format!(
+                                "⚠ Sin compilador en este equipo: {}.\n\
+                                 Las preguntas que ejecutan código no se podrán corregir; \
+                                 pasa el ratón por el botón para ver qué falta.",
+                                unavailable.join(", ")
+                            ))
+                            .color(ui.visuals().warn_fg_color),
+                        );
+                    }
+
                     // Comprobamos si hay una actualización pendiente
                     if app.has_update.is_none() {
                         app.has_update = match check_latest_release() {
@@ -117,3 +167,20 @@ pub fn ui_language_select(app: &mut QuizApp, ctx: &Context) {
         });
     });
 }
+
+fn language_button(
+    ui: &mut egui::Ui,
+    app: &QuizApp,
+    width: f32,
+    label: &str,
+    lang: Language,
+) -> egui::Response {
+    match app.language_unavailable_reason(lang) {
+        Some(reason) => ui
+            .add_sized([width, 40.0], Button::new(format!("{label} ⚠")))
+            .on_hover_text(format!(
+                "{reason}\nLas preguntas que ejecutan código no se podrán corregir."
+            )),
+        None => ui.add_sized([width, 40.0], Button::new(label)),
+    }
+}
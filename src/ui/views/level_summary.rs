@@ -60,10 +60,7 @@ pub fn ui_level_summary(app: &mut QuizApp, ctx: &Context) {
                                             .enumerate()
                                         {
                                             // ¿Completada según completed_ids?
-                                            let done =
-                                                q.id.as_ref()
-                                                    .map(|id| completed.contains(id))
-                                                    .unwrap_or(false);
+                                            let done = completed.contains(&q.id);
                                             let status = if done {
                                                 "✅ Correcta"
                                             } else if q.saw_solution {
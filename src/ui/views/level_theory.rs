@@ -1,8 +1,8 @@
 use crate::app::QuizApp;
 use crate::model::AppState;
 use crate::ui::layout::two_button_row;
+use crate::ui::markdown::bank_markdown;
 use eframe::egui;
-use egui_commonmark::CommonMarkViewer;
 
 pub fn ui_level_theory(app: &mut QuizApp, ctx: &egui::Context) {
     egui::CentralPanel::default().show(ctx, |ui| {
@@ -55,7 +55,7 @@ pub fn ui_level_theory(app: &mut QuizApp, ctx: &egui::Context) {
                         .max_height(text_h)
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
-                            CommonMarkViewer::new().show(ui, &mut app.cm_cache, &theory);
+                            bank_markdown(ui, &mut app.cm_cache, &theory);
                         });
 
                     ui.add_space(8.0);
@@ -89,8 +89,9 @@ pub fn ui_level_theory(app: &mut QuizApp, ctx: &egui::Context) {
                         if comenzar {
                             if let (Some(w), Some(l)) =
                                 (app.progress().current_module, app.progress().current_level)
+                                && let Some(key) = app.level_key_at(w, l)
                             {
-                                app.progress_mut().seen_level_theory.insert((w, l));
+                                app.progress_mut().seen_level_theory.insert(key);
                             }
                             app.state = AppState::Quiz; // ← NO llames continuar_quiz aquí
                             app.update_input_prefill();
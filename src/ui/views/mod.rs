@@ -1,8 +1,11 @@
+pub mod bank_error;
+pub mod exam;
 pub mod language;
 pub mod level_menu;
 pub mod level_summary;
 pub mod level_theory;
 pub mod module_menu;
+pub mod parsons;
 pub mod pending;
 pub mod quiz;
 pub mod summary;
@@ -0,0 +1,128 @@
+use crate::model::Question;
+use crate::parsons::{Placed, block_text};
+use egui::{Id, RichText, Ui};
+
+/// Línea arrastrada: posición en la lista de disponibles o en el programa.
+#[derive(Clone, Copy)]
+enum Dragged {
+    Pool(usize),
+    Answer(usize),
+}
+
+#[derive(Clone, Copy)]
+enum Target {
+    /// Insertar en esa posición del programa.
+    Answer(usize),
+    Pool,
+}
+
+/// Problema de Parsons: arrastrar las líneas disponibles al programa y ordenarlas
+/// (también con ➕ / ✖). Con `indent`, cada línea colocada lleva botones de sangría.
+pub fn parsons_input(
+    ui: &mut Ui,
+    width: f32,
+    question: &Question,
+    pool: &mut Vec<usize>,
+    answer: &mut Vec<Placed>,
+) {
+    let mut moved: Option<(Dragged, Target)> = None;
+    let indent_w =
+        ui.fonts(|f| f.glyph_width(&egui::TextStyle::Monospace.resolve(ui.style()), ' ')) * 4.0;
+    let zone = egui::Frame::default().inner_margin(egui::Margin::same(6));
+
+    ui.label("Tu programa (arrastra aquí las líneas en orden):");
+    let (_, dropped) = ui.dnd_drop_zone::<Dragged, _>(zone, |ui| {
+        ui.set_width(width);
+        ui.set_min_height(48.0);
+        for (pos, placed) in answer.iter_mut().enumerate() {
+            let row = ui
+                .horizontal(|ui| {
+                    if question.indent {
+                        if ui.small_button("◀").clicked() {
+                            placed.indent = placed.indent.saturating_sub(1);
+                        }
+                        if ui.small_button("▶").clicked() {
+                            placed.indent = (placed.indent + 1).min(8);
+                        }
+                        ui.add_space(placed.indent as f32 * indent_w);
+                    }
+                    ui.dnd_drag_source(
+                        Id::new(("parsons_answer", pos)),
+                        Dragged::Answer(pos),
+                        |ui| {
+                            ui.label(RichText::new(block_text(question, placed.block)).monospace());
+                        },
+                    );
+                    if ui
+                        .small_button("✖")
+                        .on_hover_text("Quitar del programa")
+                        .clicked()
+                    {
+                        moved = Some((Dragged::Answer(pos), Target::Pool));
+                    }
+                })
+                .response;
+
+            // Soltar encima de una línea la coloca justo antes.
+            if row.dnd_hover_payload::<Dragged>().is_some() {
+                let stroke = ui.visuals().selection.stroke;
+                ui.painter()
+                    .hline(row.rect.x_range(), row.rect.top(), stroke);
+            }
+            if let Some(payload) = row.dnd_release_payload::<Dragged>() {
+                moved = Some((*payload, Target::Answer(pos)));
+            }
+        }
+    });
+    if let Some(payload) = dropped
+        && moved.is_none()
+    {
+        moved = Some((*payload, Target::Answer(answer.len())));
+    }
+
+    ui.add_space(6.0);
+    ui.label("Líneas disponibles:");
+    let (_, dropped) = ui.dnd_drop_zone::<Dragged, _>(zone, |ui| {
+        ui.set_width(width);
+        ui.set_min_height(32.0);
+        for (pos, &block) in pool.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .small_button("➕")
+                    .on_hover_text("Añadir al final")
+                    .clicked()
+                {
+                    moved = Some((Dragged::Pool(pos), Target::Answer(usize::MAX)));
+                }
+                ui.dnd_drag_source(Id::new(("parsons_pool", pos)), Dragged::Pool(pos), |ui| {
+                    ui.label(RichText::new(block_text(question, block)).monospace());
+                });
+            });
+        }
+    });
+    if let Some(payload) = dropped {
+        moved = Some((*payload, Target::Pool));
+    }
+
+    if let Some((dragged, target)) = moved {
+        apply_move(dragged, target, pool, answer);
+    }
+}
+
+fn apply_move(dragged: Dragged, target: Target, pool: &mut Vec<usize>, answer: &mut Vec<Placed>) {
+    match (dragged, target) {
+        (Dragged::Pool(from), Target::Answer(at)) if from < pool.len() => {
+            let block = pool.remove(from);
+            answer.insert(at.min(answer.len()), Placed { block, indent: 0 });
+        }
+        (Dragged::Answer(from), Target::Answer(at)) if from < answer.len() => {
+            let placed = answer.remove(from);
+            let at = if from < at { at - 1 } else { at };
+            answer.insert(at.min(answer.len()), placed);
+        }
+        (Dragged::Answer(from), Target::Pool) if from < answer.len() => {
+            pool.push(answer.remove(from).block);
+        }
+        _ => {}
+    }
+}
@@ -1,8 +1,15 @@
 use crate::QuizApp;
+use crate::blanks::{self, Segment};
 use crate::code_utils::{c_syntax, kotlin_syntax, pseudo_syntax};
-use crate::model::{AppState, Language};
-use crate::ui::layout::{code_editor_input, code_editor_solution, two_button_row};
-use egui::{Align, CentralPanel, Context, ScrollArea};
+use crate::diagnostics::Severity;
+use crate::exam;
+use crate::model::{AppState, Language, Question, QuestionKind};
+use crate::parsons;
+use crate::ui::layout::{EditorMarks, code_editor_input, code_editor_solution, two_button_row};
+use crate::ui::markdown::{bank_markdown, prompt_markdown};
+use crate::ui::views::parsons::parsons_input;
+use egui::{Align, Button, CentralPanel, Context, RichText, ScrollArea, TextEdit};
+use std::collections::HashMap;
 
 pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
     CentralPanel::default().show(ctx, |ui| {
@@ -22,17 +29,50 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
                         app.progress().current_level,
                         app.progress().current_in_level,
                     ) {
-                        let question = app.quiz.modules[wi].levels[li].questions[qi].clone();
+                        let mut question = app.quiz.modules[wi].levels[li].questions[qi].clone();
+                        // En el examen no hay pistas ni solución.
+                        if app.exam.is_some() {
+                            question.fails = 0;
+                        }
+                        let diagnostics = app
+                            .diagnostics
+                            .get(&question.id)
+                            .cloned()
+                            .unwrap_or_default();
+                        let marks = EditorMarks {
+                            diagnostics: &diagnostics,
+                            jump_to: app.jump_to_line.take(),
+                        };
 
                         let module_number = app.quiz.modules[wi].number;
                         let level_number = li + 1;
                         let round = app.progress().round;
-                        // Ronda
-                        ui.heading(format!(
-                            "📅 Semana {} - ⭐ Nivel {}",
-                            module_number, level_number
-                        ));
-                        ui.heading(format!("🌀 Ronda {}", round));
+                        if let Some(session) = &app.exam {
+                            ui.heading(format!(
+                                "📝 Examen: pregunta {} de {}",
+                                session.current + 1,
+                                session.questions.len()
+                            ));
+                            let remaining = session.remaining_secs();
+                            let timer = RichText::new(format!("⏱ {}", exam::clock(remaining)));
+                            ui.heading(if remaining < 60 {
+                                timer.color(ui.visuals().error_fg_color)
+                            } else {
+                                timer
+                            });
+                        } else {
+                            // Ronda
+                            ui.heading(format!(
+                                "📅 Semana {} - ⭐ Nivel {}",
+                                module_number, level_number
+                            ));
+                            match &app.review {
+                                Some(session) => {
+                                    ui.heading(format!("🔁 Repaso: quedan {}", session.queue.len()))
+                                }
+                                None => ui.heading(format!("🌀 Ronda {}", round)),
+                            };
+                        }
 
                         ui.add_space(10.0);
 
@@ -62,7 +102,8 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
 
                                     ui.add_space(panel_width - 150.0);
 
-                                    if ui.button("📘 Ver teoría").clicked() {
+                                    if app.exam.is_none() && ui.button("📘 Ver teoría").clicked()
+                                    {
                                         app.open_level_theory(AppState::Quiz);
                                     }
                                 });
@@ -83,7 +124,11 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
                                     .show(ui, |ui| {
                                         ui.set_width(ui.available_width());
 
-                                        ui.label(prompt_text);
+                                        bank_markdown(
+                                            ui,
+                                            &mut app.cm_cache,
+                                            &prompt_markdown(prompt_text),
+                                        );
                                     });
 
                                 ui.separator();
@@ -105,20 +150,128 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
                         let line_h = ui.fonts(|f| f.row_height(&font_id));
                         let code_rows = min_lines;
 
-                        if question.fails >= 2 {
+                        if question.is_choice() {
+                            if question.fails >= 2 && app.progress().show_solution {
+                                if ui.button("Siguiente pregunta").clicked() {
+                                    app.avanzar_a_siguiente_pregunta();
+                                }
+                                ui.label(question.choice_solution());
+                            } else {
+                                if question.fails >= 2 && ui.button("Solución").clicked() {
+                                    app.progress_mut().show_solution = true;
+                                }
+                                choice_input(
+                                    ui,
+                                    panel_width,
+                                    &question,
+                                    &mut app.progress_mut().selected_options,
+                                );
+                            }
+                        } else if question.kind == QuestionKind::FillBlanks {
+                            if question.fails >= 2 && app.progress().show_solution {
+                                if ui.button("Siguiente pregunta").clicked() {
+                                    app.avanzar_a_siguiente_pregunta();
+                                }
+                                code_editor_solution(
+                                    ui,
+                                    panel_width,
+                                    code_rows,
+                                    line_h,
+                                    syntax,
+                                    &blanks::solution(&question),
+                                    max_input_h,
+                                );
+                            } else {
+                                if question.fails >= 2 && ui.button("Solución").clicked() {
+                                    app.progress_mut().show_solution = true;
+                                }
+                                blanks_input(
+                                    ui,
+                                    panel_width,
+                                    max_input_h,
+                                    &question,
+                                    &mut app.progress_mut().blank_inputs,
+                                );
+                            }
+                        } else if question.kind == QuestionKind::Parsons {
+                            if question.fails >= 2 && app.progress().show_solution {
+                                if ui.button("Siguiente pregunta").clicked() {
+                                    app.avanzar_a_siguiente_pregunta();
+                                }
+                                code_editor_solution(
+                                    ui,
+                                    panel_width,
+                                    code_rows,
+                                    line_h,
+                                    syntax,
+                                    &parsons::solution(&question),
+                                    max_input_h,
+                                );
+                            } else {
+                                if question.fails >= 2 && ui.button("Solución").clicked() {
+                                    app.progress_mut().show_solution = true;
+                                }
+                                ScrollArea::vertical()
+                                    .id_salt("quiz_parsons_scroll")
+                                    .max_height(max_input_h + 60.0)
+                                    .show(ui, |ui| {
+                                        let progress = app.progress_mut();
+                                        parsons_input(
+                                            ui,
+                                            panel_width,
+                                            &question,
+                                            &mut progress.parsons_pool,
+                                            &mut progress.parsons_answer,
+                                        );
+                                    });
+                            }
+                        } else if question.kind == QuestionKind::PredictOutput {
+                            code_editor_solution(
+                                ui,
+                                panel_width,
+                                8,
+                                line_h,
+                                syntax,
+                                question.snippet.as_deref().unwrap_or_default(),
+                                160.0,
+                            );
+                            ui.add_space(5.0);
+                            if question.fails >= 2 && app.progress().show_solution {
+                                if ui.button("Siguiente pregunta").clicked() {
+                                    app.avanzar_a_siguiente_pregunta();
+                                }
+                                let output = question
+                                    .expected_output
+                                    .clone()
+                                    .or_else(|| app.computed_outputs.get(&question.id).cloned())
+                                    .unwrap_or_default();
+                                ui.label(RichText::new(output).monospace());
+                            } else {
+                                if question.fails >= 2 && ui.button("Solución").clicked() {
+                                    app.progress_mut().show_solution = true;
+                                }
+                                ui.add(
+                                    TextEdit::multiline(&mut app.progress_mut().input)
+                                        .id_salt("prediction_input")
+                                        .font(egui::TextStyle::Monospace)
+                                        .desired_rows(4)
+                                        .desired_width(panel_width)
+                                        .hint_text("Salida del programa"),
+                                );
+                            }
+                        } else if question.fails >= 2 {
                             if !app.progress().show_solution {
                                 if ui.button("Solución").clicked() {
                                     app.progress_mut().show_solution = true;
                                 }
                                 code_editor_input(
                                     ui,
-                                    "user_input",
                                     panel_width,
                                     code_rows,
-                                    line_h,
                                     syntax.clone(),
                                     &mut app.progress_mut().input,
                                     max_input_h,
+                                    marks,
                                 );
                             } else {
                                 if ui.button("Siguiente pregunta").clicked() {
@@ -137,13 +290,12 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
                         } else {
                             code_editor_input(
                                 ui,
-                                "user_input",
                                 panel_width,
                                 code_rows,
-                                line_h,
                                 syntax,
                                 &mut app.progress_mut().input,
                                 max_input_h,
+                                marks,
                             );
                         }
 
@@ -166,9 +318,11 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
                         // ui.add_space(5.0);
                         //
                         // Botón de test: marcar semana completa
-                        if ui
-                            .button("⚡ Marcar nivel como completado (TEST)")
-                            .clicked()
+                        if app.review.is_none()
+                            && app.exam.is_none()
+                            && ui
+                                .button("⚡ Marcar nivel como completado (TEST)")
+                                .clicked()
                         {
                             app.complete_all_level();
                         }
@@ -181,24 +335,58 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
                         let (enviar, saltar) =
                             two_button_row(ui, panel_width, "Enviar", "Saltar pregunta");
                         if enviar && !app.is_remote_judge_pending() {
-                            let input = app.progress().input.clone();
-                            app.procesar_respuesta(&input);
+                            if question.is_choice() {
+                                app.procesar_eleccion();
+                            } else if question.kind == QuestionKind::FillBlanks {
+                                app.procesar_huecos();
+                            } else if question.kind == QuestionKind::Parsons {
+                                app.procesar_parsons();
+                            } else {
+                                let input = app.progress().input.clone();
+                                app.procesar_respuesta(&input);
+                            }
                         }
                         if saltar && !app.is_remote_judge_pending() {
                             app.saltar_pregunta();
                         }
 
-                        // Volver / ver progreso
-                        let (volver, progreso) =
-                            two_button_row(ui, panel_width, "Volver", "Ver progreso");
-                        if progreso {
-                            app.ver_progreso();
-                        }
-                        if volver {
-                            app.volver_niveles();
+                        // Volver / ver progreso; en el examen sólo se puede entregar
+                        if app.exam.is_some() {
+                            if ui
+                                .add_sized([panel_width, 36.0], Button::new("🏁 Entregar examen"))
+                                .clicked()
+                            {
+                                app.entregar_examen(false);
+                            }
+                        } else {
+                            let (volver, progreso) =
+                                two_button_row(ui, panel_width, "Volver", "Ver progreso");
+                            if progreso {
+                                app.ver_progreso();
+                            }
+                            if volver {
+                                app.volver_niveles();
+                            }
                         }
 
                         ui.add_space(8.0);
+                        for diagnostic in &diagnostics {
+                            let icon = match diagnostic.severity {
+                                Severity::Error => "⛔",
+                                Severity::Warning => "⚠",
+                                Severity::Note => "ℹ",
+                            };
+                            let place = match diagnostic.column {
+                                Some(column) => format!("{}:{column}", diagnostic.line),
+                                None => diagnostic.line.to_string(),
+                            };
+                            if ui
+                                .link(format!("{icon} Línea {place}: {}", diagnostic.message))
+                                .clicked()
+                            {
+                                app.jump_to_line = Some(diagnostic.line);
+                            }
+                        }
                         if !app.message.is_empty() {
                             ScrollArea::vertical()
                                 .id_salt("quiz_message_scroll")
@@ -208,6 +396,12 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
                                     ui.label(&app.message);
                                 });
                         }
+
+                        // Historial de envíos; en el repaso y en el examen revelaría
+                        // respuestas ya aceptadas.
+                        if app.review.is_none() && app.exam.is_none() {
+                            attempt_history(ui, app, &question);
+                        }
                     }
                 });
             });
@@ -215,3 +409,122 @@ pub fn ui_quiz(app: &mut QuizApp, ctx: &Context) {
         ui.add_space(extra_space);
     });
 }
+
+/// Envíos anteriores de la pregunta, del más reciente al más antiguo; los de
+/// preguntas con editor se pueden devolver a él.
+fn attempt_history(ui: &mut egui::Ui, app: &mut QuizApp, question: &Question) {
+    let attempts = app
+        .progress()
+        .history
+        .get(&question.id)
+        .cloned()
+        .unwrap_or_default();
+    if attempts.is_empty() {
+        return;
+    }
+    let restorable = !question.is_choice()
+        && !matches!(
+            question.kind,
+            QuestionKind::FillBlanks | QuestionKind::Parsons
+        );
+
+    ui.add_space(5.0);
+    egui::CollapsingHeader::new(format!("🕘 Historial ({})", attempts.len()))
+        .id_salt("attempt_history")
+        .show(ui, |ui| {
+            ScrollArea::vertical()
+                .id_salt("attempt_history_scroll")
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for attempt in attempts.iter().rev() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!(
+                                "{} · {} · {}",
+                                attempt.when(),
+                                attempt.verdict.label(),
+                                attempt.duration()
+                            ))
+                            .on_hover_ui(|ui| {
+                                ui.monospace(&attempt.code);
+                            });
+                            if restorable && ui.small_button("↩ Restaurar").clicked() {
+                                app.progress_mut().input = attempt.code.clone();
+                                app.message =
+                                    format!("↩ Restaurado el envío del {}.", attempt.when());
+                            }
+                        });
+                    }
+                });
+        });
+}
+
+/// Opciones de una pregunta `single_choice`/`true_false` (radio) o `multiple_choice` (casillas).
+fn choice_input(ui: &mut egui::Ui, width: f32, question: &Question, selected: &mut Vec<usize>) {
+    ui.vertical(|ui| {
+        ui.set_width(width);
+        for (i, option) in question.choice_options().iter().enumerate() {
+            let mut checked = selected.contains(&i);
+            if question.kind == QuestionKind::MultipleChoice {
+                if ui.checkbox(&mut checked, &option.text).changed() {
+                    if checked {
+                        selected.push(i);
+                    } else {
+                        selected.retain(|&s| s != i);
+                    }
+                }
+            } else if ui.radio(checked, &option.text).clicked() {
+                *selected = vec![i];
+            }
+        }
+    });
+}
+
+/// Plantilla de una pregunta `fill_blanks`: código de solo lectura con un campo por hueco.
+/// Caracteres que caben en un hueco vacío; crece con lo que se escribe.
+const MIN_BLANK_CHARS: usize = 6;
+
+fn blanks_input(
+    ui: &mut egui::Ui,
+    width: f32,
+    max_height: f32,
+    question: &Question,
+    inputs: &mut HashMap<String, String>,
+) {
+    let template = question.template.as_deref().unwrap_or_default();
+    let char_w = ui.fonts(|f| f.glyph_width(&egui::TextStyle::Monospace.resolve(ui.style()), '0'));
+    egui::Frame::default()
+        .fill(ui.visuals().extreme_bg_color)
+        .inner_margin(egui::Margin::same(8))
+        .show(ui, |ui| {
+            ScrollArea::vertical()
+                .id_salt("quiz_blanks_scroll")
+                .max_height(max_height)
+                .show(ui, |ui| {
+                    ui.set_width(width);
+                    for line in template.lines() {
+                        ui.horizontal(|ui| {
+                            ui.spacing_mut().item_spacing.x = 0.0;
+                            for segment in blanks::segments(line) {
+                                match segment {
+                                    Segment::Text(text) => {
+                                        ui.label(RichText::new(text).monospace());
+                                    }
+                                    Segment::Gap(name) => {
+                                        // El ancho no debe delatar la longitud de la respuesta.
+                                        let entry = inputs.entry(name.to_string()).or_default();
+                                        let chars = entry.chars().count().max(MIN_BLANK_CHARS) + 1;
+                                        ui.add(
+                                            TextEdit::singleline(entry)
+                                                .id_salt(("blank", name))
+                                                .font(egui::TextStyle::Monospace)
+                                                .hint_text(name)
+                                                .desired_width(chars as f32 * char_w),
+                                        );
+                                    }
+                                }
+                            }
+                        });
+                    }
+                });
+        });
+}
@@ -40,14 +40,24 @@ pub fn ui_welcome(app: &mut QuizApp, ctx: &Context) {
                         } else {
                             None
                         };
+                        let due_reviews = app.due_reviews().len();
+                        let btn_review = if due_reviews > 0 {
+                            ui.add_space(5.0);
+                            Some(ui.add_sized([btn_w, btn_h], Button::new(format!("🔁 Repaso ({due_reviews} pendientes)"))))
+                        } else {
+                            None
+                        };
                         ui.add_space(5.0);
                         let btn_start = ui.add_sized([btn_w, btn_h], Button::new("🔄 Empezar de 0"));
                         ui.add_space(5.0);
                         let btn_menu  = ui.add_sized([btn_w, btn_h], Button::new("📅 Seleccionar Modulo"));
                         ui.add_space(5.0);
+                        let btn_exam  = ui.add_sized([btn_w, btn_h], Button::new("📝 Examen"));
+                        ui.add_space(5.0);
                         let btn_exit  = ui.add_sized([btn_w, btn_h], Button::new("🔙 Volver"));
 
                         if let Some(b) = btn_cont { if b.clicked() { app.continuar_quiz(); } }
+                        if btn_review.is_some_and(|b| b.clicked()) { app.empezar_repaso(); }
                         if btn_start.clicked() {
                             if hay_guardado {
                                 app.confirm_reset = true;
@@ -56,6 +66,7 @@ pub fn ui_welcome(app: &mut QuizApp, ctx: &Context) {
                             }
                         }
                         if btn_menu.clicked() { app.abrir_menu_semanal(); }
+                        if btn_exam.clicked() { app.abrir_examen(); }
                         if btn_exit.clicked() { app.salir_app(); }
 
                         // Confirmación de reinicio
@@ -80,6 +91,30 @@ pub fn ui_welcome(app: &mut QuizApp, ctx: &Context) {
                                     .strong(),
                             );
                         }
+
+                        // Preguntas descartadas por errores en el banco
+                        if !app.skipped_questions.is_empty() {
+                            ui.add_space(10.0);
+                            let ids: Vec<&str> = app.skipped_questions
+                                .iter()
+                                .map(|e| e.question_id.as_deref().unwrap_or("sin id"))
+                                .collect();
+                            ui.label(
+                                RichText::new(format!(
+                                    "⚠ Se omitieron {} preguntas con errores: {}",
+                                    ids.len(),
+                                    ids.join(", ")
+                                ))
+                                .color(ui.visuals().warn_fg_color),
+                            )
+                            .on_hover_text(
+                                app.skipped_questions
+                                    .iter()
+                                    .map(|e| e.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join("\n"),
+                            );
+                        }
                     });
                 });
         });